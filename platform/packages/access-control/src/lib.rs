@@ -19,6 +19,22 @@ pub fn check(permitted_to: &Addr, accessed_by: &Addr) -> Result {
     }
 }
 
+/// A permission to access a resource granted to a set of users
+pub trait AccessPermission {
+    fn granted_to(&self, user: &Addr) -> bool;
+}
+
+pub fn check_permission<P>(permission: &P, accessed_by: &Addr) -> Result
+where
+    P: AccessPermission + ?Sized,
+{
+    if permission.granted_to(accessed_by) {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized {})
+    }
+}
+
 pub struct SingleUserAccess<'storage, S>
 where
     S: Deref<Target = dyn Storage + 'storage>,
@@ -67,7 +83,7 @@ mod tests {
 
     use crate::{
         error::{Error, Result},
-        SingleUserAccess,
+        AccessPermission, SingleUserAccess,
     };
 
     const NAMESPACE: &str = "my-nice-permission";
@@ -112,6 +128,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_permission_granted() {
+        let permission = [Addr::unchecked("user1"), Addr::unchecked("user2")];
+
+        super::check_permission(&permission[..], &Addr::unchecked("user2")).unwrap();
+        assert_eq!(
+            Error::Unauthorized {},
+            super::check_permission(&permission[..], &Addr::unchecked("user3")).unwrap_err(),
+        );
+    }
+
+    impl AccessPermission for [Addr] {
+        fn granted_to(&self, user: &Addr) -> bool {
+            self.contains(user)
+        }
+    }
+
     fn check_permission(granted_to: &str, asked_for: &str) -> Result {
         super::check(&Addr::unchecked(granted_to), &Addr::unchecked(asked_for))
     }
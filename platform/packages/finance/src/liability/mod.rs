@@ -81,6 +81,14 @@ impl Liability {
         self.healthy
     }
 
+    pub const fn first_liq_warn(&self) -> Percent {
+        self.first_liq_warn
+    }
+
+    pub const fn second_liq_warn(&self) -> Percent {
+        self.second_liq_warn
+    }
+
     pub const fn third_liq_warn(&self) -> Percent {
         self.third_liq_warn
     }
@@ -60,6 +60,11 @@ pub fn validate_code_id(
     })
 }
 
+/// Query the code the contract is an instance of
+pub fn code_of(querier: QuerierWrapper<'_>, contract_address: &Addr) -> Result<Code> {
+    query_info(querier, contract_address).map(|info| Code { id: info.code_id })
+}
+
 fn query_info(
     querier: QuerierWrapper<'_>,
     contract_address: &Addr,
@@ -114,6 +119,15 @@ pub mod tests {
         assert!(super::validate_code_id(querier, &sdk_testing::user(USER), CODE).is_ok());
     }
 
+    #[test]
+    fn code_of() {
+        let mut mock_querier = MockQuerier::default();
+        mock_querier.update_wasm(testing::valid_contract_handler);
+        let querier = QuerierWrapper::new(&mock_querier);
+
+        assert_eq!(Ok(CODE), super::code_of(querier, &sdk_testing::user(USER)));
+    }
+
     #[test]
     fn transparent_serde() {
         let id: CodeId = 13;
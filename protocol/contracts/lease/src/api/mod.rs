@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use currencies::{LeaseGroup, PaymentGroup};
-use finance::coin::CoinDTO;
+use currency::CurrencyDTO;
+use finance::{coin::CoinDTO, price::dto::PriceDTO};
 use position::ClosePolicyChange;
 use sdk::{
     cosmwasm_std::Addr,
@@ -25,6 +26,9 @@ pub type LeaseCoin = CoinDTO<LeaseAssetCurrencies>;
 
 pub type LpnCoinDTO = crate::finance::LpnCoinDTO;
 
+/// A price of the lease asset quoted in the LPN
+pub type AssetPriceDTO = PriceDTO<LeasePaymentCurrencies>;
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct MigrateMsg {}
@@ -49,11 +53,24 @@ pub enum ExecuteMsg {
     /// - SL is set and current LTV% >= SL% , or
     /// - TP is set and TP% > current LTV% .
     ///
+    /// The SL may be set up as trailing at a given distance above the current LTV%. Then, as the LTV%
+    /// goes below its lowest level since the set up, the SL% is moved down to keep the same distance.
+    /// The SL% never moves up. Setting or removing the SL afterwards cancels the trailing.
+    ///
+    /// The SL and TP may be set up at a price of the lease asset, quoted in the LPN, instead. The price is converted
+    /// into the equivalent LTV% against the current debt. As the debt changes, for example, on payments, the LTV%
+    /// is updated so that the trigger price remains the same. Setting the trigger at an LTV% afterwards cancels the price.
+    ///
     /// If the Liquidation% and SL% are surpassed simultaneously, and since the higher amount of liquidation and the stop-loss should be closed,
     /// the protocol should take the SL event with precedence and act accordingly.
     ///
     /// The full position close implies that a trigger is consumed and no longer valid once fired.
     ///
+    /// A trigger may be set up to close only a part of the position, 0% < part% < 100%, instead.
+    /// Such a trigger is also consumed once fired. If the resulting amount cannot be closed partially,
+    /// for example, being below the minimum transaction, or leaving a position below the minimum asset,
+    /// then a Full Close of the position occurs.
+    ///
     /// It's worth noting that since TP and SL are meant to be triggered on price changes, not past liquidations or payments,
     /// the TP% is reset if a partial liquidation or a payment takes the position LTV below the TP%.
     ChangeClosePolicy(ClosePolicyChange),
@@ -68,10 +85,151 @@ pub enum ExecuteMsg {
     /// with amount that would decrease a position less than the minimum asset parameter sent on
     /// lease open. Refer to `NewLeaseForm::position_spec`.
     ///
+    /// The amount of `PositionClose::PartialClosePercent` and `PositionClose::PartialCloseLtv`
+    /// is resolved against the current position before the checks above are performed.
+    /// Return `error::ContractError::PositionError` with `CloseLtvNotBelowCurrent` when the target
+    /// LTV of a `PositionClose::PartialCloseLtv` is not less than the current position LTV.
+    ///
     /// Note that these checks would not be performed on the total position amount if
     /// a `PositionClose::FullClose` is requested. It is executed irrespective of the amount.
     ClosePosition(PositionClose),
 
+    /// Customer initiated repayment from the position
+    ///
+    /// The `amount` of the lease asset is sold and the proceeds are applied as a repayment,
+    /// the same way as the attached funds of a `Repay` are. The position is reduced accordingly.
+    ///
+    /// The same checks are performed, and errors returned, as on a partial position close of
+    /// the same amount. Refer to `ClosePosition`.
+    RepayFromPosition {
+        amount: LeaseCoin,
+    },
+
+    /// Customer initiated position increase
+    ///
+    /// The funds should be sent attached to the message. They, and the optionally borrowed
+    /// extra amount, are swapped into the lease asset and added to the position.
+    ///
+    /// The `borrow` amount is capped, similarly to `max_ltd` on lease open, so that the position
+    /// LTV past the increase does not exceed the initial LTV of the liability. The due interest and
+    /// margin interest are paid off the borrowed amount, and nothing is borrowed if it does
+    /// not exceed them. Without `borrow` the loan remains intact so the position LTV goes down.
+    ///
+    /// Return `error::ContractError::PositionError` with `InsufficientTransactionAmount`
+    /// when the attached amount is less than the minimum transaction parameter sent on
+    /// lease open. Refer to `NewLeaseForm::position_spec`.
+    ///
+    /// Return `error::ContractError::OverdueLoanIncrease` when `borrow` is requested while
+    /// the lease has overdue interest.
+    ///
+    /// Note that the Take Profit, if set, is reset when the new LTV gets below the TP%.
+    IncreasePosition {
+        borrow: Option<LpnCoinDTO>,
+    },
+
+    /// Transfer the lease ownership to a new customer
+    ///
+    /// Only the lease customer may request it. The ownership is transferred immediately unless
+    /// `require_accept` is set. Then, the transfer remains pending until the new owner accepts it
+    /// with `AcceptOwnership`. A new request overrides the pending one.
+    ///
+    /// The finalizer is notified to index the lease under the new customer.
+    ///
+    /// Return `error::ContractError::AlreadyOwner` when the new owner is the current one.
+    TransferOwnership {
+        new_owner: Addr,
+        #[serde(default)]
+        require_accept: bool,
+    },
+
+    /// Accept a pending lease ownership transfer
+    ///
+    /// Only the new owner specified with the pending `TransferOwnership` may accept it.
+    ///
+    /// Return `error::ContractError::NoPendingOwnershipTransfer` when there is no pending transfer.
+    AcceptOwnership(),
+
+    /// Grant an operator the permission to manage the lease on behalf of the customer
+    ///
+    /// Only the lease customer may grant it. The operator is allowed to perform the specified
+    /// operations, or all of them if `permissions` is not provided. A subsequent grant to
+    /// the same operator replaces its permissions.
+    ///
+    /// The operators are revoked on a lease ownership transfer.
+    ///
+    /// Return `error::ContractError::AlreadyOwner` when the operator is the lease customer.
+    /// Return `error::ContractError::TooManyOperators` when the maximum number of operators is reached.
+    GrantOperator {
+        operator: Addr,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        permissions: Option<Vec<OperatorPermission>>,
+    },
+
+    /// Revoke an operator permission to manage the lease
+    ///
+    /// Only the lease customer may revoke it.
+    ///
+    /// Return `error::ContractError::OperatorNotFound` when the address is not an operator of the lease.
+    RevokeOperator {
+        operator: Addr,
+    },
+
+    /// Deposit funds into the lease margin buffer
+    ///
+    /// The funds should be sent attached to the message. They are held idle by the lease and,
+    /// on a liability breach that would otherwise liquidate the position, are used first to repay
+    /// the loan. The position is liquidated only if the repayment is not enough to bring it back
+    /// to the healthy zone. Only the lease customer may deposit.
+    ///
+    /// The buffer is kept in a single currency of the lease payment ones. Any leftover is returned
+    /// to the customer once the loan is fully paid, or the position is fully closed.
+    ///
+    /// Return `error::ContractError::MarginBufferCurrencyMismatch` when the attached currency is
+    /// different than the one of the current buffer.
+    /// Return `error::ContractError::PositionError` with `InsufficientTransactionAmount`
+    /// when the attached amount is less than the minimum transaction parameter sent on
+    /// lease open.
+    DepositMarginBuffer(),
+
+    /// Withdraw the whole margin buffer back to the lease customer
+    ///
+    /// Return `error::ContractError::MarginBufferEmpty` when there are no funds in the buffer.
+    WithdrawMarginBuffer(),
+
+    /// Deposit funds into the lease auto-repay budget
+    ///
+    /// The funds should be sent attached to the message. They are held idle by the lease and,
+    /// once the due interest gets overdue, are used to repay it on the next time alarm. The lease
+    /// keeps repaying from the budget until it runs out. Only the lease customer may deposit.
+    ///
+    /// The budget is kept in a single currency of the lease payment ones. Any leftover is returned
+    /// to the customer once the loan is fully paid, or the position is fully closed.
+    ///
+    /// Return `error::ContractError::AutoRepayCurrencyMismatch` when the attached currency is
+    /// different than the one of the current budget.
+    /// Return `error::ContractError::PositionError` with `InsufficientTransactionAmount`
+    /// when the attached amount is less than the minimum transaction parameter sent on
+    /// lease open.
+    DepositAutoRepay(),
+
+    /// Withdraw the whole auto-repay budget back to the lease customer
+    ///
+    /// Return `error::ContractError::AutoRepayEmpty` when there are no funds in the budget.
+    WithdrawAutoRepay(),
+
+    /// Move the lease loan over to another Lpp
+    ///
+    /// A new loan of the outstanding principal is opened in the `lpp`, and the proceeds
+    /// repay in full the loan in the current one, all in the same transaction. Any interest,
+    /// including the margin one, should be repaid beforehand. Only the lease customer may refinance.
+    ///
+    /// Return `error::ContractError::RefinanceLppMismatch` when the `lpp` is the current one, or
+    /// is not an instance of the same code. The `lpp` should also lend in the same currency.
+    /// Return `error::ContractError::RefinanceInterestDue` when there is interest due.
+    Refinance {
+        lpp: Addr,
+    },
+
     /// Close of a fully paid lease
     Close(),
 
@@ -96,24 +254,66 @@ pub enum ExecuteMsg {
     Heal(),
 }
 
+/// An operation a lease operator may perform on behalf of the customer
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum OperatorPermission {
+    /// `ExecuteMsg::ChangeClosePolicy`
+    ChangeClosePolicy,
+    /// `ExecuteMsg::ClosePosition` with any of the partial close variants
+    PartialClose,
+    /// `ExecuteMsg::ClosePosition` with `PositionClose::FullClose`
+    FullClose,
+}
+
 /// The execute message any `Finalizer` should respond to.
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug, PartialEq, Eq))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum FinalizerExecuteMsg {
-    FinalizeLease { customer: Addr },
+    FinalizeLease {
+        customer: Addr,
+    },
+    ChangeLeaseOwner {
+        customer: Addr,
+        new_customer: Addr,
+    },
+    /// A lease opened at a limit price has got its loan from the `lpp`
+    LeaseLoanOpened {
+        lpp: Addr,
+    },
+}
+
+/// The query message any `Finalizer` should respond to.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug, PartialEq, Eq))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum FinalizerQueryMsg {
+    /// Check whether a new loan of `borrow` fits into the open interest caps
+    /// of the lease `currency` and the `customer`
+    ///
+    /// Return `bool`.
+    OpenInterestFits {
+        currency: CurrencyDTO<LeaseAssetCurrencies>,
+        customer: Addr,
+        borrow: LpnCoinDTO,
+    },
 }
 
 #[cfg(test)]
 mod test {
+    use currencies::{testing::LeaseC1, Lpn};
+    use currency::CurrencyDef;
+    use finance::coin::Coin;
     use sdk::{
-        cosmwasm_std::{from_json, to_json_vec},
+        cosmwasm_std::{from_json, to_json_vec, Addr},
         schemars::_serde_json::to_string,
     };
 
     use crate::api::{
         position::{FullClose, PositionClose},
-        ExecuteMsg,
+        ExecuteMsg, OperatorPermission,
     };
 
     #[test]
@@ -131,9 +331,102 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_increase_position_representation() {
+        let msg = ExecuteMsg::IncreasePosition {
+            borrow: Some(Coin::<Lpn>::new(1000).into()),
+        };
+        let increase_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&increase_bin).expect("deserialization failed"),
+            msg
+        );
+
+        assert_eq!(
+            to_string(&msg).expect("deserialization failed"),
+            format!(
+                r#"{{"increase_position":{{"borrow":{{"amount":"1000","ticker":"{}"}}}}}}"#,
+                Lpn::ticker()
+            )
+        );
+
+        assert_eq!(
+            from_json::<ExecuteMsg>(r#"{"increase_position":{}}"#).expect("deserialization failed"),
+            ExecuteMsg::IncreasePosition { borrow: None }
+        );
+    }
+
+    #[test]
+    fn test_transfer_ownership_representation() {
+        let msg = ExecuteMsg::TransferOwnership {
+            new_owner: Addr::unchecked("owner"),
+            require_accept: false,
+        };
+        assert_eq!(
+            from_json::<ExecuteMsg>(r#"{"transfer_ownership":{"new_owner":"owner"}}"#)
+                .expect("deserialization failed"),
+            msg
+        );
+
+        let msg = ExecuteMsg::AcceptOwnership();
+        assert_eq!(
+            to_string(&msg).expect("serialization failed"),
+            r#"{"accept_ownership":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_grant_operator_representation() {
+        let msg = ExecuteMsg::GrantOperator {
+            operator: Addr::unchecked("bot"),
+            permissions: None,
+        };
+        assert_eq!(
+            from_json::<ExecuteMsg>(r#"{"grant_operator":{"operator":"bot"}}"#)
+                .expect("deserialization failed"),
+            msg
+        );
+
+        let msg = ExecuteMsg::GrantOperator {
+            operator: Addr::unchecked("bot"),
+            permissions: Some(vec![
+                OperatorPermission::ChangeClosePolicy,
+                OperatorPermission::PartialClose,
+            ]),
+        };
+        assert_eq!(
+            to_string(&msg).expect("serialization failed"),
+            r#"{"grant_operator":{"operator":"bot","permissions":["change_close_policy","partial_close"]}}"#
+        );
+
+        let msg = ExecuteMsg::RevokeOperator {
+            operator: Addr::unchecked("bot"),
+        };
+        assert_eq!(
+            to_string(&msg).expect("serialization failed"),
+            r#"{"revoke_operator":{"operator":"bot"}}"#
+        );
+    }
+
+    #[test]
+    fn test_repay_from_position_representation() {
+        let msg = ExecuteMsg::RepayFromPosition {
+            amount: Coin::<LeaseC1>::new(100).into(),
+        };
+        let repay_bin = to_json_vec(&msg).expect("serialization failed");
+        assert_eq!(
+            from_json::<ExecuteMsg>(&repay_bin).expect("deserialization failed"),
+            msg
+        );
+        assert!(to_string(&msg)
+            .expect("serialization failed")
+            .starts_with(r#"{"repay_from_position":{"amount":{"amount":"100","#));
+    }
+
     #[test]
     fn test_close_position_representation() {
-        let msg = ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose {}));
+        let msg =
+            ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose { max_slippage: None }));
         let close_bin = to_json_vec(&msg).expect("serialization failed");
         assert_eq!(
             from_json::<ExecuteMsg>(&close_bin).expect("deserialization failed"),
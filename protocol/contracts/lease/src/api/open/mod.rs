@@ -1,12 +1,10 @@
-#[cfg(feature = "skel")]
-use serde::Deserialize;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use currency::CurrencyDTO;
 pub use dex::{ConnectionParams, Ics20Channel};
 use finance::{duration::Duration, liability::Liability, percent::Percent};
 use sdk::{
-    cosmwasm_std::Addr,
+    cosmwasm_std::{Addr, Timestamp},
     schemars::{self, JsonSchema},
 };
 
@@ -14,7 +12,7 @@ use sdk::{
 use crate::error_de::ErrorDe;
 use crate::finance::LpnCoinDTO;
 
-use super::LeaseAssetCurrencies;
+use super::{AssetPriceDTO, LeaseAssetCurrencies};
 
 #[cfg(feature = "skel")]
 mod unchecked;
@@ -30,7 +28,9 @@ pub struct NewLeaseContract {
     pub dex: ConnectionParams,
     /// A contract to be notified when a lease just went into a final state
     ///
-    /// The finalizer API should provide all `FinalizerExecuteMsg` variants.
+    /// It is also notified when a lease opened at a limit price gets its loan, and
+    /// queried beforehand if the loan fits into the open interest caps.
+    /// The finalizer API should provide all `FinalizerExecuteMsg` and `FinalizerQueryMsg` variants.
     pub finalizer: Addr,
 }
 
@@ -45,6 +45,9 @@ pub struct NewLeaseForm {
     pub currency: CurrencyDTO<LeaseAssetCurrencies>,
     /// Maximum Loan-to-Downpayment percentage of the new lease, optional.
     pub max_ltd: Option<Percent>,
+    /// Maximum slippage of the opening swaps against the oracle prices, optional.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_slippage: Option<Percent>,
     /// Position parameters
     pub position_spec: PositionSpecDTO,
     /// Loan parameters
@@ -57,6 +60,66 @@ pub struct NewLeaseForm {
     pub time_alarms: Addr,
     /// The oracle contract that sends market price alerts to the lease
     pub market_price_oracle: Addr,
+    /// Postpone the opening until the lease asset price drops to a limit, optional.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_at: Option<OpenLimit>,
+    /// Split the position across further lease currencies, optional.
+    ///
+    /// The `currency` of the lease takes the part not allocated to the basket legs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub basket: Option<BasketForm>,
+}
+
+/// A limit order to open a lease
+///
+/// The downpayment is held by the lease until the lease asset price, quoted in the LPN,
+/// drops below `price`. Then the loan is requested and the lease opened at the market price.
+/// If that does not happen before `expiry`, the downpayment is sent back to the customer.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct OpenLimit {
+    pub price: AssetPriceDTO,
+    pub expiry: Timestamp,
+}
+
+/// A position split across several lease currencies
+///
+/// Each leg gets its weight of the downpayment and the loan, and the lease currency gets the rest.
+/// The position LTV is computed on the summed value of all currencies.
+#[derive(Serialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(
+    feature = "skel",
+    derive(Deserialize),
+    serde(deny_unknown_fields, try_from = "unchecked::BasketForm")
+)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(rename_all = "snake_case")]
+pub struct BasketForm {
+    pub legs: Vec<BasketLeg>,
+    /// How the basket is sold on partial closes and liquidations
+    #[serde(default)]
+    pub liquidation: BasketLiquidation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct BasketLeg {
+    pub currency: CurrencyDTO<LeaseAssetCurrencies>,
+    /// The part of the downpayment and the loan spent on this currency
+    pub weight: Percent,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(rename_all = "snake_case")]
+pub enum BasketLiquidation {
+    /// Sell the same fraction of each currency
+    #[default]
+    Proportional,
+    /// Sell the basket legs in their order first, and the lease currency last
+    Priority,
 }
 
 #[derive(Serialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -94,6 +157,55 @@ pub struct PositionSpecDTO {
     /// The minimum amount to liquidate or close. Any attempt to liquidate a smaller
     /// amount would be postponed until the amount goes above this limit
     pub min_transaction: LpnCoinDTO,
+    /// When to warn the customer ahead of an overdue interest collection, optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overdue_warnings: Option<OverdueWarnings>,
+    /// Maximum slippage of the liquidation swaps against the oracle prices, optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liquidation_max_slippage: Option<Percent>,
+    /// The direction of the leases, long if not present
+    #[serde(default, skip_serializing_if = "LeaseKind::is_long")]
+    pub kind: LeaseKind,
+}
+
+/// The direction of the lease positions
+///
+/// A long lease borrows the LPN and buys the lease currency. It is liquidated on
+/// the lease currency price falls.
+///
+/// A short lease is opened against a pool denominated in the shorted asset, i.e. the LPN
+/// is the shorted asset. It borrows the LPN and sells it into the lease currency, and is
+/// liquidated on the LPN price rises. Since these are the lease currency price falls,
+/// the position LTV, the liquidations and the price alarms are the same as of a long lease.
+/// The prices, either sent in or reported back, stay quoted in the LPN and are to be
+/// presented inverted.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(rename_all = "snake_case")]
+pub enum LeaseKind {
+    #[default]
+    Long,
+    Short,
+}
+
+impl LeaseKind {
+    pub fn is_long(&self) -> bool {
+        self == &Self::Long
+    }
+}
+
+/// Overdue interest warning levels
+///
+/// Each level specifies how long ahead of the overdue interest collection
+/// the customer is warned. The first level comes the earliest.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(feature = "skel", derive(Deserialize))]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct OverdueWarnings {
+    pub first: Duration,
+    pub second: Duration,
+    pub third: Duration,
 }
 
 #[cfg(feature = "skel")]
@@ -111,6 +223,62 @@ impl PositionSpecDTO {
             self.min_asset.currency() == self.min_transaction.currency(),
             "The currency of min asset should be the same as the currency of min transaction",
         ))
+        .and(Self::check(
+            self.overdue_warnings.is_none_or(|warnings| {
+                warnings.first > warnings.second
+                    && warnings.second > warnings.third
+                    && warnings.third > Duration::default()
+            }),
+            "The overdue warnings should be positive and each one should come before the next",
+        ))
+        .and(Self::check(
+            self.liquidation_max_slippage
+                .is_none_or(|max_slippage| max_slippage <= Percent::HUNDRED),
+            "The liquidation max slippage should not exceed 100%",
+        ))
+    }
+
+    fn check(invariant: bool, msg: &str) -> Result<(), ErrorDe> {
+        ErrorDe::broken_invariant_if::<Self>(!invariant, msg)
+    }
+}
+
+#[cfg(feature = "skel")]
+impl BasketForm {
+    #[cfg(any(test, feature = "testing"))]
+    pub fn new(legs: Vec<BasketLeg>, liquidation: BasketLiquidation) -> Self {
+        let obj = Self { legs, liquidation };
+        obj.invariant_held()
+            .expect("BasketForm invariant to be held");
+        obj
+    }
+
+    fn invariant_held(&self) -> Result<(), ErrorDe> {
+        Self::check(
+            !self.legs.is_empty(),
+            "The basket should have at least one leg",
+        )
+        .and(Self::check(
+            self.legs.iter().all(|leg| !leg.weight.is_zero()),
+            "The basket leg weights should be positive",
+        ))
+        .and(Self::check(
+            self.legs
+                .iter()
+                .try_fold(Percent::ZERO, |total, leg| {
+                    total.checked_add(leg.weight).ok()
+                })
+                .is_some_and(|total| total < Percent::HUNDRED),
+            "The basket leg weights should sum up to less than 100%",
+        ))
+        .and(Self::check(
+            self.legs.iter().enumerate().all(|(leg_idx, leg)| {
+                self.legs[..leg_idx]
+                    .iter()
+                    .all(|prev_leg| prev_leg.currency != leg.currency)
+            }),
+            "The basket leg currencies should be distinct",
+        ))
     }
 
     fn check(invariant: bool, msg: &str) -> Result<(), ErrorDe> {
@@ -125,28 +293,78 @@ impl PositionSpecDTO {
         liability: Liability,
         min_asset: LpnCoinDTO,
         min_transaction: LpnCoinDTO,
+        overdue_warnings: Option<OverdueWarnings>,
+        liquidation_max_slippage: Option<Percent>,
+        kind: LeaseKind,
     ) -> Self {
-        Self::new_unchecked(liability, min_asset, min_transaction)
+        Self::new_unchecked(
+            liability,
+            min_asset,
+            min_transaction,
+            overdue_warnings,
+            liquidation_max_slippage,
+            kind,
+        )
     }
 
     #[cfg(any(test, feature = "testing"))]
     pub fn new(liability: Liability, min_asset: LpnCoinDTO, min_transaction: LpnCoinDTO) -> Self {
-        let obj = Self::new_unchecked(liability, min_asset, min_transaction);
+        let obj = Self::new_unchecked(
+            liability,
+            min_asset,
+            min_transaction,
+            None,
+            None,
+            LeaseKind::Long,
+        );
+        obj.invariant_held()
+            .expect("PositionSpecDTO invariant to be held");
+        obj
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_overdue_warnings(self, overdue_warnings: OverdueWarnings) -> Self {
+        let obj = Self {
+            overdue_warnings: Some(overdue_warnings),
+            ..self
+        };
         obj.invariant_held()
             .expect("PositionSpecDTO invariant to be held");
         obj
     }
 
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_liquidation_max_slippage(self, liquidation_max_slippage: Percent) -> Self {
+        let obj = Self {
+            liquidation_max_slippage: Some(liquidation_max_slippage),
+            ..self
+        };
+        obj.invariant_held()
+            .expect("PositionSpecDTO invariant to be held");
+        obj
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_kind(self, kind: LeaseKind) -> Self {
+        Self { kind, ..self }
+    }
+
     #[cfg(any(test, feature = "testing", feature = "contract"))]
     fn new_unchecked(
         liability: Liability,
         min_asset: LpnCoinDTO,
         min_transaction: LpnCoinDTO,
+        overdue_warnings: Option<OverdueWarnings>,
+        liquidation_max_slippage: Option<Percent>,
+        kind: LeaseKind,
     ) -> Self {
         let obj = Self {
             liability,
             min_asset,
             min_transaction,
+            overdue_warnings,
+            liquidation_max_slippage,
+            kind,
         };
         debug_assert_eq!(Ok(()), obj.invariant_held());
         obj
@@ -160,7 +378,7 @@ mod test_position_spec {
     use finance::{coin::Coin, duration::Duration, liability::Liability, percent::Percent};
     use sdk::cosmwasm_std::{from_json, StdError};
 
-    use super::PositionSpecDTO;
+    use super::{LeaseKind, OverdueWarnings, PositionSpecDTO};
 
     type LpnCoin = Coin<Lpn>;
 
@@ -202,6 +420,68 @@ mod test_position_spec {
         assert_err(r, "'ATOM' pretending to be");
     }
 
+    #[test]
+    fn overdue_warnings() {
+        assert_load_ok(
+            spec_dto().with_overdue_warnings(OverdueWarnings {
+                first: Duration::from_days(3),
+                second: Duration::from_days(1),
+                third: Duration::from_hours(2),
+            }),
+            format!(
+                r#"{{"liability":{{"initial":650,"healthy":700,"first_liq_warn":730,"second_liq_warn":750,"third_liq_warn":780,"max":800,"recalc_time":3600000000000}},"min_asset":{{"amount":"9000000","ticker":"{lpn}"}},"min_transaction":{{"amount":"5000","ticker":"{lpn}"}},"overdue_warnings":{{"first":259200000000000,"second":86400000000000,"third":7200000000000}}}}"#,
+                lpn = Lpn::ticker()
+            ),
+        );
+    }
+
+    #[test]
+    fn overdue_warnings_out_of_order() {
+        let r = from_json(format!(
+            r#"{{"liability":{{"initial":650,"healthy":700,"first_liq_warn":730,"second_liq_warn":750,"third_liq_warn":780,"max":800,"recalc_time":3600000000000}},"min_asset":{{"amount":"9000000","ticker":"{lpn}"}},"min_transaction":{{"amount":"5000","ticker":"{lpn}"}},"overdue_warnings":{{"first":86400000000000,"second":259200000000000,"third":7200000000000}}}}"#,
+            lpn = Lpn::ticker()
+        ));
+        assert_err(r, "each one should come before the next");
+    }
+
+    #[test]
+    fn liquidation_max_slippage() {
+        assert_load_ok(
+            spec_dto().with_liquidation_max_slippage(Percent::from_permille(25)),
+            format!(
+                r#"{{"liability":{{"initial":650,"healthy":700,"first_liq_warn":730,"second_liq_warn":750,"third_liq_warn":780,"max":800,"recalc_time":3600000000000}},"min_asset":{{"amount":"9000000","ticker":"{lpn}"}},"min_transaction":{{"amount":"5000","ticker":"{lpn}"}},"liquidation_max_slippage":25}}"#,
+                lpn = Lpn::ticker()
+            ),
+        );
+    }
+
+    #[test]
+    fn liquidation_max_slippage_above_hundred() {
+        let r = from_json(format!(
+            r#"{{"liability":{{"initial":650,"healthy":700,"first_liq_warn":730,"second_liq_warn":750,"third_liq_warn":780,"max":800,"recalc_time":3600000000000}},"min_asset":{{"amount":"9000000","ticker":"{lpn}"}},"min_transaction":{{"amount":"5000","ticker":"{lpn}"}},"liquidation_max_slippage":1001}}"#,
+            lpn = Lpn::ticker()
+        ));
+        assert_err(r, "should not exceed 100%");
+    }
+
+    #[test]
+    fn short() {
+        assert_load_ok(
+            spec_dto().with_kind(LeaseKind::Short),
+            format!(
+                r#"{{"liability":{{"initial":650,"healthy":700,"first_liq_warn":730,"second_liq_warn":750,"third_liq_warn":780,"max":800,"recalc_time":3600000000000}},"min_asset":{{"amount":"9000000","ticker":"{lpn}"}},"min_transaction":{{"amount":"5000","ticker":"{lpn}"}},"kind":"short"}}"#,
+                lpn = Lpn::ticker()
+            ),
+        );
+        assert_load_ok(
+            spec_dto(),
+            format!(
+                r#"{{"liability":{{"initial":650,"healthy":700,"first_liq_warn":730,"second_liq_warn":750,"third_liq_warn":780,"max":800,"recalc_time":3600000000000}},"min_asset":{{"amount":"9000000","ticker":"{lpn}"}},"min_transaction":{{"amount":"5000","ticker":"{lpn}"}},"kind":"long"}}"#,
+                lpn = Lpn::ticker()
+            ),
+        );
+    }
+
     fn assert_load_ok<Json>(exp: PositionSpecDTO, json: Json)
     where
         Json: AsRef<[u8]>,
@@ -239,3 +519,110 @@ mod test_position_spec {
         )
     }
 }
+
+#[cfg(all(test, feature = "skel"))]
+mod test_basket {
+    use currencies::testing::{LeaseC1, LeaseC2};
+    use currency::{CurrencyDef, MemberOf};
+    use finance::percent::Percent;
+    use sdk::cosmwasm_std::{from_json, StdError};
+
+    use crate::api::LeaseAssetCurrencies;
+
+    use super::{BasketForm, BasketLeg, BasketLiquidation};
+
+    #[test]
+    fn new_valid() {
+        assert_eq!(
+            Ok(BasketForm::new(
+                vec![
+                    leg::<LeaseC1>(Percent::from_percent(30)),
+                    leg::<LeaseC2>(Percent::from_percent(20))
+                ],
+                BasketLiquidation::Priority
+            )),
+            from_json(format!(
+                r#"{{"legs":[{{"currency":"{lc1}","weight":300}},{{"currency":"{lc2}","weight":200}}],"liquidation":"priority"}}"#,
+                lc1 = LeaseC1::ticker(),
+                lc2 = LeaseC2::ticker(),
+            ))
+        );
+    }
+
+    #[test]
+    fn proportional_by_default() {
+        assert_eq!(
+            Ok(BasketForm::new(
+                vec![leg::<LeaseC1>(Percent::from_percent(30))],
+                BasketLiquidation::Proportional
+            )),
+            from_json(format!(
+                r#"{{"legs":[{{"currency":"{lc1}","weight":300}}]}}"#,
+                lc1 = LeaseC1::ticker(),
+            ))
+        );
+    }
+
+    #[test]
+    fn no_legs() {
+        assert_err(from_json(r#"{"legs":[]}"#), "at least one leg");
+    }
+
+    #[test]
+    fn zero_weight() {
+        assert_err(
+            from_json(format!(
+                r#"{{"legs":[{{"currency":"{lc1}","weight":0}}]}}"#,
+                lc1 = LeaseC1::ticker(),
+            )),
+            "should be positive",
+        );
+    }
+
+    #[test]
+    fn weights_sum_up_to_hundred() {
+        assert_err(
+            from_json(format!(
+                r#"{{"legs":[{{"currency":"{lc1}","weight":600}},{{"currency":"{lc2}","weight":400}}]}}"#,
+                lc1 = LeaseC1::ticker(),
+                lc2 = LeaseC2::ticker(),
+            )),
+            "less than 100%",
+        );
+    }
+
+    #[test]
+    fn same_currency() {
+        assert_err(
+            from_json(format!(
+                r#"{{"legs":[{{"currency":"{lc1}","weight":200}},{{"currency":"{lc1}","weight":100}}]}}"#,
+                lc1 = LeaseC1::ticker(),
+            )),
+            "should be distinct",
+        );
+    }
+
+    fn leg<C>(weight: Percent) -> BasketLeg
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeaseAssetCurrencies>,
+    {
+        BasketLeg {
+            currency: currency::dto::<C, _>(),
+            weight,
+        }
+    }
+
+    fn assert_err(r: Result<BasketForm, StdError>, msg: &str) {
+        assert!(
+            matches!( // TODO migrate to using assert_matches!() once stabilized
+                r,
+                Err(StdError::ParseErr {
+                    target_type,
+                    msg: real_msg,
+                    backtrace: _,
+                }) if target_type.contains("BasketForm") && real_msg.contains(msg)
+            )
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use serde::Deserialize;
 
-use finance::liability::Liability;
+use finance::{liability::Liability, percent::Percent};
 
 use crate::{error_de::ErrorDe, finance::LpnCoinDTO};
 
-use super::PositionSpecDTO as ValidatedPositionSpec;
+use super::{
+    BasketForm as ValidatedBasketForm, BasketLeg, BasketLiquidation, LeaseKind, OverdueWarnings,
+    PositionSpecDTO as ValidatedPositionSpec,
+};
 
 /// Bring invariant checking as a step in deserializing a PositionSpecDTO
 #[derive(Deserialize)]
@@ -13,6 +16,12 @@ pub(super) struct PositionSpecDTO {
     liability: Liability,
     min_asset: LpnCoinDTO,
     min_transaction: LpnCoinDTO,
+    #[serde(default)]
+    overdue_warnings: Option<OverdueWarnings>,
+    #[serde(default)]
+    liquidation_max_slippage: Option<Percent>,
+    #[serde(default)]
+    kind: LeaseKind,
 }
 
 impl TryFrom<PositionSpecDTO> for ValidatedPositionSpec {
@@ -23,6 +32,30 @@ impl TryFrom<PositionSpecDTO> for ValidatedPositionSpec {
             liability: value.liability,
             min_asset: value.min_asset,
             min_transaction: value.min_transaction,
+            overdue_warnings: value.overdue_warnings,
+            liquidation_max_slippage: value.liquidation_max_slippage,
+            kind: value.kind,
+        };
+        res.invariant_held().map(|_| res)
+    }
+}
+
+/// Bring invariant checking as a step in deserializing a BasketForm
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub(super) struct BasketForm {
+    legs: Vec<BasketLeg>,
+    #[serde(default)]
+    liquidation: BasketLiquidation,
+}
+
+impl TryFrom<BasketForm> for ValidatedBasketForm {
+    type Error = ErrorDe;
+
+    fn try_from(value: BasketForm) -> Result<Self, Self::Error> {
+        let res = Self {
+            legs: value.legs,
+            liquidation: value.liquidation,
         };
         res.invariant_held().map(|_| res)
     }
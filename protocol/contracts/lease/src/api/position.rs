@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use sdk::schemars::{self, JsonSchema};
 
-use super::LeaseCoin;
+use super::{AssetPriceDTO, LeaseCoin};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -11,18 +11,54 @@ use super::LeaseCoin;
 pub enum PositionClose {
     FullClose(FullClose),
     PartialClose(PartialClose),
+    PartialClosePercent(PartialClosePercent),
+    PartialCloseLtv(PartialCloseLtv),
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
-pub struct FullClose {}
+pub struct FullClose {
+    /// Maximum slippage of the close swap against the oracle prices, optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_slippage: Option<Percent>,
+}
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct PartialClose {
     pub amount: LeaseCoin,
+    /// Maximum slippage of the close swap against the oracle prices, optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_slippage: Option<Percent>,
+}
+
+/// Close a percentage of the position
+///
+/// The amount is resolved against the position amount at the time of execution.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PartialClosePercent {
+    pub percent: Percent,
+    /// Maximum slippage of the close swap against the oracle prices, optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_slippage: Option<Percent>,
+}
+
+/// Close as much of the position as to bring its LTV down to the target one
+///
+/// The amount is resolved against the position amount, the due amount and the asset price
+/// at the time of execution.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PartialCloseLtv {
+    pub ltv: Percent,
+    /// Maximum slippage of the close swap against the oracle prices, optional
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_slippage: Option<Percent>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -31,6 +67,22 @@ pub struct PartialClose {
 pub enum ChangeCmd {
     Reset,
     Set(Percent),
+    /// Set a trigger at `ltv` that closes only the `close` part of the position
+    ///
+    /// The trigger is consumed once fired. The `close` part should be above 0% and below 100%.
+    SetPartial {
+        ltv: Percent,
+        close: Percent,
+    },
+    /// Set a trigger at the lease asset price quoted in the LPN
+    ///
+    /// The price is converted into the equivalent LTV against the current debt. The LTV is kept
+    /// in sync as the debt changes, for example, on repayments, so the trigger price stays intact.
+    SetPrice(AssetPriceDTO),
+    /// Set up a trailing stop loss at the specified distance above the current position LTV
+    ///
+    /// The stop loss ratchets down as the position LTV improves. Not applicable to a take profit.
+    Trail(Percent),
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -43,10 +95,14 @@ pub struct ClosePolicyChange {
 
 #[cfg(test)]
 mod test {
-    use finance::percent::Percent;
+    use currencies::{testing::PaymentC4, Lpn};
+    use finance::{coin::Coin, percent::Percent, price};
     use sdk::cosmwasm_std;
 
-    use crate::api::position::{ChangeCmd, ClosePolicyChange};
+    use crate::api::position::{
+        ChangeCmd, ClosePolicyChange, FullClose, PartialCloseLtv, PartialClosePercent,
+        PositionClose,
+    };
 
     #[test]
     fn sl_reset() {
@@ -131,4 +187,93 @@ mod test {
             msg
         );
     }
+
+    #[test]
+    fn full_close_max_slippage() {
+        let msg = PositionClose::FullClose(FullClose {
+            max_slippage: Some(Percent::from_permille(15)),
+        });
+        const CLOSE_JSON: &str = "{ \"full_close\": { \"max_slippage\": 15 } }";
+        assert_eq!(
+            cosmwasm_std::from_json::<PositionClose>(&CLOSE_JSON).expect("deserialization failed"),
+            msg
+        );
+    }
+
+    #[test]
+    fn partial_close_percent() {
+        let msg = PositionClose::PartialClosePercent(PartialClosePercent {
+            percent: Percent::from_permille(250),
+            max_slippage: None,
+        });
+        const CLOSE_JSON: &str = "{ \"partial_close_percent\": { \"percent\": 250 } }";
+        assert_eq!(
+            cosmwasm_std::from_json::<PositionClose>(&CLOSE_JSON).expect("deserialization failed"),
+            msg
+        );
+    }
+
+    #[test]
+    fn partial_close_ltv() {
+        let msg = PositionClose::PartialCloseLtv(PartialCloseLtv {
+            ltv: Percent::from_permille(600),
+            max_slippage: None,
+        });
+        const CLOSE_JSON: &str = "{ \"partial_close_ltv\": { \"ltv\": 600 } }";
+        assert_eq!(
+            cosmwasm_std::from_json::<PositionClose>(&CLOSE_JSON).expect("deserialization failed"),
+            msg
+        );
+    }
+
+    #[test]
+    fn sl_trail() {
+        let msg = ClosePolicyChange {
+            stop_loss: Some(ChangeCmd::Trail(Percent::from_permille(50))),
+            take_profit: None,
+        };
+        const CLOSE_JSON: &str = "{ \"stop_loss\": { \"trail\": 50 } }";
+        assert_eq!(
+            cosmwasm_std::from_json::<ClosePolicyChange>(&CLOSE_JSON)
+                .expect("deserialization failed"),
+            msg
+        );
+    }
+
+    #[test]
+    fn tp_set_partial() {
+        let msg = ClosePolicyChange {
+            stop_loss: None,
+            take_profit: Some(ChangeCmd::SetPartial {
+                ltv: Percent::from_permille(400),
+                close: Percent::from_permille(500),
+            }),
+        };
+        const CLOSE_JSON: &str =
+            "{ \"take_profit\": { \"set_partial\": { \"ltv\": 400, \"close\": 500 } } }";
+        assert_eq!(
+            cosmwasm_std::from_json::<ClosePolicyChange>(&CLOSE_JSON)
+                .expect("deserialization failed"),
+            msg
+        );
+    }
+
+    #[test]
+    fn sl_set_price() {
+        let msg = ClosePolicyChange {
+            stop_loss: Some(ChangeCmd::SetPrice(
+                price::total_of(Coin::<PaymentC4>::new(2))
+                    .is(Coin::<Lpn>::new(3))
+                    .into(),
+            )),
+            take_profit: None,
+        };
+        assert_eq!(
+            cosmwasm_std::from_json::<ClosePolicyChange>(
+                cosmwasm_std::to_json_vec(&msg).expect("serialization failed")
+            )
+            .expect("deserialization failed"),
+            msg
+        );
+    }
 }
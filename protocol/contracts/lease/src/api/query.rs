@@ -6,18 +6,80 @@ use finance::{
     percent::Percent,
 };
 use sdk::{
-    cosmwasm_std::Timestamp,
+    cosmwasm_std::{Attribute, Timestamp},
     schemars::{self, JsonSchema},
 };
 
 use crate::finance::LpnCoinDTO;
 
-use super::{DownpaymentCoin, LeaseAssetCurrencies, LeaseCoin};
+use super::{
+    open::{BasketForm, LeaseKind, OpenLimit},
+    position::PositionClose,
+    AssetPriceDTO, DownpaymentCoin, LeaseAssetCurrencies, LeaseCoin, PaymentCoin,
+};
+
+pub use opened::{ClosePolicy, LiabilityStatus, LiabilityZone, SteadyPrices};
+
+/// The lease query message
+///
+/// A `StateQuery` is accepted as is, untagged, to keep the query API backward compatible.
+#[derive(Deserialize, JsonSchema)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, Debug, PartialEq, Serialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Ask for the lease asset prices at which the position crosses the liability zone
+    /// boundaries and the close policy triggers
+    ///
+    /// The prices are determined against the debt estimated at the `due_projection` ahead.
+    /// The response is `ProjectionResponse`. Supported only if the lease is in Opened state
+    /// with no ongoing transaction.
+    Projection {
+        #[serde(default, rename = "due_projection_secs")]
+        due_projection: Seconds,
+    },
+    /// Ask for the outcome of an action if it were executed now
+    ///
+    /// The action is simulated against the current oracle prices without mutating the lease.
+    /// Any payment, or position close proceeds, are estimated in the LPN at these prices.
+    /// The response is `SimulationResponse`. Supported only if the lease is in Opened state
+    /// with no ongoing transaction.
+    Simulate { action: SimulateAction },
+    /// Ask for the recorded lease history, the response is `HistoryResponse`
+    ///
+    /// The records are ordered by their sequence number, starting after `start_after`, if
+    /// provided. Only the most recent records are kept. Supported in any lease state.
+    History {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        start_after: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+    /// Ask for the lease state, the response is `StateResponse`
+    #[serde(untagged)]
+    State(StateQuery),
+}
 
-pub use opened::ClosePolicy;
+#[derive(Deserialize, JsonSchema)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, Debug, PartialEq, Serialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum SimulateAction {
+    /// A repayment with the specified amount of any of the lease payment currencies
+    Repay(PaymentCoin),
+    /// A customer initiated position close
+    ClosePosition(PositionClose),
+}
 
 #[derive(Deserialize, JsonSchema)]
-#[cfg_attr(any(test, feature = "testing"), derive(Clone, Debug, Serialize))]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, Debug, PartialEq, Serialize)
+)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct StateQuery {
     /// Ask for estimation of the due and overdue amounts and periods in that point of time
@@ -34,15 +96,30 @@ pub struct StateQuery {
 )]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum StateResponse {
+    /// The downpayment is held until the limit price is reached
+    Pending {
+        currency: CurrencyDTO<LeaseAssetCurrencies>,
+        downpayment: DownpaymentCoin,
+        open_at: OpenLimit,
+    },
     Opening {
         currency: CurrencyDTO<LeaseAssetCurrencies>,
         downpayment: DownpaymentCoin,
         loan: LpnCoinDTO,
         loan_interest_rate: Percent,
+        /// The basket the position is split across, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        basket: Option<BasketForm>,
         in_progress: opening::OngoingTrx,
     },
     Opened {
+        /// The direction of the position, long if not present
+        #[serde(default, skip_serializing_if = "LeaseKind::is_long")]
+        kind: LeaseKind,
         amount: LeaseCoin,
+        /// The amounts of the lease currencies held on top of the `amount`, if any
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        basket: Vec<LeaseCoin>,
         loan_interest_rate: Percent,
         margin_interest_rate: Percent,
         principal_due: LpnCoinDTO,
@@ -56,18 +133,126 @@ pub enum StateResponse {
         /// It always corresponds to the requested `StateQuery::due_projection` or 0 if not present.
         #[serde(rename = "due_projection_ns")]
         due_projection: Duration,
-        close_policy: ClosePolicy,
+        close_policy: Box<ClosePolicy>,
+        /// The position liability against the debt estimated at the `validity` plus `due_projection`
+        ///
+        /// It is `None` if there is no debt, or the lease asset price is not available.
+        liability: Option<Box<LiabilityStatus>>,
         validity: Timestamp,
         in_progress: Option<opened::OngoingTrx>,
     },
     Paid {
         amount: LeaseCoin,
+        /// The amounts of the lease currencies held on top of the `amount`, if any
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        basket: Vec<LeaseCoin>,
         in_progress: Option<paid::ClosingTrx>,
     },
     Closed(),
     Liquidated(),
 }
 
+/// The lease asset price levels, quoted in the LPN, the position crosses
+///
+/// A position is at a liability zone, or triggers a close, if the asset price
+/// gets lower than or equal to the respective level. The only exception is the take profit
+/// which triggers if the price gets higher than its level.
+#[derive(Serialize)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, PartialEq, Eq, Debug, Deserialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct ProjectionResponse {
+    pub amount: LeaseCoin,
+    /// The total debt estimated at the `validity` plus `due_projection`
+    pub total_due: LpnCoinDTO,
+    /// The price levels, `None` if there is no debt
+    pub price_levels: Option<PriceLevels>,
+    #[serde(rename = "due_projection_ns")]
+    pub due_projection: Duration,
+    pub validity: Timestamp,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, PartialEq, Eq, Debug, Deserialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PriceLevels {
+    pub first_liq_warn: AssetPriceDTO,
+    pub second_liq_warn: AssetPriceDTO,
+    pub third_liq_warn: AssetPriceDTO,
+    /// The price at which the position becomes subject of a liquidation
+    pub liquidation: AssetPriceDTO,
+    pub take_profit: Option<AssetPriceDTO>,
+    pub stop_loss: Option<AssetPriceDTO>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, PartialEq, Eq, Debug, Deserialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum SimulationResponse {
+    Accepted(Box<SimulationOutcome>),
+    /// The action would be rejected, for example, the amount is below the minimum transaction
+    Rejected {
+        reason: String,
+    },
+}
+
+/// The lease state past an accepted simulated action
+#[derive(Serialize)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, PartialEq, Eq, Debug, Deserialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct SimulationOutcome {
+    /// The position amount past the action
+    pub amount: LeaseCoin,
+    /// The payment, or the position close proceeds, estimated in the LPN
+    pub payment: LpnCoinDTO,
+    /// The overdue and due loan interest paid to the Lpp
+    pub interest_paid: LpnCoinDTO,
+    /// The overdue and due margin interest paid to the Profit
+    pub margin_paid: LpnCoinDTO,
+    pub principal_paid: LpnCoinDTO,
+    /// The amount exceeding the total debt, returned to the customer
+    pub change: LpnCoinDTO,
+    /// The principal left to be repaid
+    pub principal_due: LpnCoinDTO,
+    /// The position LTV past the action, `None` if there is no debt left
+    pub ltv: Option<Percent>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, PartialEq, Eq, Debug, Deserialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct HistoryResponse {
+    pub records: Vec<HistoryRecord>,
+}
+
+/// A lease event as emitted at the time it happened
+#[derive(Serialize)]
+#[cfg_attr(
+    any(test, feature = "testing"),
+    derive(Clone, PartialEq, Eq, Debug, Deserialize)
+)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct HistoryRecord {
+    pub seq: u64,
+    /// The event type, for example, `ls-repay`
+    pub event: String,
+    pub attributes: Vec<Attribute>,
+}
+
 pub(crate) mod opening {
     #[cfg(any(test, feature = "testing"))]
     use serde::Deserialize;
@@ -88,11 +273,12 @@ pub(crate) mod opening {
 
 pub(crate) mod opened {
     use finance::percent::Percent;
+    use sdk::cosmwasm_std::Timestamp;
     #[cfg(any(test, feature = "testing"))]
     use serde::Deserialize;
     use serde::Serialize;
 
-    use crate::api::{LeaseCoin, PaymentCoin};
+    use crate::api::{AssetPriceDTO, LeaseCoin, PaymentCoin};
 
     /// The data transport type of the configured Lease close policy
     ///
@@ -106,6 +292,76 @@ pub(crate) mod opened {
     pub struct ClosePolicy {
         take_profit: Option<Percent>,
         stop_loss: Option<Percent>,
+        /// The distance of a trailing stop loss above the lowest LTV observed since it was set up
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stop_loss_trail: Option<Percent>,
+        /// The part of the position to close on take profit, if not the whole
+        #[serde(skip_serializing_if = "Option::is_none")]
+        take_profit_close: Option<Percent>,
+        /// The part of the position to close on stop loss, if not the whole
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stop_loss_close: Option<Percent>,
+        /// The price the take profit is set at, if any
+        #[serde(skip_serializing_if = "Option::is_none")]
+        take_profit_price: Option<AssetPriceDTO>,
+        /// The price the stop loss is set at, if any
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stop_loss_price: Option<AssetPriceDTO>,
+    }
+
+    /// The data transport type of the position liability
+    ///
+    /// Designed for use in query responses only!
+    #[derive(Serialize)]
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        derive(Clone, PartialEq, Eq, Debug, Deserialize)
+    )]
+    #[serde(deny_unknown_fields, rename_all = "snake_case")]
+    pub enum LiabilityStatus {
+        /// The position LTV is within a liability zone
+        Steady {
+            ltv: Percent,
+            zone: LiabilityZone,
+            /// The lease asset prices the position stays steady within
+            steady_prices: SteadyPrices,
+            /// The latest time the position is checked again at, unless the lease asset price
+            /// gets out of the `steady_prices` earlier
+            next_check: Timestamp,
+        },
+        /// The position is subject to a close, either triggered by the close policy or
+        /// a liquidation, on the next check
+        CloseDue { ltv: Percent },
+    }
+
+    /// A right-open range of LTVs the liability zone spans
+    #[derive(Serialize)]
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        derive(Clone, PartialEq, Eq, Debug, Deserialize)
+    )]
+    #[serde(deny_unknown_fields, rename_all = "snake_case")]
+    pub struct LiabilityZone {
+        /// The liquidation warning level the zone starts at, 0 if below the first one
+        pub level: u8,
+        /// The LTV the zone starts at, `None` if it starts at zero
+        pub low: Option<Percent>,
+        pub high: Percent,
+    }
+
+    /// The lease asset prices, quoted in the LPN, a position is steady within
+    ///
+    /// The range is left-open, i.e. the position is steady if the price is above `above`
+    /// and, if present, below or equal to `below_or_equal`.
+    #[derive(Serialize)]
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        derive(Clone, PartialEq, Eq, Debug, Deserialize)
+    )]
+    #[serde(deny_unknown_fields, rename_all = "snake_case")]
+    pub struct SteadyPrices {
+        pub above: AssetPriceDTO,
+        pub below_or_equal: Option<AssetPriceDTO>,
     }
 
     #[derive(Serialize)]
@@ -127,6 +383,14 @@ pub(crate) mod opened {
             close: LeaseCoin,
             in_progress: PositionCloseTrx,
         },
+        RepaymentFromPosition {
+            sell: LeaseCoin,
+            in_progress: PositionCloseTrx,
+        },
+        IncreasePosition {
+            payment: PaymentCoin,
+            in_progress: IncreasePositionTrx,
+        },
     }
 
     #[derive(Serialize)]
@@ -142,6 +406,17 @@ pub(crate) mod opened {
         TransferInFinish,
     }
 
+    #[derive(Serialize)]
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        derive(Clone, PartialEq, Eq, Debug, Deserialize)
+    )]
+    #[serde(deny_unknown_fields, rename_all = "snake_case")]
+    pub enum IncreasePositionTrx {
+        TransferOut,
+        Swap,
+    }
+
     #[derive(Serialize)]
     #[cfg_attr(
         any(test, feature = "testing"),
@@ -160,6 +435,42 @@ pub(crate) mod opened {
             Self {
                 take_profit: tp,
                 stop_loss: sl,
+                stop_loss_trail: None,
+                take_profit_close: None,
+                stop_loss_close: None,
+                take_profit_price: None,
+                stop_loss_price: None,
+            }
+        }
+
+        pub fn with_stop_loss_trail(self, trail: Option<Percent>) -> Self {
+            Self {
+                stop_loss_trail: trail,
+                ..self
+            }
+        }
+
+        pub fn with_close_parts(
+            self,
+            tp_close: Option<Percent>,
+            sl_close: Option<Percent>,
+        ) -> Self {
+            Self {
+                take_profit_close: tp_close,
+                stop_loss_close: sl_close,
+                ..self
+            }
+        }
+
+        pub fn with_prices(
+            self,
+            tp_price: Option<AssetPriceDTO>,
+            sl_price: Option<AssetPriceDTO>,
+        ) -> Self {
+            Self {
+                take_profit_price: tp_price,
+                stop_loss_price: sl_price,
+                ..self
             }
         }
     }
@@ -181,3 +492,71 @@ pub(crate) mod paid {
         TransferInFinish,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::from_json;
+
+    use crate::api::position::{FullClose, PositionClose};
+
+    use super::{QueryMsg, SimulateAction, StateQuery};
+
+    #[test]
+    fn state_query_compatible() {
+        assert_eq!(
+            QueryMsg::State(StateQuery { due_projection: 0 }),
+            from_json(r#"{}"#).unwrap()
+        );
+        assert_eq!(
+            QueryMsg::State(StateQuery {
+                due_projection: 3600
+            }),
+            from_json(r#"{"due_projection_secs":3600}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn history_query() {
+        assert_eq!(
+            QueryMsg::History {
+                start_after: None,
+                limit: None
+            },
+            from_json(r#"{"history":{}}"#).unwrap()
+        );
+        assert_eq!(
+            QueryMsg::History {
+                start_after: Some(12),
+                limit: Some(5)
+            },
+            from_json(r#"{"history":{"start_after":12,"limit":5}}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn projection_query() {
+        assert_eq!(
+            QueryMsg::Projection { due_projection: 0 },
+            from_json(r#"{"projection":{}}"#).unwrap()
+        );
+        assert_eq!(
+            QueryMsg::Projection {
+                due_projection: 3600
+            },
+            from_json(r#"{"projection":{"due_projection_secs":3600}}"#).unwrap()
+        );
+        assert!(from_json::<QueryMsg>(r#"{"projection":{"due_projection":3600}}"#).is_err());
+    }
+
+    #[test]
+    fn simulate_query() {
+        assert_eq!(
+            QueryMsg::Simulate {
+                action: SimulateAction::ClosePosition(PositionClose::FullClose(FullClose {
+                    max_slippage: None
+                }))
+            },
+            from_json(r#"{"simulate":{"action":{"close_position":{"full_close":{}}}}}"#).unwrap()
+        );
+    }
+}
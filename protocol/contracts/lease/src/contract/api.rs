@@ -1,12 +1,13 @@
 use enum_dispatch::enum_dispatch;
 
 use finance::duration::Duration;
-use sdk::cosmwasm_std::{Binary, Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
+use sdk::cosmwasm_std::{Addr, Binary, Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
 
 use crate::{
     api::{
         position::{ClosePolicyChange, PositionClose},
-        query::StateResponse,
+        query::{ProjectionResponse, SimulateAction, SimulationResponse, StateResponse},
+        LeaseCoin, LpnCoinDTO, OperatorPermission,
     },
     error::{ContractError, ContractResult},
 };
@@ -90,6 +91,24 @@ where
         querier: QuerierWrapper<'_>,
     ) -> ContractResult<StateResponse>;
 
+    fn projection(
+        self,
+        _now: Timestamp,
+        _due_projection: Duration,
+        _querier: QuerierWrapper<'_>,
+    ) -> ContractResult<ProjectionResponse> {
+        err("projection")
+    }
+
+    fn simulate(
+        self,
+        _action: SimulateAction,
+        _now: Timestamp,
+        _querier: QuerierWrapper<'_>,
+    ) -> ContractResult<SimulationResponse> {
+        err("simulate")
+    }
+
     fn reply(
         self,
         _querier: QuerierWrapper<'_>,
@@ -128,6 +147,113 @@ where
         err("close position")
     }
 
+    fn repay_from_position(
+        self,
+        _amount: LeaseCoin,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("repay from position")
+    }
+
+    fn increase_position(
+        self,
+        _borrow: Option<LpnCoinDTO>,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("increase position")
+    }
+
+    fn transfer_ownership(
+        self,
+        _new_owner: Addr,
+        _require_accept: bool,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("transfer ownership")
+    }
+
+    fn accept_ownership(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("accept ownership")
+    }
+
+    fn grant_operator(
+        self,
+        _operator: Addr,
+        _permissions: Option<Vec<OperatorPermission>>,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("grant operator")
+    }
+
+    fn revoke_operator(
+        self,
+        _operator: Addr,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("revoke operator")
+    }
+
+    fn deposit_margin_buffer(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("deposit margin buffer")
+    }
+
+    fn withdraw_margin_buffer(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("withdraw margin buffer")
+    }
+
+    fn deposit_auto_repay(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("deposit auto-repay")
+    }
+
+    fn withdraw_auto_repay(
+        self,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("withdraw auto-repay")
+    }
+
+    fn refinance(
+        self,
+        _lpp: Addr,
+        _querier: QuerierWrapper<'_>,
+        _env: Env,
+        _info: MessageInfo,
+    ) -> ContractResult<Response> {
+        err("refinance")
+    }
+
     fn close(
         self,
         _querier: QuerierWrapper<'_>,
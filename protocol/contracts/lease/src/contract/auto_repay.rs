@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use finance::coin;
+use platform::batch::Batch;
+use sdk::cosmwasm_std::Addr;
+
+use crate::{
+    api::PaymentCoin,
+    error::{ContractError, ContractResult},
+    finance::LpnCoinDTO,
+};
+
+use super::funds;
+
+/// Customer funds the lease repays the due interest from once it gets overdue
+///
+/// The funds are kept in the lease account in a single payment currency.
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[serde(transparent)]
+pub(super) struct AutoRepay(Option<PaymentCoin>);
+
+impl AutoRepay {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn budget(&self) -> Option<&PaymentCoin> {
+        self.0.as_ref()
+    }
+
+    pub fn deposit(&mut self, amount: PaymentCoin) -> ContractResult<()> {
+        match self.0 {
+            None => {
+                self.0 = Some(amount);
+                Ok(())
+            }
+            Some(ref budget) if budget.currency() == amount.currency() => {
+                self.0 = Some(coin::from_amount_ticker(
+                    budget.amount() + amount.amount(),
+                    budget.currency(),
+                ));
+                Ok(())
+            }
+            Some(ref budget) => Err(ContractError::AutoRepayCurrencyMismatch(
+                budget.currency().to_string(),
+            )),
+        }
+    }
+
+    /// Deduct `amount` from the budget leaving it empty once it runs out
+    ///
+    /// Pre: `amount` is in the currency of the budget and does not exceed it.
+    pub fn spend(&mut self, amount: &PaymentCoin) {
+        let budget = self.0.take().expect("the budget should not be empty");
+        debug_assert_eq!(budget.currency(), amount.currency());
+        debug_assert!(amount.amount() <= budget.amount());
+
+        let left = budget.amount() - amount.amount();
+        if left != 0 {
+            self.0 = Some(coin::from_amount_ticker(left, budget.currency()));
+        }
+    }
+
+    /// Take out all funds leaving the budget empty
+    pub fn take(&mut self) -> Option<PaymentCoin> {
+        self.0.take()
+    }
+
+    /// Deduct the budget from the LPN `balance` of the lease account if held in the LPN
+    pub fn exclude_from(&self, balance: LpnCoinDTO) -> LpnCoinDTO {
+        match self.0 {
+            Some(ref budget) if budget.currency() == balance.currency() => {
+                coin::from_amount_ticker(
+                    balance.amount().saturating_sub(budget.amount()),
+                    balance.currency(),
+                )
+            }
+            _ => balance,
+        }
+    }
+
+    /// Take out all funds and send them to `customer`
+    pub fn take_out_to(&mut self, customer: Addr) -> ContractResult<Batch> {
+        self.take().map_or_else(
+            || Ok(Batch::default()),
+            |budget| funds::send(&budget, customer),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currencies::testing::PaymentC3;
+    use finance::coin::{Amount, Coin};
+
+    use crate::api::PaymentCoin;
+
+    use super::AutoRepay;
+
+    #[test]
+    fn spend() {
+        let mut budget = AutoRepay::default();
+        assert!(budget.is_empty());
+
+        budget.deposit(payment(100)).unwrap();
+        budget.spend(&payment(40));
+        assert_eq!(Some(&payment(60)), budget.budget());
+
+        budget.spend(&payment(60));
+        assert!(budget.is_empty());
+    }
+
+    fn payment(amount: Amount) -> PaymentCoin {
+        Coin::<PaymentC3>::new(amount).into()
+    }
+}
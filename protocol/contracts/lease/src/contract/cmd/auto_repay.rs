@@ -0,0 +1,72 @@
+use currency::{Currency, CurrencyDef, MemberOf};
+use finance::coin::{Coin, WithCoin, WithCoinResult};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies, PaymentCoin},
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency},
+    lease::{with_lease::WithLease, Lease},
+};
+
+/// Determine how much of the auto-repay budget to spend on the due interest
+pub(crate) struct AutoRepayAmount<'now> {
+    budget: PaymentCoin,
+    now: &'now Timestamp,
+}
+
+impl<'now> AutoRepayAmount<'now> {
+    pub fn new(budget: PaymentCoin, now: &'now Timestamp) -> Self {
+        Self { budget, now }
+    }
+}
+
+impl WithLease for AutoRepayAmount<'_> {
+    type Output = Option<PaymentCoin>;
+
+    type Error = ContractError;
+
+    fn exec<Asset, LppLoan, Oracle>(
+        self,
+        lease: Lease<Asset, LppLoan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency + MemberOf<LeaseAssetCurrencies>,
+        LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    {
+        self.budget.with_coin(AmountOf {
+            lease,
+            now: self.now,
+        })
+    }
+}
+
+struct AmountOf<'now, Asset, LppLoan, Oracle> {
+    lease: Lease<Asset, LppLoan, Oracle>,
+    now: &'now Timestamp,
+}
+
+impl<Asset, LppLoan, Oracle> WithCoin<LeasePaymentCurrencies>
+    for AmountOf<'_, Asset, LppLoan, Oracle>
+where
+    Asset: Currency + MemberOf<LeaseAssetCurrencies>,
+    LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+{
+    type Output = Option<PaymentCoin>;
+
+    type Error = ContractError;
+
+    fn on<C>(self, budget: Coin<C>) -> WithCoinResult<LeasePaymentCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        self.lease
+            .auto_repay_amount(budget, self.now)
+            .map(|may_amount| may_amount.map(Into::into))
+    }
+}
@@ -0,0 +1,99 @@
+use currency::{CurrencyDef, MemberOf};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use profit::stub::ProfitRef;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{position::PartialClose, LeaseAssetCurrencies, LeasePaymentCurrencies},
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO, TriggeredClose},
+    position::{CloseStrategy, PositionError},
+};
+
+/// Resolve the position close on a triggered close policy
+///
+/// A partial trigger is consumed, so the lease should be persisted afterwards.
+pub(crate) struct AutoCloseCmd<'strategy> {
+    strategy: &'strategy CloseStrategy,
+    // LeaseDTO attributes
+    profit: ProfitRef,
+    reserve: ReserveRef,
+    time_alarms: TimeAlarmsRef,
+}
+
+impl<'strategy> AutoCloseCmd<'strategy> {
+    pub fn new(
+        strategy: &'strategy CloseStrategy,
+        // LeaseDTO attributes follow
+        profit: ProfitRef,
+        time_alarms: TimeAlarmsRef,
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            strategy,
+            profit,
+            reserve,
+            time_alarms,
+        }
+    }
+}
+
+pub(crate) enum AutoCloseSpec {
+    Full,
+    Partial(PartialClose),
+    /// The part to close is not valid, so the position is kept open
+    Skipped(PositionError),
+}
+
+pub(crate) struct AutoCloseResult {
+    lease: LeaseDTO,
+    close: AutoCloseSpec,
+}
+
+impl SplitDTOOut for AutoCloseResult {
+    type Other = AutoCloseSpec;
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.close)
+    }
+}
+
+impl WithLease for AutoCloseCmd<'_> {
+    type Output = AutoCloseResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Loan, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Loan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+        Loan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+            + Into<OracleRef>,
+    {
+        lease.partial_auto_close(self.strategy).and_then(|close| {
+            lease
+                .try_into_dto(self.profit, self.time_alarms, self.reserve)
+                .inspect(|res| {
+                    debug_assert!(res.batch.is_empty());
+                })
+                .map(|IntoDTOResult { lease, batch: _ }| AutoCloseResult {
+                    lease,
+                    close: match close {
+                        TriggeredClose::Full => AutoCloseSpec::Full,
+                        TriggeredClose::Partial(amount) => AutoCloseSpec::Partial(PartialClose {
+                            amount: amount.into(),
+                            max_slippage: None,
+                        }),
+                        TriggeredClose::Skipped(cause) => AutoCloseSpec::Skipped(cause),
+                    },
+                })
+        })
+    }
+}
@@ -67,11 +67,11 @@ impl WithLease for ChangeCmd<'_, '_> {
         lease
             .change_close_policy(self.change, self.now)
             .and_then(|()| lease.check_close_policy(self.now))
-            .and_then(|status|CloseStatusDTO::try_from_do(status, self.now, &self.time_alarms, self.price_alarms) )
+            .and_then(|status|CloseStatusDTO::try_from_do(status, vec![], self.now, &self.time_alarms, self.price_alarms) )
             .and_then(|status_dto| {
                 let alarms = match status_dto {
                     CloseStatusDTO::Paid => unimplemented!("changing an Active Opened Lease is only permitted"),
-                    CloseStatusDTO::None { current_liability: _, alarms  } => alarms,
+                    CloseStatusDTO::None { current_liability: _, overdue_warning: _, dropped_triggers: _, alarms  } => alarms,
                     CloseStatusDTO::CloseAsked(_) => unimplemented!("triggering a close with a policy change should have already resulted in an error"),
                     CloseStatusDTO::NeedLiquidation(_) => unimplemented!("triggering a liquidation with a policy change should have already resulted in an error"),
                 };
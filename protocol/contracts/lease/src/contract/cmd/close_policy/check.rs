@@ -1,20 +1,27 @@
 use currency::{CurrencyDef, MemberOf};
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
+use profit::stub::ProfitRef;
 use sdk::cosmwasm_std::Timestamp;
 use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
     api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    contract::SplitDTOOut,
     error::{ContractError, ContractResult},
-    finance::{LpnCurrencies, LpnCurrency, OracleRef},
-    lease::{with_lease::WithLease, Lease as LeaseDO},
+    finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
 };
 
 use super::CloseStatusDTO;
 
+/// Check the lease close policy
+///
+/// The close policy is brought up to date beforehand, for example, a trailing stop loss is ratcheted
+/// down, so the lease should be persisted afterwards. The triggers dropped meanwhile are reported
+/// only if the lease stays open.
 pub(crate) fn check<Asset, Lpp, Oracle>(
-    lease: &LeaseDO<Asset, Lpp, Oracle>,
+    lease: &mut LeaseDO<Asset, Lpp, Oracle>,
     when: &Timestamp,
     time_alarms: &TimeAlarmsRef,
     price_alarms: &OracleRef,
@@ -26,45 +33,85 @@ where
     Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
 {
     lease
-        .check_close_policy(when)
-        .and_then(|status| CloseStatusDTO::try_from_do(status, when, time_alarms, price_alarms))
+        .update_close_policy(when)
+        .and_then(|dropped_triggers| {
+            lease.check_close_policy(when).and_then(|status| {
+                CloseStatusDTO::try_from_do(
+                    status,
+                    dropped_triggers,
+                    when,
+                    time_alarms,
+                    price_alarms,
+                )
+            })
+        })
 }
 
-pub(crate) struct CheckCmd<'a> {
-    now: &'a Timestamp,
-    time_alarms: &'a TimeAlarmsRef,
-    price_alarms: &'a OracleRef,
+pub(crate) struct CheckCmd<'now, 'price_alarms> {
+    now: &'now Timestamp,
+    // LeaseDTO attributes
+    profit: ProfitRef,
+    reserve: ReserveRef,
+    time_alarms: TimeAlarmsRef,
+    // alarms setup
+    price_alarms: &'price_alarms OracleRef,
 }
 
-impl<'a> CheckCmd<'a> {
+impl<'now, 'price_alarms> CheckCmd<'now, 'price_alarms> {
     pub fn new(
-        now: &'a Timestamp,
-        time_alarms: &'a TimeAlarmsRef,
-        price_alarms: &'a OracleRef,
+        now: &'now Timestamp,
+        // LeaseDTO attributes follow
+        profit: ProfitRef,
+        time_alarms: TimeAlarmsRef,
+        price_alarms: &'price_alarms OracleRef,
+        reserve: ReserveRef,
     ) -> Self {
         Self {
             now,
+            profit,
+            reserve,
             time_alarms,
             price_alarms,
         }
     }
 }
 
-impl WithLease for CheckCmd<'_> {
-    type Output = CloseStatusDTO;
+pub(crate) struct CheckResult {
+    lease: LeaseDTO,
+    status: CloseStatusDTO,
+}
+
+impl SplitDTOOut for CheckResult {
+    type Other = CloseStatusDTO;
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.status)
+    }
+}
+
+impl WithLease for CheckCmd<'_, '_> {
+    type Output = CheckResult;
 
     type Error = ContractError;
 
     fn exec<Asset, Loan, Oracle>(
         self,
-        lease: LeaseDO<Asset, Loan, Oracle>,
+        mut lease: LeaseDO<Asset, Loan, Oracle>,
     ) -> Result<Self::Output, Self::Error>
     where
         Asset: CurrencyDef,
         Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
         Loan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
-        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+            + Into<OracleRef>,
     {
-        check(&lease, self.now, self.time_alarms, self.price_alarms)
+        check(&mut lease, self.now, &self.time_alarms, self.price_alarms).and_then(|status| {
+            lease
+                .try_into_dto(self.profit, self.time_alarms, self.reserve)
+                .inspect(|res| {
+                    debug_assert!(res.batch.is_empty());
+                })
+                .map(|IntoDTOResult { lease, batch: _ }| CheckResult { lease, status })
+        })
     }
 }
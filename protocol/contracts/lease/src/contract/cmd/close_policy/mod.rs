@@ -11,9 +11,10 @@ use crate::{
     error::ContractResult,
     finance::OracleRef,
     lease::CloseStatus,
-    position::{Cause, CloseStrategy, Liquidation},
+    position::{Cause, CloseStrategy, Liquidation, OverdueWarning},
 };
 
+pub(crate) mod auto_close;
 pub(crate) mod change;
 pub(crate) mod check;
 
@@ -21,6 +22,9 @@ pub(crate) enum CloseStatusDTO {
     Paid,
     None {
         current_liability: Zone,
+        overdue_warning: Option<OverdueWarning>,
+        /// Triggers dropped from the close policy since their LTV has got out of range
+        dropped_triggers: Vec<CloseStrategy>,
         alarms: Batch,
     },
     NeedLiquidation(LiquidationDTO),
@@ -46,6 +50,7 @@ pub(crate) struct FullLiquidationDTO {
 impl CloseStatusDTO {
     fn try_from_do<Asset>(
         status: CloseStatus<Asset>,
+        dropped_triggers: Vec<CloseStrategy>,
         when: &Timestamp,
         time_alarms: &TimeAlarmsRef,
         price_alarms: &OracleRef,
@@ -58,11 +63,14 @@ impl CloseStatusDTO {
             CloseStatus::Paid => Ok(Self::Paid),
             CloseStatus::None {
                 current_liability,
+                overdue_warning,
                 steadiness,
             } => steadiness
                 .try_into_alarms(when, time_alarms, price_alarms)
                 .map(|alarms| Self::None {
                     current_liability,
+                    overdue_warning,
+                    dropped_triggers,
                     alarms,
                 }),
             CloseStatus::NeedLiquidation(liquidation) => {
@@ -0,0 +1,129 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::coin::{Coin, WithCoin, WithCoinResult};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::{bank::FixedAddressSender, batch::Batch};
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies, PaymentCoin},
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCoinDTO, LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+};
+
+pub(crate) struct IncreaseLoanCmd<'now> {
+    payment: PaymentCoin,
+    borrow: LpnCoinDTO,
+    now: &'now Timestamp,
+    // LeaseDTO attributes
+    profit: ProfitRef,
+    time_alarms: TimeAlarmsRef,
+    reserve: ReserveRef,
+}
+
+impl<'now> IncreaseLoanCmd<'now> {
+    pub fn new(
+        payment: PaymentCoin,
+        borrow: LpnCoinDTO,
+        now: &'now Timestamp,
+        // LeaseDTO attributes follow
+        profit: ProfitRef,
+        time_alarms: TimeAlarmsRef,
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            payment,
+            borrow,
+            now,
+            profit,
+            time_alarms,
+            reserve,
+        }
+    }
+}
+
+pub(crate) struct IncreaseLoanResult {
+    lease: LeaseDTO,
+    borrowed: LpnCoinDTO,
+    messages: Batch,
+}
+
+impl SplitDTOOut for IncreaseLoanResult {
+    type Other = (LpnCoinDTO, Batch);
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, (self.borrowed, self.messages))
+    }
+}
+
+impl WithLease for IncreaseLoanCmd<'_> {
+    type Output = IncreaseLoanResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Loan, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Loan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+        Loan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+            + Into<OracleRef>,
+    {
+        let borrow = self.borrow.try_into()?;
+        let mut profit_sender = self.profit.clone().into_stub();
+
+        self.payment
+            .with_coin(LoanIncrease {
+                lease: &mut lease,
+                borrow,
+                now: self.now,
+                profit: &mut profit_sender,
+            })
+            .and_then(|borrowed| {
+                lease
+                    .try_into_dto(self.profit, self.time_alarms, self.reserve)
+                    .map(|IntoDTOResult { lease, batch }| IncreaseLoanResult {
+                        lease,
+                        borrowed: borrowed.into(),
+                        messages: batch.merge(profit_sender.into()),
+                    })
+            })
+    }
+}
+
+struct LoanIncrease<'lease, 'now, 'profit, Asset, Lpp, Oracle, Profit> {
+    lease: &'lease mut LeaseDO<Asset, Lpp, Oracle>,
+    borrow: Coin<LpnCurrency>,
+    now: &'now Timestamp,
+    profit: &'profit mut Profit,
+}
+
+impl<Asset, Lpp, Oracle, Profit> WithCoin<LeasePaymentCurrencies>
+    for LoanIncrease<'_, '_, '_, Asset, Lpp, Oracle, Profit>
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    Profit: FixedAddressSender,
+{
+    type Output = Coin<LpnCurrency>;
+
+    type Error = ContractError;
+
+    fn on<C>(self, payment: Coin<C>) -> WithCoinResult<LeasePaymentCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        self.lease
+            .increase_loan(payment, self.borrow, self.now, self.profit)
+    }
+}
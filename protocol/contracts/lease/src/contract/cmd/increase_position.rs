@@ -0,0 +1,120 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::coin::Coin;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        position::{ChangeCmd, ClosePolicyChange},
+        LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies,
+    },
+    contract::SplitDTOOut,
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+    position::CloseStrategy,
+};
+
+use super::{close_policy::check, CloseStatusDTO};
+
+pub(crate) struct IncreaseCmd<'now, 'price_alarms> {
+    amount: LeaseCoin,
+    now: &'now Timestamp,
+    // LeaseDTO attributes
+    profit: ProfitRef,
+    reserve: ReserveRef,
+    time_alarms: TimeAlarmsRef,
+    // alarms setup
+    price_alarms: &'price_alarms OracleRef,
+}
+
+impl<'now, 'price_alarms> IncreaseCmd<'now, 'price_alarms> {
+    pub fn new(
+        amount: LeaseCoin,
+        now: &'now Timestamp,
+        // LeaseDTO attributes follow
+        profit: ProfitRef,
+        time_alarms: TimeAlarmsRef,
+        price_alarms: &'price_alarms OracleRef,
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            amount,
+            now,
+            profit,
+            reserve,
+            time_alarms,
+            price_alarms,
+        }
+    }
+}
+
+pub(crate) struct IncreaseResult {
+    lease: LeaseDTO,
+    status: CloseStatusDTO,
+}
+
+impl SplitDTOOut for IncreaseResult {
+    type Other = CloseStatusDTO;
+
+    fn split_into(self) -> (LeaseDTO, Self::Other) {
+        (self.lease, self.status)
+    }
+}
+
+impl WithLease for IncreaseCmd<'_, '_> {
+    type Output = IncreaseResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, Loan, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, Loan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+        Loan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+            + Into<OracleRef>,
+    {
+        Coin::<Asset>::try_from(self.amount)
+            .map_err(Into::into)
+            .and_then(|amount| {
+                lease.increase_position(amount);
+                check::check(&mut lease, self.now, &self.time_alarms, self.price_alarms)
+            })
+            .and_then(|status| {
+                // the same as on payments, a Take Profit is reset if the increase takes the LTV below it
+                if matches!(
+                    status,
+                    CloseStatusDTO::CloseAsked(CloseStrategy::TakeProfit(_))
+                ) {
+                    lease
+                        .change_close_policy(
+                            ClosePolicyChange {
+                                take_profit: Some(ChangeCmd::Reset),
+                                stop_loss: None,
+                            },
+                            self.now,
+                        )
+                        .and_then(|()| {
+                            check::check(&mut lease, self.now, &self.time_alarms, self.price_alarms)
+                        })
+                } else {
+                    Ok(status)
+                }
+            })
+            .and_then(|status| {
+                lease
+                    .try_into_dto(self.profit, self.time_alarms, self.reserve)
+                    .inspect(|res| {
+                        debug_assert!(res.batch.is_empty());
+                    })
+                    .map(|IntoDTOResult { lease, batch: _ }| IncreaseResult { lease, status })
+            })
+    }
+}
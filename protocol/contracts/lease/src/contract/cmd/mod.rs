@@ -1,26 +1,40 @@
+pub(super) use auto_repay::AutoRepayAmount;
 pub(super) use close_full::Close as FullClose;
 pub(super) use close_paid::Close;
 pub(super) use close_partial::CloseFn as PartialCloseFn;
 pub(super) use close_policy::{
-    change::ChangeCmd as ChangeClosePolicy, check::CheckCmd as CloseStatusCmd, CloseStatusDTO,
-    FullLiquidationDTO, LiquidationDTO, PartialLiquidationDTO,
+    auto_close::{AutoCloseCmd as AutoClose, AutoCloseSpec},
+    change::ChangeCmd as ChangeClosePolicy,
+    check::CheckCmd as CloseStatusCmd,
+    CloseStatusDTO, FullLiquidationDTO, LiquidationDTO, PartialLiquidationDTO,
 };
+pub(super) use increase_loan::IncreaseLoanCmd as IncreaseLoan;
+pub(super) use increase_position::IncreaseCmd as IncreasePosition;
 pub(super) use obtain_payment::ObtainPayment;
 pub(super) use open::{LeaseFactory, OpenLeaseResult};
 pub(super) use open_loan::{OpenLoanReq, OpenLoanReqResult, OpenLoanResp, OpenLoanRespResult};
+pub(super) use projection::LeaseProjection;
+pub(super) use refinance::{RefinanceAmount, RefinanceCmd, RefinanceLoanReq};
 pub(super) use repay::RepayLeaseFn;
 pub(super) use repayable::{Emitter as RepayEmitter, Repay, RepayFn, RepayResult};
+pub(super) use simulate::Simulate;
 pub(super) use state::LeaseState;
 pub(super) use validate_close_position::Cmd as ValidateClosePosition;
 
+mod auto_repay;
 mod close_full;
 mod close_paid;
 mod close_partial;
 mod close_policy;
+mod increase_loan;
+mod increase_position;
 mod obtain_payment;
 mod open;
 mod open_loan;
+mod projection;
+mod refinance;
 mod repay;
 mod repayable;
+mod simulate;
 mod state;
 mod validate_close_position;
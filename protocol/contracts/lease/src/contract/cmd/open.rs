@@ -81,7 +81,7 @@ impl WithLeaseDeps for LeaseFactory<'_> {
         Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
             + Into<OracleRef>,
     {
-        let lease = {
+        let mut lease = {
             let loan = Loan::new(
                 lpp_loan,
                 self.start_at,
@@ -91,16 +91,18 @@ impl WithLeaseDeps for LeaseFactory<'_> {
             Lease::new(self.lease_addr, self.form.customer, position, loan, oracle)
         };
 
-        check::check(&lease, self.now, &self.time_alarms, &self.price_alarms).and_then(|status| {
-            lease
-                .try_into_dto(self.profit, self.time_alarms, self.reserve)
-                .inspect(|res| {
-                    debug_assert!(res.batch.is_empty());
-                })
-                .map(|res| OpenLeaseResult {
-                    lease: res.lease,
-                    status,
-                })
-        })
+        check::check(&mut lease, self.now, &self.time_alarms, &self.price_alarms).and_then(
+            |status| {
+                lease
+                    .try_into_dto(self.profit, self.time_alarms, self.reserve)
+                    .inspect(|res| {
+                        debug_assert!(res.batch.is_empty());
+                    })
+                    .map(|res| OpenLeaseResult {
+                        lease: res.lease,
+                        status,
+                    })
+            },
+        )
     }
 }
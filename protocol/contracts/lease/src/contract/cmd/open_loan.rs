@@ -67,10 +67,15 @@ impl WithLppLender<LpnCurrency, LpnCurrencies> for OpenLoanReq<'_> {
                 spec.calc_borrow_amount(downpayment_lpn, self.max_ltd)
                     .map_err(ContractError::from)
             })
-            .and_then(|borrow_lpn| lpp.open_loan_req(borrow_lpn).map_err(ContractError::from))
-            .map(|()| Self::Output {
+            .and_then(|borrow_lpn| {
+                lpp.open_loan_req(borrow_lpn)
+                    .map(|()| borrow_lpn)
+                    .map_err(ContractError::from)
+            })
+            .map(|borrow| Self::Output {
                 batch: lpp.into().batch,
                 downpayment,
+                borrow,
             })
     }
 }
@@ -105,6 +110,7 @@ impl WithCoin<LeasePaymentCurrencies> for DownpaymentHandler<'_> {
 pub struct OpenLoanReqResult {
     pub(in crate::contract) batch: Batch,
     pub(in crate::contract) downpayment: DownpaymentCoin,
+    pub(in crate::contract) borrow: LpnCoin,
 }
 
 pub struct OpenLoanResp {
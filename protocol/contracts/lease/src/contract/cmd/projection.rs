@@ -0,0 +1,47 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::duration::Duration;
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{query::ProjectionResponse, LeaseAssetCurrencies, LeasePaymentCurrencies},
+    error::ContractError,
+    finance::{LpnCurrencies, LpnCurrency},
+    lease::{with_lease::WithLease, Lease},
+};
+
+pub struct LeaseProjection {
+    now: Timestamp,
+    due_projection: Duration,
+}
+
+impl LeaseProjection {
+    pub fn new(now: Timestamp, due_projection: Duration) -> Self {
+        Self {
+            now,
+            due_projection,
+        }
+    }
+}
+
+impl WithLease for LeaseProjection {
+    type Output = ProjectionResponse;
+
+    type Error = ContractError;
+
+    fn exec<Asset, LppLoan, Oracle>(
+        self,
+        lease: Lease<Asset, LppLoan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+        LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    {
+        Ok(ProjectionResponse::from_projection(
+            lease.projection(self.now, self.due_projection),
+        ))
+    }
+}
@@ -0,0 +1,130 @@
+use currency::{Currency, CurrencyDef, MemberOf};
+use lpp::stub::{
+    lender::{LppLender as LppLenderTrait, WithLppLender},
+    loan::LppLoan as LppLoanTrait,
+};
+use oracle_platform::Oracle as OracleTrait;
+use platform::batch::Batch;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    error::ContractError,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency, LppRef, OracleRef, ReserveRef},
+    lease::{with_lease::WithLease, IntoDTOResult, Lease as LeaseDO, LeaseDTO},
+};
+
+/// Determine the amount of a new loan that would refinance the current one
+pub(crate) struct RefinanceAmount<'now> {
+    now: &'now Timestamp,
+}
+
+impl<'now> RefinanceAmount<'now> {
+    pub fn new(now: &'now Timestamp) -> Self {
+        Self { now }
+    }
+}
+
+impl WithLease for RefinanceAmount<'_> {
+    type Output = LpnCoin;
+
+    type Error = ContractError;
+
+    fn exec<Asset, LppLoan, Oracle>(
+        self,
+        lease: LeaseDO<Asset, LppLoan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: Currency + MemberOf<LeaseAssetCurrencies>,
+        LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    {
+        lease.refinance_amount(self.now)
+    }
+}
+
+/// Request a new loan to refinance the current one with
+pub(crate) struct RefinanceLoanReq {
+    amount: LpnCoin,
+}
+
+impl RefinanceLoanReq {
+    pub fn new(amount: LpnCoin) -> Self {
+        Self { amount }
+    }
+}
+
+impl WithLppLender<LpnCurrency, LpnCurrencies> for RefinanceLoanReq {
+    type Output = Batch;
+
+    type Error = ContractError;
+
+    fn exec<LppLender>(self, mut lpp: LppLender) -> Result<Self::Output, Self::Error>
+    where
+        LppLender: LppLenderTrait<LpnCurrency, LpnCurrencies>,
+    {
+        lpp.open_loan_req(self.amount)
+            .map_err(ContractError::from)
+            .map(|()| lpp.into().batch)
+    }
+}
+
+/// Repay in full the current loan and move the lease over to the new Lpp
+pub(crate) struct RefinanceCmd<'now> {
+    new_lpp: LppRef,
+    now: &'now Timestamp,
+    // LeaseDTO attributes
+    profit: ProfitRef,
+    time_alarms: TimeAlarmsRef,
+    reserve: ReserveRef,
+}
+
+impl<'now> RefinanceCmd<'now> {
+    pub fn new(
+        new_lpp: LppRef,
+        now: &'now Timestamp,
+        // LeaseDTO attributes follow
+        profit: ProfitRef,
+        time_alarms: TimeAlarmsRef,
+        reserve: ReserveRef,
+    ) -> Self {
+        Self {
+            new_lpp,
+            now,
+            profit,
+            time_alarms,
+            reserve,
+        }
+    }
+}
+
+impl WithLease for RefinanceCmd<'_> {
+    type Output = IntoDTOResult;
+
+    type Error = ContractError;
+
+    fn exec<Asset, LppLoan, Oracle>(
+        self,
+        mut lease: LeaseDO<Asset, LppLoan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+        LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+            + Into<OracleRef>,
+    {
+        lease.refinance(self.now);
+        lease
+            .try_into_dto(self.profit, self.time_alarms, self.reserve)
+            .map(|IntoDTOResult { lease, batch }| IntoDTOResult {
+                lease: LeaseDTO {
+                    loan: lease.loan.refinance(self.new_lpp),
+                    ..lease
+                },
+                batch,
+            })
+    }
+}
@@ -264,6 +264,8 @@ mod test {
         match close_status {
             CloseStatusDTO::None {
                 current_liability,
+                overdue_warning: _,
+                dropped_triggers: _,
                 alarms: _,
             } => assert_eq!(Zone::no_warnings(FIRST_LIQ_WARN), current_liability),
             _ => panic!("unexpected close status!"),
@@ -0,0 +1,119 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::coin::{Coin, WithCoin, WithCoinResult};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::bank::FixedAddressSender;
+use profit::stub::ProfitRef;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{
+        position::PositionClose,
+        query::{SimulateAction, SimulationResponse},
+        LeaseAssetCurrencies, LeasePaymentCurrencies,
+    },
+    error::{ContractError, ContractResult},
+    finance::{LpnCurrencies, LpnCurrency, OracleRef},
+    lease::{with_lease::WithLease, Lease, Simulation},
+    position::PositionError,
+};
+
+use super::validate_close_position;
+
+/// Simulate an action on the lease without persisting the outcome
+pub(crate) struct Simulate<'now> {
+    action: SimulateAction,
+    now: &'now Timestamp,
+    profit: ProfitRef,
+}
+
+impl<'now> Simulate<'now> {
+    pub fn new(action: SimulateAction, now: &'now Timestamp, profit: ProfitRef) -> Self {
+        Self {
+            action,
+            now,
+            profit,
+        }
+    }
+}
+
+impl WithLease for Simulate<'_> {
+    type Output = SimulationResponse;
+
+    type Error = ContractError;
+
+    fn exec<Asset, LppLoan, Oracle>(
+        self,
+        lease: Lease<Asset, LppLoan, Oracle>,
+    ) -> Result<Self::Output, Self::Error>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+        LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+        Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+            + Into<OracleRef>,
+    {
+        // the messages sent to the Profit are never delivered
+        let mut profit = self.profit.into_stub();
+        match self.action {
+            SimulateAction::Repay(payment) => payment.with_coin(RepaySimulation {
+                lease,
+                now: self.now,
+                profit: &mut profit,
+            }),
+            SimulateAction::ClosePosition(PositionClose::FullClose(_)) => {
+                lease.simulate_close_full(self.now, &mut profit)
+            }
+            SimulateAction::ClosePosition(spec) => {
+                validate_close_position::close_amount(&spec, &lease, self.now)
+                    .and_then(|amount| lease.simulate_close_partial(amount, self.now, &mut profit))
+            }
+        }
+        .map(SimulationResponse::from_simulation)
+        .or_else(may_reject)
+    }
+}
+
+struct RepaySimulation<'now, 'profit, Asset, LppLoan, Oracle, ProfitT> {
+    lease: Lease<Asset, LppLoan, Oracle>,
+    now: &'now Timestamp,
+    profit: &'profit mut ProfitT,
+}
+
+impl<Asset, LppLoan, Oracle, ProfitT> WithCoin<LeasePaymentCurrencies>
+    for RepaySimulation<'_, '_, Asset, LppLoan, Oracle, ProfitT>
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+        + Into<OracleRef>,
+    ProfitT: FixedAddressSender,
+{
+    type Output = Simulation<Asset>;
+
+    type Error = ContractError;
+
+    fn on<C>(self, coin: Coin<C>) -> WithCoinResult<LeasePaymentCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        self.lease.simulate_repay(coin, self.now, self.profit)
+    }
+}
+
+/// Turn the errors of an action that would be rejected into a response
+fn may_reject(err: ContractError) -> ContractResult<SimulationResponse> {
+    match err {
+        ContractError::PositionError(
+            err @ (PositionError::InsufficientTransactionAmount(_)
+            | PositionError::PositionCloseAmountTooSmall(_)
+            | PositionError::PositionCloseAmountTooBig(_)
+            | PositionError::CloseLtvNotBelowCurrent { .. }),
+        ) => Ok(SimulationResponse::Rejected {
+            reason: err.to_string(),
+        }),
+        _ => Err(err),
+    }
+}
@@ -45,8 +45,14 @@ impl WithLease for LeaseState {
         LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
         Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
     {
+        // a missing lease asset price should not make the lease state unavailable
+        let liability = lease
+            .liability_status(self.now, self.due_projection)
+            .ok()
+            .flatten();
         Ok(StateResponse::opened_from(
             lease.state(self.now, self.due_projection),
+            liability,
             self.in_progress,
         ))
     }
@@ -1,27 +1,34 @@
 use currency::{CurrencyDef, MemberOf};
-use finance::coin::Coin;
+use finance::{coin::Coin, percent::Percent};
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
 
 use crate::{
-    api::{position::PartialClose, LeaseAssetCurrencies, LeasePaymentCurrencies},
+    api::{
+        position::{PartialClose, PositionClose},
+        LeaseAssetCurrencies, LeasePaymentCurrencies,
+    },
     error::ContractError,
     finance::{LpnCurrencies, LpnCurrency, OracleRef},
     lease::{with_lease::WithLease, Lease},
 };
 
-pub(in crate::contract) struct Cmd<'spec> {
-    spec: &'spec PartialClose,
+/// Resolve the amount of a partial position close and validate it
+pub(in crate::contract) struct Cmd<'spec, 'now> {
+    spec: &'spec PositionClose,
+    now: &'now Timestamp,
 }
 
-impl<'spec> Cmd<'spec> {
-    pub fn new(spec: &'spec PartialClose) -> Self {
-        Self { spec }
+impl<'spec, 'now> Cmd<'spec, 'now> {
+    pub fn new(spec: &'spec PositionClose, now: &'now Timestamp) -> Self {
+        debug_assert!(!matches!(spec, PositionClose::FullClose(_)));
+        Self { spec, now }
     }
 }
 
-impl WithLease for Cmd<'_> {
-    type Output = ();
+impl WithLease for Cmd<'_, '_> {
+    type Output = PartialClose;
 
     type Error = ContractError;
 
@@ -36,8 +43,45 @@ impl WithLease for Cmd<'_> {
         Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
             + Into<OracleRef>,
     {
-        Coin::<Asset>::try_from(self.spec.amount)
-            .map_err(Into::into)
-            .and_then(|amount| lease.validate_close(amount))
+        close_amount(self.spec, &lease, self.now).and_then(|amount| {
+            lease.validate_close(amount).map(|()| PartialClose {
+                amount: amount.into(),
+                max_slippage: max_slippage(self.spec),
+            })
+        })
+    }
+}
+
+/// Resolve the amount of a partial position close
+pub(super) fn close_amount<Asset, LppLoan, Oracle>(
+    spec: &PositionClose,
+    lease: &Lease<Asset, LppLoan, Oracle>,
+    now: &Timestamp,
+) -> Result<Coin<Asset>, ContractError>
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+        + Into<OracleRef>,
+{
+    match spec {
+        PositionClose::FullClose(_) => {
+            unimplemented!("a full close is executed irrespective of the amount")
+        }
+        PositionClose::PartialClose(spec) => {
+            Coin::<Asset>::try_from(spec.amount).map_err(Into::into)
+        }
+        PositionClose::PartialClosePercent(spec) => Ok(lease.close_amount_of(spec.percent)),
+        PositionClose::PartialCloseLtv(spec) => lease.close_amount_to_ltv(spec.ltv, now),
+    }
+}
+
+fn max_slippage(spec: &PositionClose) -> Option<Percent> {
+    match spec {
+        PositionClose::FullClose(spec) => spec.max_slippage,
+        PositionClose::PartialClose(spec) => spec.max_slippage,
+        PositionClose::PartialClosePercent(spec) => spec.max_slippage,
+        PositionClose::PartialCloseLtv(spec) => spec.max_slippage,
     }
 }
@@ -3,7 +3,7 @@ use platform::{error as platform_error, message::Response as MessageResponse, re
 use sdk::{
     cosmwasm_ext::Response as CwResponse,
     cosmwasm_std::{
-        entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, QuerierWrapper,
+        entry_point, to_json_binary, Api, Binary, Deps, DepsMut, Env, MessageInfo, QuerierWrapper,
         Reply, Storage,
     },
     neutron_sdk::sudo::msg::SudoMsg,
@@ -11,12 +11,19 @@ use sdk::{
 use versioning::{package_version, SemVer, Version, VersionSegment};
 
 use crate::{
-    api::{open::NewLeaseContract, query::StateQuery, ExecuteMsg, MigrateMsg},
+    api::{
+        open::NewLeaseContract,
+        query::{QueryMsg, StateQuery},
+        ExecuteMsg, MigrateMsg,
+    },
     contract::api::Contract,
     error::ContractResult,
 };
 
-use super::state::{self, Response, State};
+use super::{
+    history,
+    state::{self, Response, State},
+};
 
 // const CONTRACT_STORAGE_VERSION_FROM: VersionSegment = 8;
 const CONTRACT_STORAGE_VERSION: VersionSegment = 9;
@@ -26,7 +33,7 @@ const CONTRACT_VERSION: Version = Version::new(CONTRACT_STORAGE_VERSION, PACKAGE
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut<'_>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_lease: NewLeaseContract,
 ) -> ContractResult<CwResponse> {
@@ -41,7 +48,7 @@ pub fn instantiate(
 
     versioning::initialize(deps.storage, CONTRACT_VERSION)?;
 
-    state::new_lease(deps.querier, info, new_lease)
+    state::new_lease(deps.querier, &env, info, new_lease)
         .and_then(|(batch, next_state)| state::save(deps.storage, &next_state).map(|()| batch))
         .map(response::response_only_messages)
         .inspect_err(platform_error::log(deps.api))
@@ -62,6 +69,7 @@ pub fn migrate(
 pub fn reply(deps: DepsMut<'_>, env: Env, msg: Reply) -> ContractResult<CwResponse> {
     process_lease(deps.storage, |lease| lease.reply(deps.querier, env, msg))
         .map(response::response_only_messages)
+        .and_then(|resp| record_history(deps.storage, resp))
         .inspect_err(platform_error::log(deps.api))
 }
 
@@ -73,9 +81,10 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> ContractResult<CwResponse> {
     process_lease(deps.storage, |lease| {
-        process_execute(msg, lease, deps.querier, env, info)
+        process_execute(msg, lease, deps.api, deps.querier, env, info)
     })
     .map(response::response_only_messages)
+    .and_then(|resp| record_history(deps.storage, resp))
     .inspect_err(platform_error::log(deps.api))
 }
 
@@ -85,21 +94,38 @@ pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> ContractResult<CwRespo
         process_sudo(msg, lease, deps.querier, env)
     })
     .map(response::response_only_messages)
+    .and_then(|resp| record_history(deps.storage, resp))
     .inspect_err(platform_error::log(deps.api))
 }
 
 #[entry_point]
-pub fn query(deps: Deps<'_>, env: Env, msg: StateQuery) -> ContractResult<Binary> {
-    state::load(deps.storage)
-        .and_then(|state| {
-            state.state(
-                env.block.time,
-                Duration::from_secs(msg.due_projection),
-                deps.querier,
-            )
-        })
-        .and_then(|resp| to_json_binary(&resp).map_err(Into::into))
-        .inspect_err(platform_error::log(deps.api))
+pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::State(StateQuery { due_projection }) => state::load(deps.storage)
+            .and_then(|state| {
+                state.state(
+                    env.block.time,
+                    Duration::from_secs(due_projection),
+                    deps.querier,
+                )
+            })
+            .and_then(|resp| to_json_binary(&resp).map_err(Into::into)),
+        QueryMsg::Simulate { action } => state::load(deps.storage)
+            .and_then(|state| state.simulate(action, env.block.time, deps.querier))
+            .and_then(|resp| to_json_binary(&resp).map_err(Into::into)),
+        QueryMsg::Projection { due_projection } => state::load(deps.storage)
+            .and_then(|state| {
+                state.projection(
+                    env.block.time,
+                    Duration::from_secs(due_projection),
+                    deps.querier,
+                )
+            })
+            .and_then(|resp| to_json_binary(&resp).map_err(Into::into)),
+        QueryMsg::History { start_after, limit } => history::load(deps.storage, start_after, limit)
+            .and_then(|resp| to_json_binary(&resp).map_err(Into::into)),
+    }
+    .inspect_err(platform_error::log(deps.api))
 }
 
 fn process_lease<ProcFn>(
@@ -117,9 +143,14 @@ where
     )
 }
 
+fn record_history(storage: &mut dyn Storage, resp: CwResponse) -> ContractResult<CwResponse> {
+    history::record(storage, &resp.events).map(|()| resp)
+}
+
 fn process_execute(
     msg: ExecuteMsg,
     state: State,
+    api: &dyn Api,
     querier: QuerierWrapper<'_>,
     env: Env,
     info: MessageInfo,
@@ -130,6 +161,37 @@ fn process_execute(
             state.change_close_policy(change, querier, env, info)
         }
         ExecuteMsg::ClosePosition(spec) => state.close_position(spec, querier, env, info),
+        ExecuteMsg::RepayFromPosition { amount } => {
+            state.repay_from_position(amount, querier, env, info)
+        }
+        ExecuteMsg::IncreasePosition { borrow } => {
+            state.increase_position(borrow, querier, env, info)
+        }
+        ExecuteMsg::TransferOwnership {
+            new_owner,
+            require_accept,
+        } => api
+            .addr_validate(new_owner.as_str())
+            .map_err(Into::into)
+            .and_then(|new_owner| {
+                state.transfer_ownership(new_owner, require_accept, querier, env, info)
+            }),
+        ExecuteMsg::AcceptOwnership() => state.accept_ownership(querier, env, info),
+        ExecuteMsg::GrantOperator {
+            operator,
+            permissions,
+        } => api
+            .addr_validate(operator.as_str())
+            .map_err(Into::into)
+            .and_then(|operator| state.grant_operator(operator, permissions, querier, env, info)),
+        ExecuteMsg::RevokeOperator { operator } => {
+            state.revoke_operator(operator, querier, env, info)
+        }
+        ExecuteMsg::DepositMarginBuffer() => state.deposit_margin_buffer(querier, env, info),
+        ExecuteMsg::WithdrawMarginBuffer() => state.withdraw_margin_buffer(querier, env, info),
+        ExecuteMsg::DepositAutoRepay() => state.deposit_auto_repay(querier, env, info),
+        ExecuteMsg::WithdrawAutoRepay() => state.withdraw_auto_repay(querier, env, info),
+        ExecuteMsg::Refinance { lpp } => state.refinance(lpp, querier, env, info),
         ExecuteMsg::Close() => state.close(querier, env, info),
         ExecuteMsg::TimeAlarm {} => state.on_time_alarm(querier, env, info),
         ExecuteMsg::PriceAlarm() => state.on_price_alarm(querier, env, info),
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use currency::CurrencyDTO;
 use platform::batch::Batch;
 use sdk::cosmwasm_std::{Addr, QuerierWrapper};
 
-use crate::{api::FinalizerExecuteMsg, error::ContractResult};
+use crate::{
+    api::{FinalizerExecuteMsg, FinalizerQueryMsg, LeaseAssetCurrencies},
+    error::ContractResult,
+    finance::LpnCoin,
+};
 
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -29,4 +34,50 @@ impl FinalizerRef {
         .map(|()| msgs)
         .map_err(Into::into)
     }
+
+    pub(super) fn notify_loan_opened(&self, lpp: Addr) -> ContractResult<Batch> {
+        let mut msgs = Batch::default();
+        msgs.schedule_execute_wasm_no_reply_no_funds(
+            self.addr.clone(),
+            &FinalizerExecuteMsg::LeaseLoanOpened { lpp },
+        )
+        .map(|()| msgs)
+        .map_err(Into::into)
+    }
+
+    pub(super) fn open_interest_fits(
+        &self,
+        currency: CurrencyDTO<LeaseAssetCurrencies>,
+        customer: Addr,
+        borrow: LpnCoin,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<bool> {
+        querier
+            .query_wasm_smart(
+                self.addr.clone(),
+                &FinalizerQueryMsg::OpenInterestFits {
+                    currency,
+                    customer,
+                    borrow: borrow.into(),
+                },
+            )
+            .map_err(Into::into)
+    }
+
+    pub(super) fn notify_owner_change(
+        &self,
+        customer: Addr,
+        new_customer: Addr,
+    ) -> ContractResult<Batch> {
+        let mut msgs = Batch::default();
+        msgs.schedule_execute_wasm_no_reply_no_funds(
+            self.addr.clone(),
+            &FinalizerExecuteMsg::ChangeLeaseOwner {
+                customer,
+                new_customer,
+            },
+        )
+        .map(|()| msgs)
+        .map_err(Into::into)
+    }
 }
@@ -0,0 +1,53 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::coin::{Coin, WithCoin, WithCoinResult};
+use platform::{bank, batch::Batch};
+use sdk::cosmwasm_std::{Addr, Coin as CwCoin};
+
+use crate::{
+    api::{LeasePaymentCurrencies, PaymentCoin},
+    error::{ContractError, ContractResult},
+};
+
+/// Send customer funds held by the lease
+pub(super) fn send(amount: &PaymentCoin, to: Addr) -> ContractResult<Batch> {
+    amount.with_coin(SendTo { to })
+}
+
+/// Obtain the customer funds attached to a message
+pub(super) fn receive(cw_amount: Vec<CwCoin>) -> ContractResult<PaymentCoin> {
+    bank::may_received(&cw_amount, Receive {}).ok_or_else(ContractError::NoPaymentError)?
+}
+
+struct SendTo {
+    to: Addr,
+}
+
+impl WithCoin<LeasePaymentCurrencies> for SendTo {
+    type Output = Batch;
+
+    type Error = ContractError;
+
+    fn on<C>(self, coin: Coin<C>) -> WithCoinResult<LeasePaymentCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        Ok(bank::bank_send(self.to, coin))
+    }
+}
+
+struct Receive {}
+
+impl WithCoin<LeasePaymentCurrencies> for Receive {
+    type Output = PaymentCoin;
+
+    type Error = ContractError;
+
+    fn on<C>(self, coin: Coin<C>) -> WithCoinResult<LeasePaymentCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        Ok(coin.into())
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+use sdk::{
+    cosmwasm_std::{Attribute, Event, Order, Storage},
+    cw_storage_plus::{Bound, Item, Map},
+};
+
+use crate::{
+    api::query::{HistoryRecord, HistoryResponse},
+    error::ContractResult,
+    event::Type,
+};
+
+/// The maximum number of records kept, the oldest ones are dropped first
+const MAX_RECORDS: u64 = 100;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 50;
+
+/// The lease events that make up its history
+const RECORDED: [Type; 17] = [
+    Type::OpenedActive,
+    Type::IncreasePosition,
+    Type::PaidActive,
+    Type::Liquidation,
+    Type::ClosePosition,
+    Type::AutoClosePosition,
+    Type::ChangeClosePolicy,
+    Type::TransferOwnership,
+    Type::DepositMarginBuffer,
+    Type::WithdrawMarginBuffer,
+    Type::CoverFromMarginBuffer,
+    Type::DepositAutoRepay,
+    Type::WithdrawAutoRepay,
+    Type::AutoRepay,
+    Type::Refinance,
+    Type::CancelOpen,
+    Type::Closed,
+];
+
+const RECORDS: Map<u64, Record> = Map::new("history");
+const NEXT_SEQ: Item<u64> = Item::new("history_next_seq");
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Record {
+    event: String,
+    attributes: Vec<Attribute>,
+}
+
+/// Append the lease events of interest to the history
+///
+/// The storage is kept separately from the lease state so the history outlives any
+/// state transition and migration.
+pub(super) fn record(storage: &mut dyn Storage, events: &[Event]) -> ContractResult<()> {
+    events
+        .iter()
+        .filter(|event| RECORDED.iter().any(|ty| ty.as_str() == event.ty))
+        .try_for_each(|event| {
+            NEXT_SEQ
+                .may_load(storage)
+                .map(Option::unwrap_or_default)
+                .and_then(|seq| {
+                    RECORDS
+                        .save(
+                            storage,
+                            seq,
+                            &Record {
+                                event: event.ty.clone(),
+                                attributes: event.attributes.clone(),
+                            },
+                        )
+                        .and_then(|()| NEXT_SEQ.save(storage, &(seq + 1)))
+                        .map(|()| {
+                            if let Some(dropped) = seq.checked_sub(MAX_RECORDS) {
+                                RECORDS.remove(storage, dropped)
+                            }
+                        })
+                })
+        })
+        .map_err(Into::into)
+}
+
+pub(super) fn load(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<HistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    RECORDS
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.try_into().expect("u32 fits in usize"))
+        .map(|item| {
+            item.map(|(seq, record)| HistoryRecord {
+                seq,
+                event: record.event,
+                attributes: record.attributes,
+            })
+        })
+        .collect::<Result<_, _>>()
+        .map(|records| HistoryResponse { records })
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::{testing::MockStorage, Event};
+
+    use crate::event::Type;
+
+    use super::{MAX_LIMIT, MAX_RECORDS};
+
+    #[test]
+    fn record_only_recorded() {
+        let mut storage = MockStorage::default();
+        super::record(
+            &mut storage,
+            &[
+                Event::new(Type::LiquidationWarning).add_attribute("level", "1"),
+                Event::new(Type::PaidActive).add_attribute("principal", "100"),
+            ],
+        )
+        .unwrap();
+        super::record(
+            &mut storage,
+            &[Event::new(Type::Liquidation).add_attribute("cause", "overdue interest")],
+        )
+        .unwrap();
+
+        let records = super::load(&storage, None, None).unwrap().records;
+        assert_eq!(2, records.len());
+        assert_eq!(0, records[0].seq);
+        assert_eq!(Type::PaidActive.as_str(), records[0].event);
+        assert_eq!("principal", records[0].attributes[0].key);
+        assert_eq!(1, records[1].seq);
+        assert_eq!(Type::Liquidation.as_str(), records[1].event);
+        assert_eq!("overdue interest", records[1].attributes[0].value);
+    }
+
+    #[test]
+    fn bounded() {
+        let mut storage = MockStorage::default();
+        (0..MAX_RECORDS + 3)
+            .for_each(|_| super::record(&mut storage, &[Event::new(Type::PaidActive)]).unwrap());
+
+        let first_page = super::load(&storage, None, Some(MAX_LIMIT))
+            .unwrap()
+            .records;
+        assert_eq!(3, first_page[0].seq);
+        assert_eq!(usize::try_from(MAX_LIMIT).unwrap(), first_page.len());
+
+        let last_page = super::load(&storage, Some(MAX_RECORDS), Some(MAX_LIMIT + 1))
+            .unwrap()
+            .records;
+        assert_eq!(2, last_page.len());
+        assert_eq!(MAX_RECORDS + 2, last_page[1].seq);
+    }
+}
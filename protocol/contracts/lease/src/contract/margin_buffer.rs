@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use finance::coin;
+use platform::batch::Batch;
+use sdk::cosmwasm_std::Addr;
+
+use crate::{
+    api::PaymentCoin,
+    error::{ContractError, ContractResult},
+    finance::LpnCoinDTO,
+};
+
+use super::funds;
+
+/// Customer funds held idle by the lease to cover a liability breach before resorting to
+/// a liquidation
+///
+/// The funds are kept in the lease account in a single payment currency.
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[serde(transparent)]
+pub(super) struct MarginBuffer(Option<PaymentCoin>);
+
+impl MarginBuffer {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn deposit(&mut self, amount: PaymentCoin) -> ContractResult<()> {
+        match self.0 {
+            None => {
+                self.0 = Some(amount);
+                Ok(())
+            }
+            Some(ref buffer) if buffer.currency() == amount.currency() => {
+                self.0 = Some(coin::from_amount_ticker(
+                    buffer.amount() + amount.amount(),
+                    buffer.currency(),
+                ));
+                Ok(())
+            }
+            Some(ref buffer) => Err(ContractError::MarginBufferCurrencyMismatch(
+                buffer.currency().to_string(),
+            )),
+        }
+    }
+
+    /// Take out all funds leaving the buffer empty
+    pub fn take(&mut self) -> Option<PaymentCoin> {
+        self.0.take()
+    }
+
+    /// Deduct the buffer from the LPN `balance` of the lease account if held in the LPN
+    pub fn exclude_from(&self, balance: LpnCoinDTO) -> LpnCoinDTO {
+        match self.0 {
+            Some(ref buffer) if buffer.currency() == balance.currency() => {
+                coin::from_amount_ticker(
+                    balance.amount().saturating_sub(buffer.amount()),
+                    balance.currency(),
+                )
+            }
+            _ => balance,
+        }
+    }
+
+    /// Take out all funds and send them to `customer`
+    pub fn take_out_to(&mut self, customer: Addr) -> ContractResult<Batch> {
+        self.take().map_or_else(
+            || Ok(Batch::default()),
+            |buffer| funds::send(&buffer, customer),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currencies::{
+        testing::{PaymentC3, PaymentC4},
+        Lpn,
+    };
+    use currency::{CurrencyDef, MemberOf};
+    use finance::coin::{Amount, Coin};
+
+    use crate::{
+        api::{LeasePaymentCurrencies, PaymentCoin},
+        error::ContractError,
+        finance::LpnCoinDTO,
+    };
+
+    use super::MarginBuffer;
+
+    #[test]
+    fn deposit() {
+        let mut buffer = MarginBuffer::default();
+        assert!(buffer.is_empty());
+
+        buffer.deposit(payment::<PaymentC3>(100)).unwrap();
+        buffer.deposit(payment::<PaymentC3>(50)).unwrap();
+        assert_eq!(
+            Err(ContractError::MarginBufferCurrencyMismatch(
+                currency::dto::<PaymentC3, LeasePaymentCurrencies>().to_string()
+            )),
+            buffer.deposit(payment::<PaymentC4>(10))
+        );
+        assert_eq!(Some(payment::<PaymentC3>(150)), buffer.take());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn exclude_from() {
+        let balance: LpnCoinDTO = Coin::<Lpn>::new(200).into();
+
+        let mut buffer = MarginBuffer::default();
+        assert_eq!(balance, buffer.exclude_from(balance));
+
+        buffer.deposit(payment::<PaymentC3>(100)).unwrap();
+        assert_eq!(balance, buffer.exclude_from(balance));
+
+        let mut buffer = MarginBuffer::default();
+        buffer.deposit(payment::<Lpn>(150)).unwrap();
+        assert_eq!(
+            LpnCoinDTO::from(Coin::<Lpn>::new(50)),
+            buffer.exclude_from(balance)
+        );
+    }
+
+    fn payment<C>(amount: Amount) -> PaymentCoin
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        Coin::<C>::new(amount).into()
+    }
+}
@@ -1,21 +1,33 @@
 use serde::{Deserialize, Serialize};
 
 use dex::{Account, ConnectionParams, DexConnectable};
-use sdk::cosmwasm_std::QuerierWrapper;
+use sdk::cosmwasm_std::{Addr, QuerierWrapper};
 
 use crate::{
+    api::OperatorPermission,
+    error::ContractResult,
     lease::{with_lease::WithLease, LeaseDTO},
     position::PositionError,
 };
 
 pub use self::endpoins::{execute, instantiate, migrate, query, reply, sudo};
-use self::finalize::FinalizerRef;
+use self::{
+    auto_repay::AutoRepay,
+    finalize::FinalizerRef,
+    margin_buffer::MarginBuffer,
+    operators::{CustomerOrOperator, Operators},
+};
 
 mod api;
+mod auto_repay;
 mod cmd;
 mod endpoins;
 mod finalize;
+mod funds;
+mod history;
+mod margin_buffer;
 pub mod msg;
+mod operators;
 mod state;
 
 #[derive(Serialize, Deserialize)]
@@ -24,6 +36,18 @@ pub(crate) struct Lease {
     lease: LeaseDTO,
     dex: Account,
     finalizer: FinalizerRef,
+    /// The new owner of a transfer of the lease ownership that has not been accepted yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending_owner: Option<Addr>,
+    /// The addresses permitted to manage the lease on behalf of the customer
+    #[serde(default, skip_serializing_if = "Operators::is_empty")]
+    operators: Operators,
+    /// The customer funds set aside to cover a liability breach before a liquidation
+    #[serde(default, skip_serializing_if = "MarginBuffer::is_empty")]
+    margin_buffer: MarginBuffer,
+    /// The customer funds set aside to repay the due interest once it gets overdue
+    #[serde(default, skip_serializing_if = "AutoRepay::is_empty")]
+    auto_repay: AutoRepay,
 }
 
 pub(crate) trait SplitDTOOut {
@@ -38,9 +62,22 @@ impl Lease {
             lease,
             dex,
             finalizer,
+            pending_owner: None,
+            operators: Operators::default(),
+            margin_buffer: MarginBuffer::default(),
+            auto_repay: AutoRepay::default(),
         }
     }
 
+    /// Check whether `user` is the customer or an operator permitted to perform `permission`
+    fn check_access(&self, user: &Addr, permission: OperatorPermission) -> ContractResult<()> {
+        access_control::check_permission(
+            &CustomerOrOperator::new(&self.lease.customer, &self.operators, permission),
+            user,
+        )
+        .map_err(Into::into)
+    }
+
     fn update<Cmd>(
         self,
         cmd: Cmd,
@@ -57,7 +94,18 @@ impl Lease {
     {
         self.lease.execute(cmd, querier).map(|result| {
             let (lease, other) = result.split_into();
-            (Self::new(lease, self.dex, self.finalizer), other)
+            (
+                Self {
+                    lease,
+                    dex: self.dex,
+                    finalizer: self.finalizer,
+                    pending_owner: self.pending_owner,
+                    operators: self.operators,
+                    margin_buffer: self.margin_buffer,
+                    auto_repay: self.auto_repay,
+                },
+                other,
+            )
         })
     }
 }
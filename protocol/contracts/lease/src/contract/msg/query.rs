@@ -1,24 +1,34 @@
 use currency::{CurrencyDef, MemberOf};
+use finance::{liability::Zone, price::dto::PriceDTO};
 
 use crate::{
     api::{
-        query::{opened, paid, StateResponse},
-        LeaseAssetCurrencies,
+        query::{
+            opened, paid, LiabilityStatus as APILiabilityStatus, LiabilityZone,
+            PriceLevels as APIPriceLevels, ProjectionResponse, SimulationOutcome,
+            SimulationResponse, StateResponse, SteadyPrices,
+        },
+        AssetPriceDTO, LeaseAssetCurrencies, LeasePaymentCurrencies,
     },
-    lease::{LeaseDTO, State},
+    finance::{LpnCurrency, Price},
+    lease::{LeaseDTO, LiabilityStatus, Projection, Simulation, State},
+    position::PriceLevels,
 };
 
 impl StateResponse {
-    pub fn opened_from<Asset>(
+    pub(crate) fn opened_from<Asset>(
         open_lease: State<Asset>,
+        liability: Option<LiabilityStatus<Asset>>,
         in_progress: Option<opened::OngoingTrx>,
     ) -> Self
     where
         Asset: CurrencyDef,
-        Asset::Group: MemberOf<LeaseAssetCurrencies>,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
     {
         Self::Opened {
+            kind: open_lease.kind,
             amount: open_lease.amount.into(),
+            basket: open_lease.basket,
             loan_interest_rate: open_lease.interest_rate,
             margin_interest_rate: open_lease.interest_rate_margin,
             principal_due: open_lease.principal_due.into(),
@@ -28,16 +38,118 @@ impl StateResponse {
             due_margin: open_lease.due_margin.into(),
             due_interest: open_lease.due_interest.into(),
             due_projection: open_lease.due_projection,
-            close_policy: open_lease.close_policy,
+            close_policy: Box::new(open_lease.close_policy),
+            liability: liability.map(liability_into_api).map(Box::new),
             validity: open_lease.validity,
             in_progress,
         }
     }
 
     pub fn paid_from(lease: LeaseDTO, in_progress: Option<paid::ClosingTrx>) -> Self {
+        let basket = lease.position.basket().coins().copied().collect();
         Self::Paid {
             amount: lease.position.into(),
+            basket,
             in_progress,
         }
     }
 }
+
+impl ProjectionResponse {
+    pub fn from_projection<Asset>(projection: Projection<Asset>) -> Self
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    {
+        Self {
+            amount: projection.amount.into(),
+            total_due: projection.total_due.into(),
+            price_levels: projection.price_levels.map(price_levels_into_api),
+            due_projection: projection.due_projection,
+            validity: projection.validity,
+        }
+    }
+}
+
+impl SimulationResponse {
+    pub(crate) fn from_simulation<Asset>(simulation: Simulation<Asset>) -> Self
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies>,
+    {
+        let receipt = simulation.receipt;
+        Self::Accepted(Box::new(SimulationOutcome {
+            amount: simulation.amount.into(),
+            payment: simulation.payment.into(),
+            interest_paid: (receipt.overdue_interest_paid() + receipt.due_interest_paid()).into(),
+            margin_paid: (receipt.overdue_margin_paid() + receipt.due_margin_paid()).into(),
+            principal_paid: receipt.principal_paid().into(),
+            change: receipt.change().into(),
+            principal_due: simulation.principal_due.into(),
+            ltv: simulation.ltv,
+        }))
+    }
+}
+
+fn price_levels_into_api<Asset>(levels: PriceLevels<Asset>) -> APIPriceLevels
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeasePaymentCurrencies>,
+{
+    APIPriceLevels {
+        first_liq_warn: price_into_api(levels.first_liq_warn),
+        second_liq_warn: price_into_api(levels.second_liq_warn),
+        third_liq_warn: price_into_api(levels.third_liq_warn),
+        liquidation: price_into_api(levels.liquidation),
+        take_profit: levels.take_profit.map(price_into_api),
+        stop_loss: levels.stop_loss.map(price_into_api),
+    }
+}
+
+fn liability_into_api<Asset>(liability: LiabilityStatus<Asset>) -> APILiabilityStatus
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeasePaymentCurrencies>,
+{
+    match liability {
+        LiabilityStatus::Steady {
+            ltv,
+            zone,
+            steadiness,
+            next_check,
+        } => APILiabilityStatus::Steady {
+            ltv,
+            zone: zone_into_api(zone),
+            steady_prices: SteadyPrices {
+                above: price_into_api(*steadiness.within().above()),
+                below_or_equal: steadiness
+                    .within()
+                    .may_below_or_equal()
+                    .copied()
+                    .map(price_into_api),
+            },
+            next_check,
+        },
+        LiabilityStatus::CloseDue { ltv } => APILiabilityStatus::CloseDue { ltv },
+    }
+}
+
+fn zone_into_api(zone: Zone) -> LiabilityZone {
+    LiabilityZone {
+        level: zone.low().map_or(0, |low| low.ordinal()),
+        low: zone.low().map(Into::into),
+        high: zone.high().into(),
+    }
+}
+
+fn price_into_api<Asset>(price: Price<Asset>) -> AssetPriceDTO
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeasePaymentCurrencies>,
+{
+    PriceDTO::from_price(
+        price,
+        currency::dto::<Asset, LeasePaymentCurrencies>(),
+        currency::dto::<LpnCurrency, LeasePaymentCurrencies>(),
+    )
+}
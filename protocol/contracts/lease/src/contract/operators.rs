@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use access_control::AccessPermission;
+use sdk::cosmwasm_std::Addr;
+
+use crate::{
+    api::OperatorPermission,
+    error::{ContractError, ContractResult},
+};
+
+/// The maximum number of operators of a lease
+const MAX_OPERATORS: usize = 5;
+
+/// Addresses permitted to manage the lease on behalf of the customer
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[serde(transparent)]
+pub(super) struct Operators(Vec<Operator>);
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Operator {
+    addr: Addr,
+    /// The permitted operations, or all of them if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    permissions: Option<Vec<OperatorPermission>>,
+}
+
+impl Operators {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn grant(
+        &mut self,
+        operator: Addr,
+        permissions: Option<Vec<OperatorPermission>>,
+    ) -> ContractResult<()> {
+        let permissions = permissions.map(|mut permissions| {
+            permissions.sort_unstable();
+            permissions.dedup();
+            permissions
+        });
+        if let Some(granted) = self.0.iter_mut().find(|op| op.addr == operator) {
+            granted.permissions = permissions;
+            Ok(())
+        } else if self.0.len() < MAX_OPERATORS {
+            self.0.push(Operator {
+                addr: operator,
+                permissions,
+            });
+            Ok(())
+        } else {
+            Err(ContractError::TooManyOperators(MAX_OPERATORS))
+        }
+    }
+
+    pub fn revoke(&mut self, operator: Addr) -> ContractResult<()> {
+        let operators_before = self.0.len();
+        self.0.retain(|op| op.addr != operator);
+        if self.0.len() == operators_before {
+            Err(ContractError::OperatorNotFound(operator))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn revoke_all(&mut self) {
+        self.0.clear()
+    }
+
+    fn permitted(&self, user: &Addr, permission: OperatorPermission) -> bool {
+        self.0.iter().any(|op| {
+            op.addr == *user
+                && op
+                    .permissions
+                    .as_ref()
+                    .is_none_or(|permissions| permissions.contains(&permission))
+        })
+    }
+}
+
+/// The permission of the customer, and the operators granted to, to perform an operation
+pub(super) struct CustomerOrOperator<'lease> {
+    customer: &'lease Addr,
+    operators: &'lease Operators,
+    permission: OperatorPermission,
+}
+
+impl<'lease> CustomerOrOperator<'lease> {
+    pub const fn new(
+        customer: &'lease Addr,
+        operators: &'lease Operators,
+        permission: OperatorPermission,
+    ) -> Self {
+        Self {
+            customer,
+            operators,
+            permission,
+        }
+    }
+}
+
+impl AccessPermission for CustomerOrOperator<'_> {
+    fn granted_to(&self, user: &Addr) -> bool {
+        self.customer == user || self.operators.permitted(user, self.permission)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use access_control::error::Error as AccessError;
+    use sdk::cosmwasm_std::Addr;
+
+    use crate::{api::OperatorPermission, error::ContractError};
+
+    use super::{CustomerOrOperator, Operators, MAX_OPERATORS};
+
+    const CUSTOMER: &str = "customer";
+    const BOT: &str = "bot";
+
+    #[test]
+    fn customer_always_permitted() {
+        let operators = Operators::default();
+        check(&operators, CUSTOMER, OperatorPermission::FullClose).unwrap();
+        assert_eq!(
+            AccessError::Unauthorized {},
+            check(&operators, BOT, OperatorPermission::FullClose).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn all_permissions() {
+        let mut operators = Operators::default();
+        operators.grant(Addr::unchecked(BOT), None).unwrap();
+        check(&operators, BOT, OperatorPermission::ChangeClosePolicy).unwrap();
+        check(&operators, BOT, OperatorPermission::PartialClose).unwrap();
+        check(&operators, BOT, OperatorPermission::FullClose).unwrap();
+        assert!(check(&operators, "another", OperatorPermission::FullClose).is_err());
+    }
+
+    #[test]
+    fn selected_permissions() {
+        let mut operators = Operators::default();
+        operators
+            .grant(
+                Addr::unchecked(BOT),
+                Some(vec![
+                    OperatorPermission::ChangeClosePolicy,
+                    OperatorPermission::ChangeClosePolicy,
+                ]),
+            )
+            .unwrap();
+        check(&operators, BOT, OperatorPermission::ChangeClosePolicy).unwrap();
+        assert!(check(&operators, BOT, OperatorPermission::PartialClose).is_err());
+        assert!(check(&operators, BOT, OperatorPermission::FullClose).is_err());
+
+        operators
+            .grant(
+                Addr::unchecked(BOT),
+                Some(vec![OperatorPermission::FullClose]),
+            )
+            .unwrap();
+        assert!(check(&operators, BOT, OperatorPermission::ChangeClosePolicy).is_err());
+        check(&operators, BOT, OperatorPermission::FullClose).unwrap();
+    }
+
+    #[test]
+    fn revoke() {
+        let mut operators = Operators::default();
+        operators.grant(Addr::unchecked(BOT), None).unwrap();
+        operators.revoke(Addr::unchecked(BOT)).unwrap();
+        assert!(operators.is_empty());
+        assert!(check(&operators, BOT, OperatorPermission::PartialClose).is_err());
+
+        assert_eq!(
+            Err(ContractError::OperatorNotFound(Addr::unchecked(BOT))),
+            operators.revoke(Addr::unchecked(BOT))
+        );
+    }
+
+    #[test]
+    fn too_many() {
+        let mut operators = Operators::default();
+        (0..MAX_OPERATORS).for_each(|i| {
+            operators
+                .grant(Addr::unchecked(format!("bot{i}")), None)
+                .unwrap()
+        });
+        operators.grant(Addr::unchecked("bot0"), None).unwrap();
+        assert_eq!(
+            Err(ContractError::TooManyOperators(MAX_OPERATORS)),
+            operators.grant(Addr::unchecked(BOT), None)
+        );
+    }
+
+    fn check(
+        operators: &Operators,
+        user: &str,
+        permission: OperatorPermission,
+    ) -> access_control::error::Result {
+        let customer = Addr::unchecked(CUSTOMER);
+        access_control::check_permission(
+            &CustomerOrOperator::new(&customer, operators, permission),
+            &Addr::unchecked(user),
+        )
+    }
+}
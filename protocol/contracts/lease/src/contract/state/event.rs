@@ -41,6 +41,23 @@ impl RepayEmitter for PositionCloseEmitter<'_> {
     }
 }
 
+pub(crate) struct RepayFromPositionEmitter<'env> {
+    amount: LeaseCoin,
+    env: &'env Env,
+}
+
+impl<'env> RepayFromPositionEmitter<'env> {
+    pub fn new(amount: LeaseCoin, env: &'env Env) -> Self {
+        Self { amount, env }
+    }
+}
+impl RepayEmitter for RepayFromPositionEmitter<'_> {
+    fn emit(self, lease: &Addr, receipt: &RepayReceipt) -> Emitter {
+        let emitter = emit_payment_int(Type::PaidActive, self.env, lease, receipt);
+        emitter.emit_coin_dto("position-sold", &self.amount)
+    }
+}
+
 pub(super) fn emit_payment_int(
     event_type: Type,
     env: &Env,
@@ -60,14 +77,14 @@ pub(super) fn emit_payment_int(
         .emit_coin_amount("change", receipt.change())
 }
 
-pub(super) fn emit_auto_close(strategy: CloseStrategy, env: &Env, lease: &Addr) -> Emitter {
+pub(super) fn emit_auto_close(strategy: &CloseStrategy, env: &Env, lease: &Addr) -> Emitter {
     let emitter = Emitter::of_type(Type::AutoClosePosition)
         .emit_tx_info(env)
         .emit("to", lease);
 
     match strategy {
-        CloseStrategy::TakeProfit(tp) => emitter.emit_percent_amount("take-profit-ltv", tp),
-        CloseStrategy::StopLoss(sl) => emitter.emit_percent_amount("stop-loss-ltv", sl),
+        CloseStrategy::TakeProfit(tp) => emitter.emit_percent_amount("take-profit-ltv", *tp),
+        CloseStrategy::StopLoss(sl) => emitter.emit_percent_amount("stop-loss-ltv", *sl),
     }
 }
 
@@ -1,12 +1,13 @@
 use finance::duration::Duration;
 use serde::{Deserialize, Serialize};
 
-use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
+use sdk::cosmwasm_std::{Addr, Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
 
 use crate::{
     api::{
         position::{ClosePolicyChange, PositionClose},
-        query::StateResponse,
+        query::{ProjectionResponse, SimulateAction, SimulationResponse, StateResponse},
+        LeaseCoin, LpnCoinDTO, OperatorPermission,
     },
     error::ContractResult,
 };
@@ -38,6 +39,24 @@ where
         self.handler.state(now, due_projection, querier)
     }
 
+    fn projection(
+        self,
+        now: Timestamp,
+        due_projection: Duration,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<ProjectionResponse> {
+        self.handler.projection(now, due_projection, querier)
+    }
+
+    fn simulate(
+        self,
+        action: SimulateAction,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<SimulationResponse> {
+        self.handler.simulate(action, now, querier)
+    }
+
     fn reply(self, querier: QuerierWrapper<'_>, env: Env, msg: Reply) -> ContractResult<Response> {
         self.handler.reply(querier, env, msg)
     }
@@ -71,6 +90,115 @@ where
         self.handler.close_position(spec, querier, env, info)
     }
 
+    fn repay_from_position(
+        self,
+        amount: LeaseCoin,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.repay_from_position(amount, querier, env, info)
+    }
+
+    fn increase_position(
+        self,
+        borrow: Option<LpnCoinDTO>,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.increase_position(borrow, querier, env, info)
+    }
+
+    fn transfer_ownership(
+        self,
+        new_owner: Addr,
+        require_accept: bool,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler
+            .transfer_ownership(new_owner, require_accept, querier, env, info)
+    }
+
+    fn accept_ownership(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.accept_ownership(querier, env, info)
+    }
+
+    fn grant_operator(
+        self,
+        operator: Addr,
+        permissions: Option<Vec<OperatorPermission>>,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler
+            .grant_operator(operator, permissions, querier, env, info)
+    }
+
+    fn revoke_operator(
+        self,
+        operator: Addr,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.revoke_operator(operator, querier, env, info)
+    }
+
+    fn deposit_margin_buffer(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.deposit_margin_buffer(querier, env, info)
+    }
+
+    fn withdraw_margin_buffer(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.withdraw_margin_buffer(querier, env, info)
+    }
+
+    fn deposit_auto_repay(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.deposit_auto_repay(querier, env, info)
+    }
+
+    fn withdraw_auto_repay(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.withdraw_auto_repay(querier, env, info)
+    }
+
+    fn refinance(
+        self,
+        lpp: Addr,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.handler.refinance(lpp, querier, env, info)
+    }
+
     fn close(
         self,
         querier: QuerierWrapper<'_>,
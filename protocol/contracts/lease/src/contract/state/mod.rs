@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use platform::{batch::Batch, message::Response as MessageResponse};
 use sdk::{
-    cosmwasm_std::{Binary, Env, MessageInfo, QuerierWrapper, Reply, Storage, Timestamp},
+    cosmwasm_std::{Addr, Binary, Env, MessageInfo, QuerierWrapper, Reply, Storage, Timestamp},
     cw_storage_plus::Item,
 };
 use swap::Impl;
@@ -15,7 +15,8 @@ use crate::{
     api::{
         open::NewLeaseContract,
         position::{ClosePolicyChange, PositionClose},
-        query::StateResponse,
+        query::{ProjectionResponse, SimulateAction, SimulationResponse, StateResponse},
+        LeaseCoin, LpnCoinDTO, OperatorPermission,
     },
     contract::api::Contract,
     error::ContractResult,
@@ -36,6 +37,8 @@ mod opening;
 mod paid;
 mod resp_delivery;
 
+type Pending = LeaseState<opening::pending::Pending>;
+
 type RequestLoan = LeaseState<opening::request_loan::RequestLoan>;
 
 type BuyAsset = DexState<opening::buy_asset::DexState>;
@@ -44,6 +47,8 @@ type OpenedActive = LeaseState<opened::active::Active>;
 
 type BuyLpn = DexState<opened::repay::buy_lpn::DexState>;
 
+type IncreasePosition = DexState<opened::increase::buy_asset::DexState>;
+
 type PartialLiquidation = DexState<opened::close::liquidation::partial::DexState>;
 
 type FullLiquidation = DexState<opened::close::liquidation::full::DexState>;
@@ -52,6 +57,10 @@ type PartialClose = DexState<opened::close::customer_close::partial::DexState>;
 
 type FullClose = DexState<opened::close::customer_close::full::DexState>;
 
+type RepayFromPosition = DexState<opened::close::repay_from_position::DexState>;
+
+type Refinance = LeaseState<opened::refinance::Refinance>;
+
 type PaidActive = LeaseState<paid::Active>;
 
 type ClosingTransferIn = DexState<paid::transfer_in::DexState>;
@@ -67,14 +76,18 @@ type SwapClient = Impl;
 #[enum_dispatch(Contract)]
 #[derive(Serialize, Deserialize)]
 pub(crate) enum State {
+    Pending,
     RequestLoan,
     BuyAsset,
     OpenedActive,
     BuyLpn,
+    IncreasePosition,
     PartialLiquidation,
     FullLiquidation,
     PartialClose,
     FullClose,
+    RepayFromPosition,
+    Refinance,
     PaidActive,
     ClosingTransferIn,
     Closed,
@@ -93,11 +106,20 @@ pub(super) fn save(storage: &mut dyn Storage, next_state: &State) -> ContractRes
 
 pub(super) fn new_lease(
     querier: QuerierWrapper<'_>,
+    env: &Env,
     info: MessageInfo,
-    spec: NewLeaseContract,
+    mut spec: NewLeaseContract,
 ) -> ContractResult<(Batch, State)> {
-    opening::request_loan::RequestLoan::new(querier, info, spec)
-        .map(|(batch, start_state)| (batch, start_state.into()))
+    opening::check_basket(&spec.form)?;
+
+    match spec.form.open_at.take() {
+        Some(open_at) => {
+            opening::pending::Pending::new(querier, &env.block.time, info, spec, open_at)
+                .map(|(batch, start_state)| (batch, start_state.into()))
+        }
+        None => opening::request_loan::RequestLoan::new(querier, info.funds, spec)
+            .map(|(batch, _borrow, start_state)| (batch, start_state.into())),
+    }
 }
 
 fn ignore_msg<S>(state: S) -> ContractResult<Response>
@@ -109,10 +131,17 @@ where
 
 mod impl_from {
     use super::{
-        BuyAsset, BuyLpn, Closed, ClosingTransferIn, FullClose, FullLiquidation, Liquidated,
-        OpenedActive, PaidActive, PartialClose, PartialLiquidation, RequestLoan, State,
+        BuyAsset, BuyLpn, Closed, ClosingTransferIn, FullClose, FullLiquidation, IncreasePosition,
+        Liquidated, OpenedActive, PaidActive, PartialClose, PartialLiquidation, Pending, Refinance,
+        RepayFromPosition, RequestLoan, State,
     };
 
+    impl From<super::opening::pending::Pending> for State {
+        fn from(value: super::opening::pending::Pending) -> Self {
+            Pending::new(value).into()
+        }
+    }
+
     impl From<super::opening::request_loan::RequestLoan> for State {
         fn from(value: super::opening::request_loan::RequestLoan) -> Self {
             RequestLoan::new(value).into()
@@ -137,6 +166,12 @@ mod impl_from {
         }
     }
 
+    impl From<super::opened::increase::buy_asset::DexState> for State {
+        fn from(value: super::opened::increase::buy_asset::DexState) -> Self {
+            IncreasePosition::new(value).into()
+        }
+    }
+
     impl From<super::opened::close::liquidation::partial::DexState> for State {
         fn from(value: super::opened::close::liquidation::partial::DexState) -> Self {
             PartialLiquidation::new(value).into()
@@ -161,6 +196,18 @@ mod impl_from {
         }
     }
 
+    impl From<super::opened::close::repay_from_position::DexState> for State {
+        fn from(value: super::opened::close::repay_from_position::DexState) -> Self {
+            RepayFromPosition::new(value).into()
+        }
+    }
+
+    impl From<super::opened::refinance::Refinance> for State {
+        fn from(value: super::opened::refinance::Refinance) -> Self {
+            Refinance::new(value).into()
+        }
+    }
+
     impl From<super::paid::Active> for State {
         fn from(value: super::paid::Active) -> Self {
             PaidActive::new(value).into()
@@ -4,29 +4,30 @@ use serde::{Deserialize, Serialize};
 use dex::Enterable;
 use finance::{coin::IntoDTO, duration::Duration};
 use platform::{bank, batch::Emitter, message::Response as MessageResponse};
-use sdk::cosmwasm_std::{Coin as CwCoin, Env, MessageInfo, QuerierWrapper, Timestamp};
+use sdk::cosmwasm_std::{Addr, Coin as CwCoin, Env, MessageInfo, QuerierWrapper, Timestamp};
 
 use crate::{
     api::{
         position::{ClosePolicyChange, PositionClose},
-        query::StateResponse,
-        DownpaymentCoin,
+        query::{ProjectionResponse, SimulateAction, SimulationResponse, StateResponse},
+        DownpaymentCoin, LeaseCoin, OperatorPermission,
     },
     contract::{
         cmd::{
-            ChangeClosePolicy, CloseStatusCmd, CloseStatusDTO, ObtainPayment, OpenLoanRespResult,
+            AutoRepayAmount, ChangeClosePolicy, CloseStatusCmd, CloseStatusDTO, LeaseProjection,
+            ObtainPayment, OpenLoanRespResult, Simulate,
         },
         state::{Handler, Response},
         Lease,
     },
     error::{ContractError, ContractResult},
-    finance::LpnCurrencies,
+    finance::{LpnCoinDTO, LpnCurrencies},
 };
 
 use super::{
-    alarm, balance,
-    close::{customer_close, liquidation},
-    event,
+    alarm, auto_repay, balance,
+    close::{customer_close, liquidation, repay_from_position},
+    event, increase, margin_buffer, operators, ownership, refinance,
     repay::{
         self,
         buy_lpn::{self, DexState as BuyLpnState},
@@ -99,14 +100,34 @@ impl Active {
             ));
         }
 
-        self.try_on_alarm(querier, env)
+        // the due interest is repaid from the budget before checking the position
+        // so an overdue collection is avoided
+        match self.lease.auto_repay.budget().copied() {
+            Some(budget) => self
+                .lease
+                .lease
+                .clone()
+                .execute(AutoRepayAmount::new(budget, &env.block.time), querier)
+                .and_then(|may_amount| match may_amount {
+                    Some(amount) => auto_repay::repay(self.lease, amount, env, querier),
+                    None => self.try_on_alarm(querier, env),
+                }),
+            None => self.try_on_alarm(querier, env),
+        }
     }
 
-    fn try_on_alarm(self, querier: QuerierWrapper<'_>, env: &Env) -> ContractResult<Response> {
-        let time_alarms_ref = self.lease.lease.time_alarms.clone();
+    pub(in super::super) fn try_on_alarm(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: &Env,
+    ) -> ContractResult<Response> {
+        let profit = self.lease.lease.loan.profit().clone();
+        let time_alarms = self.lease.lease.time_alarms.clone();
         let oracle_ref = self.lease.lease.oracle.clone();
-        let close_status = self.lease.lease.clone().execute(
-            CloseStatusCmd::new(&env.block.time, &time_alarms_ref, &oracle_ref),
+        let reserve = self.lease.lease.reserve.clone();
+        // the lease is updated since the close policy might have been brought up to date
+        let (lease, close_status) = self.lease.update(
+            CloseStatusCmd::new(&env.block.time, profit, time_alarms, &oracle_ref, reserve),
             querier,
         )?;
 
@@ -116,24 +137,47 @@ impl Active {
             }
             CloseStatusDTO::None {
                 current_liability,
+                overdue_warning,
+                dropped_triggers,
                 alarms,
             } => Ok(Response::from(
-                alarm::build_resp(&self.lease, current_liability, alarms),
-                self,
+                alarm::build_resp(
+                    &lease,
+                    current_liability,
+                    overdue_warning,
+                    dropped_triggers,
+                    alarms,
+                ),
+                Self::new(lease),
             )),
-            CloseStatusDTO::NeedLiquidation(liquidation) => liquidation::start(
-                self.lease,
-                liquidation,
-                MessageResponse::default(),
-                env,
-                querier,
-            ),
+            CloseStatusDTO::NeedLiquidation(liquidation) => {
+                liquidation::start(lease, liquidation, MessageResponse::default(), env, querier)
+            }
             CloseStatusDTO::CloseAsked(strategy) => {
-                customer_close::auto_start(strategy, self.lease, env, querier)
+                customer_close::auto_start(strategy, lease, env, querier)
             }
         }
     }
 
+    fn try_increase_position(
+        self,
+        borrow: Option<LpnCoinDTO>,
+        querier: QuerierWrapper<'_>,
+        env: &Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        access_control::check(&self.lease.lease.customer, &info.sender)
+            .map_err(Into::into)
+            .and_then(|()| {
+                // the payment is validated against the minimum transaction amount
+                self.lease
+                    .lease
+                    .clone()
+                    .execute(ObtainPayment::new(info.funds), querier)
+            })
+            .and_then(|payment| increase::start(self.lease, payment, borrow, env, querier))
+    }
+
     fn start_swap(
         self,
         cw_amount: Vec<CwCoin>,
@@ -170,6 +214,29 @@ impl Handler for Active {
         super::lease_state(self.lease, None, now, due_projection, querier)
     }
 
+    fn projection(
+        self,
+        now: Timestamp,
+        due_projection: Duration,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<ProjectionResponse> {
+        self.lease
+            .lease
+            .execute(LeaseProjection::new(now, due_projection), querier)
+    }
+
+    fn simulate(
+        self,
+        action: SimulateAction,
+        now: Timestamp,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<SimulationResponse> {
+        let profit = self.lease.lease.loan.profit().clone();
+        self.lease
+            .lease
+            .execute(Simulate::new(action, &now, profit), querier)
+    }
+
     fn repay(
         self,
         querier: QuerierWrapper<'_>,
@@ -186,13 +253,14 @@ impl Handler for Active {
         env: Env,
         info: MessageInfo,
     ) -> ContractResult<Response> {
-        access_control::check(&self.lease.lease.customer, &info.sender)
-            .map_err(Into::into)
+        self.lease
+            .check_access(&info.sender, OperatorPermission::ChangeClosePolicy)
             .and_then(|()| {
                 let profit = self.lease.lease.loan.profit().clone();
                 let time_alarms = self.lease.lease.time_alarms.clone();
                 let oracle_ref = self.lease.lease.oracle.clone();
                 let reserve = self.lease.lease.reserve.clone();
+                let emitter = event::emit_close_policy_changed(&env, &self.lease.lease, &change);
                 self.lease
                     .update(
                         ChangeClosePolicy::new(
@@ -205,7 +273,12 @@ impl Handler for Active {
                         ),
                         querier,
                     )
-                    .map(|(lease, batch)| Response::from(batch, Self::new(lease)))
+                    .map(|(lease, batch)| {
+                        Response::from(
+                            MessageResponse::messages_with_events(batch, emitter),
+                            Self::new(lease),
+                        )
+                    })
             })
     }
 
@@ -215,10 +288,135 @@ impl Handler for Active {
         querier: QuerierWrapper<'_>,
         env: Env,
         info: MessageInfo,
+    ) -> ContractResult<Response> {
+        let permission = match spec {
+            PositionClose::FullClose(_) => OperatorPermission::FullClose,
+            PositionClose::PartialClose(_)
+            | PositionClose::PartialClosePercent(_)
+            | PositionClose::PartialCloseLtv(_) => OperatorPermission::PartialClose,
+        };
+        self.lease
+            .check_access(&info.sender, permission)
+            .and_then(|()| customer_close::start(spec, self.lease, &env, querier))
+    }
+
+    fn repay_from_position(
+        self,
+        amount: LeaseCoin,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.lease
+            .check_access(&info.sender, OperatorPermission::PartialClose)
+            .and_then(|()| repay_from_position::start(amount, self.lease, &env, querier))
+    }
+
+    fn increase_position(
+        self,
+        borrow: Option<LpnCoinDTO>,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        self.try_increase_position(borrow, querier, &env, info)
+    }
+
+    fn transfer_ownership(
+        self,
+        new_owner: Addr,
+        require_accept: bool,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        ownership::request(self.lease, new_owner, require_accept, &env, &info)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn accept_ownership(
+        self,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        ownership::accept(self.lease, &env, &info)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn grant_operator(
+        self,
+        operator: Addr,
+        permissions: Option<Vec<OperatorPermission>>,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        operators::grant(self.lease, operator, permissions, &env, &info)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn revoke_operator(
+        self,
+        operator: Addr,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        operators::revoke(self.lease, operator, &env, &info)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn deposit_margin_buffer(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        margin_buffer::deposit(self.lease, &env, info, querier)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn withdraw_margin_buffer(
+        self,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        margin_buffer::withdraw(self.lease, &env, &info)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn deposit_auto_repay(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        auto_repay::deposit(self.lease, &env, info, querier)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn withdraw_auto_repay(
+        self,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        auto_repay::withdraw(self.lease, &env, &info)
+            .map(|(lease, response)| Response::from(response, Self::new(lease)))
+    }
+
+    fn refinance(
+        self,
+        lpp: Addr,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
     ) -> ContractResult<Response> {
         access_control::check(&self.lease.lease.customer, &info.sender)
             .map_err(Into::into)
-            .and_then(|()| customer_close::start(spec, self.lease, &env, querier))
+            .and_then(|()| refinance::start(self.lease, lpp, &env, querier))
     }
 
     fn on_time_alarm(
@@ -247,6 +445,11 @@ impl Handler for Active {
     ) -> ContractResult<Response> {
         let lease_addr = self.lease.lease.addr.clone();
         balance::lpn_balance(&lease_addr, querier).and_then(|balance| {
+            // neither the margin buffer, nor the auto-repay budget is a payment
+            let balance = self
+                .lease
+                .auto_repay
+                .exclude_from(self.lease.margin_buffer.exclude_from(balance));
             if balance.is_zero() {
                 Err(ContractError::InconsistencyNotDetected())
             } else {
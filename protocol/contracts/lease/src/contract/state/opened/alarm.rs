@@ -1,17 +1,34 @@
 use finance::liability::Zone;
 use platform::{batch::Batch, message::Response as MessageResponse};
 
-use crate::contract::Lease;
+use crate::{
+    contract::Lease,
+    position::{CloseStrategy, OverdueWarning},
+};
 
 use super::event;
 
-pub(super) fn build_resp(lease: &Lease, current_liability: Zone, alarms: Batch) -> MessageResponse {
-    if let Some(events) = current_liability
+pub(super) fn build_resp(
+    lease: &Lease,
+    current_liability: Zone,
+    overdue_warning: Option<OverdueWarning>,
+    dropped_triggers: Vec<CloseStrategy>,
+    alarms: Batch,
+) -> MessageResponse {
+    let resp = if let Some(events) = current_liability
         .low()
         .map(|low_level| event::emit_liquidation_warning(&lease.lease, &low_level))
     {
         MessageResponse::messages_with_events(alarms, events)
     } else {
         MessageResponse::messages_only(alarms)
-    }
+    };
+    let resp = if let Some(warning) = overdue_warning {
+        resp.merge_with(event::emit_overdue_warning(&lease.lease, &warning))
+    } else {
+        resp
+    };
+    dropped_triggers.iter().fold(resp, |resp, trigger| {
+        resp.merge_with(event::emit_close_trigger_dropped(&lease.lease, trigger))
+    })
 }
@@ -0,0 +1,70 @@
+use platform::message::Response as MessageResponse;
+use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper};
+
+use crate::{
+    api::PaymentCoin,
+    contract::{cmd::ObtainPayment, funds, state::Response, Lease},
+    error::{ContractError, ContractResult},
+};
+
+use super::{event, repay};
+
+/// Add the funds attached by the customer to the auto-repay budget
+pub(super) fn deposit(
+    mut lease: Lease,
+    env: &Env,
+    info: MessageInfo,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            lease
+                .lease
+                .clone()
+                .execute(ObtainPayment::new(info.funds), querier)
+        })
+        .and_then(|payment| {
+            let events = event::emit_auto_repay_deposit(env, &lease.lease, &payment);
+            lease
+                .auto_repay
+                .deposit(payment)
+                .map(|()| (lease, events.into()))
+        })
+}
+
+/// Send the whole auto-repay budget back to the customer
+pub(super) fn withdraw(
+    mut lease: Lease,
+    env: &Env,
+    info: &MessageInfo,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            lease
+                .auto_repay
+                .take()
+                .ok_or_else(ContractError::AutoRepayEmpty)
+        })
+        .and_then(|budget| {
+            let events = event::emit_auto_repay_withdraw(env, &lease.lease, &budget);
+            funds::send(&budget, lease.lease.customer.clone())
+                .map(|msgs| (lease, MessageResponse::messages_with_events(msgs, events)))
+        })
+}
+
+/// Repay the loan with `amount` out of the auto-repay budget
+///
+/// Pre-condition: `amount` does not exceed the budget.
+pub(super) fn repay(
+    mut lease: Lease,
+    amount: PaymentCoin,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    lease.auto_repay.spend(&amount);
+    let events = event::emit_auto_repay(env, &lease.lease, &amount, lease.auto_repay.budget());
+
+    repay::repay_with(lease, amount, events.into(), env, querier)
+}
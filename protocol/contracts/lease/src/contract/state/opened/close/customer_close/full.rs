@@ -1,3 +1,4 @@
+use finance::percent::Percent;
 use profit::stub::ProfitStub;
 use sdk::cosmwasm_std::Env;
 
@@ -38,6 +39,10 @@ impl Closable for Spec {
         lease.lease.position.amount()
     }
 
+    fn coins(&self, lease: &Lease) -> Vec<LeaseCoin> {
+        lease.lease.position.coins()
+    }
+
     fn transaction(&self, lease: &Lease, in_progress: PositionCloseTrx) -> OngoingTrx {
         OngoingTrx::Close {
             close: *self.amount(lease),
@@ -48,6 +53,10 @@ impl Closable for Spec {
     fn event_type(&self) -> Type {
         Type::ClosePosition
     }
+
+    fn max_slippage(&self, _lease: &Lease) -> Option<Percent> {
+        self.max_slippage
+    }
 }
 
 impl CloseAlgo for Spec {
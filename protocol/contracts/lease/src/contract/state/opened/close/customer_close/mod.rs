@@ -1,11 +1,11 @@
-use platform::message::Response as MessageResponse;
+use platform::{batch::Emit, message::Response as MessageResponse};
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
     api::position::{FullClose, PositionClose},
     contract::{
-        cmd::ValidateClosePosition,
-        state::{event, Response},
+        cmd::{AutoClose, AutoCloseSpec, ValidateClosePosition},
+        state::{event, opened::active::Active, Response},
         Lease,
     },
     error::ContractResult,
@@ -24,14 +24,16 @@ pub(in super::super) fn start(
     querier: QuerierWrapper<'_>,
 ) -> ContractResult<Response> {
     match close {
-        PositionClose::PartialClose(spec) => lease
-            .lease
-            .clone()
-            .execute(ValidateClosePosition::new(&spec), querier)
-            .and_then(|()| spec.start(lease, MessageResponse::default(), env, querier)),
         PositionClose::FullClose(spec) => {
             spec.start(lease, MessageResponse::default(), env, querier)
         }
+        PositionClose::PartialClose(_)
+        | PositionClose::PartialClosePercent(_)
+        | PositionClose::PartialCloseLtv(_) => lease
+            .lease
+            .clone()
+            .execute(ValidateClosePosition::new(&close, &env.block.time), querier)
+            .and_then(|spec| spec.start(lease, MessageResponse::default(), env, querier)),
     }
 }
 
@@ -41,6 +43,32 @@ pub(in super::super) fn auto_start(
     env: &Env,
     querier: QuerierWrapper<'_>,
 ) -> ContractResult<Response> {
-    let events = event::emit_auto_close(strategy, env, &lease.lease.addr);
-    FullClose {}.start(lease, events.into(), env, querier)
+    let events = event::emit_auto_close(&strategy, env, &lease.lease.addr);
+    let profit = lease.lease.loan.profit().clone();
+    let time_alarms = lease.lease.time_alarms.clone();
+    let reserve = lease.lease.reserve.clone();
+    // the lease is updated since a partial trigger is consumed
+    lease
+        .update(
+            AutoClose::new(&strategy, profit, time_alarms, reserve),
+            querier,
+        )
+        .and_then(|(lease, close)| match close {
+            AutoCloseSpec::Full => {
+                FullClose { max_slippage: None }.start(lease, events.into(), env, querier)
+            }
+            AutoCloseSpec::Partial(spec) => spec.start(lease, events.into(), env, querier),
+            // the consumed trigger does not fire anymore, so the rest of the policy is checked
+            AutoCloseSpec::Skipped(cause) => Active::new(lease).try_on_alarm(querier, env).map(
+                |Response {
+                     response,
+                     next_state,
+                 }| {
+                    Response::from(
+                        response.merge_with(events.emit_to_string_value("skipped", cause)),
+                        next_state,
+                    )
+                },
+            ),
+        })
 }
@@ -1,3 +1,4 @@
+use finance::percent::Percent;
 use sdk::cosmwasm_std::Env;
 
 use crate::{
@@ -47,6 +48,10 @@ impl Closable for Spec {
     fn event_type(&self) -> Type {
         Type::ClosePosition
     }
+
+    fn max_slippage(&self, _lease: &Lease) -> Option<Percent> {
+        self.max_slippage
+    }
 }
 
 impl RepayAlgo for Spec {
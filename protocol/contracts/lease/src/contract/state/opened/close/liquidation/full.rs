@@ -1,3 +1,4 @@
+use finance::percent::Percent;
 use profit::stub::ProfitStub;
 use sdk::cosmwasm_std::Env;
 
@@ -38,6 +39,10 @@ impl Closable for Spec {
         lease.lease.position.amount()
     }
 
+    fn coins(&self, lease: &Lease) -> Vec<LeaseCoin> {
+        lease.lease.position.coins()
+    }
+
     fn transaction(&self, lease: &Lease, in_progress: PositionCloseTrx) -> OngoingTrx {
         OngoingTrx::Liquidation {
             liquidation: *self.amount(lease),
@@ -48,6 +53,10 @@ impl Closable for Spec {
     fn event_type(&self) -> Type {
         Type::LiquidationSwap
     }
+
+    fn max_slippage(&self, lease: &Lease) -> Option<Percent> {
+        lease.lease.position.liquidation_max_slippage()
+    }
 }
 
 impl CloseAlgo for Spec {
@@ -2,8 +2,13 @@ use platform::message::Response as MessageResponse;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
-    contract::{cmd::LiquidationDTO, state::Response, Lease},
+    contract::{
+        cmd::{FullLiquidationDTO, LiquidationDTO, PartialLiquidationDTO},
+        state::{opened::margin_buffer, Response},
+        Lease,
+    },
     error::ContractResult,
+    position::Cause,
 };
 
 use super::ClosePositionTask;
@@ -11,6 +16,9 @@ use super::ClosePositionTask;
 pub mod full;
 pub mod partial;
 
+/// Start a liquidation of the position
+///
+/// A liability breach is covered from the margin buffer, if any, instead.
 pub(in crate::contract::state) fn start(
     lease: Lease,
     liquidation: LiquidationDTO,
@@ -19,8 +27,16 @@ pub(in crate::contract::state) fn start(
     querier: QuerierWrapper<'_>,
 ) -> ContractResult<Response> {
     match liquidation {
+        LiquidationDTO::Partial(PartialLiquidationDTO {
+            cause: Cause::Liability { ltv, .. },
+            ..
+        })
+        | LiquidationDTO::Full(FullLiquidationDTO {
+            cause: Cause::Liability { ltv, .. },
+        }) if !lease.margin_buffer.is_empty() => {
+            margin_buffer::cover(lease, ltv, curr_request_response, env, querier)
+        }
         LiquidationDTO::Partial(spec) => spec.start(lease, curr_request_response, env, querier),
         LiquidationDTO::Full(spec) => spec.start(lease, curr_request_response, env, querier),
     }
-    .map_err(Into::into)
 }
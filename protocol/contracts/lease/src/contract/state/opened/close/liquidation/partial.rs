@@ -1,3 +1,4 @@
+use finance::percent::Percent;
 use sdk::cosmwasm_std::Env;
 
 use crate::{
@@ -46,6 +47,10 @@ impl Closable for Spec {
     fn event_type(&self) -> Type {
         Type::LiquidationSwap
     }
+
+    fn max_slippage(&self, lease: &Lease) -> Option<Percent> {
+        lease.lease.position.liquidation_max_slippage()
+    }
 }
 
 impl RepayAlgo for Spec {
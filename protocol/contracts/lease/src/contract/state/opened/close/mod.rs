@@ -1,4 +1,5 @@
 use dex::Enterable;
+use finance::percent::Percent;
 use platform::message::Response as MessageResponse;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
@@ -24,12 +25,19 @@ use super::payment::Repayable;
 
 pub mod customer_close;
 pub mod liquidation;
+pub mod repay_from_position;
 pub mod sell_asset;
 
 pub(crate) trait Closable {
     fn amount<'a>(&'a self, lease: &'a Lease) -> &'a LeaseCoin;
+    /// The lease coins to sell, by default the `amount` split over the position basket, if any
+    fn coins(&self, lease: &Lease) -> Vec<LeaseCoin> {
+        lease.lease.position.sale_coins(self.amount(lease))
+    }
     fn transaction(&self, lease: &Lease, in_progress: PositionCloseTrx) -> OngoingTrx;
     fn event_type(&self) -> Type;
+    /// Maximum slippage of the lease asset sale, [`None`] accepts any output amount
+    fn max_slippage(&self, lease: &Lease) -> Option<Percent>;
 }
 
 type Task<RepayableT> = SellAsset<RepayableT>;
@@ -0,0 +1,97 @@
+use finance::percent::Percent;
+use platform::message::Response as MessageResponse;
+use sdk::cosmwasm_std::{Env, QuerierWrapper};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{
+        position::{PartialClose, PositionClose},
+        query::opened::{OngoingTrx, PositionCloseTrx},
+        LeaseCoin,
+    },
+    contract::{
+        cmd::{PartialCloseFn, ValidateClosePosition},
+        state::{
+            event::RepayFromPositionEmitter,
+            opened::{
+                close::{self, Closable, ClosePositionTask, IntoRepayable},
+                payment::{Repay, RepayAlgo},
+            },
+            Response,
+        },
+        Lease,
+    },
+    error::ContractResult,
+    event::Type,
+};
+
+/// A repayment with the proceeds of selling an amount of the lease asset
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RepayFromPosition {
+    amount: LeaseCoin,
+}
+
+type RepayableImpl = Repay<RepayFromPosition>;
+pub(crate) type DexState = close::DexState<RepayableImpl>;
+
+pub(in super::super) fn start(
+    amount: LeaseCoin,
+    lease: Lease,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    let spec = PositionClose::PartialClose(PartialClose {
+        amount,
+        max_slippage: None,
+    });
+    lease
+        .lease
+        .clone()
+        .execute(ValidateClosePosition::new(&spec, &env.block.time), querier)
+        .and_then(|PartialClose { amount, .. }| {
+            RepayFromPosition { amount }.start(lease, MessageResponse::default(), env, querier)
+        })
+}
+
+impl IntoRepayable for RepayFromPosition {
+    type Repayable = RepayableImpl;
+
+    fn into(self) -> Self::Repayable {
+        Into::into(self)
+    }
+}
+
+impl Closable for RepayFromPosition {
+    fn amount(&self, _lease: &Lease) -> &LeaseCoin {
+        &self.amount
+    }
+
+    fn transaction(&self, lease: &Lease, in_progress: PositionCloseTrx) -> OngoingTrx {
+        OngoingTrx::RepaymentFromPosition {
+            sell: *self.amount(lease),
+            in_progress,
+        }
+    }
+
+    fn event_type(&self) -> Type {
+        Type::RepaymentSwap
+    }
+
+    fn max_slippage(&self, _lease: &Lease) -> Option<Percent> {
+        None
+    }
+}
+
+impl RepayAlgo for RepayFromPosition {
+    type RepayFn = PartialCloseFn;
+
+    type PaymentEmitter<'this, 'env> = RepayFromPositionEmitter<'env>;
+
+    fn repay_fn(&self) -> Self::RepayFn {
+        Self::RepayFn::new(self.amount)
+    }
+
+    fn emitter_fn<'this, 'env>(&'this self, env: &'env Env) -> Self::PaymentEmitter<'this, 'env> {
+        Self::PaymentEmitter::new(self.amount, env)
+    }
+}
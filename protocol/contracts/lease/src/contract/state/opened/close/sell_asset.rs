@@ -6,7 +6,7 @@ use dex::{
     Account, CoinVisitor, ContractInSwap, IterNext, IterState, SwapState, SwapTask,
     TransferInFinishState, TransferInInitState, TransferOutState,
 };
-use finance::{coin::CoinDTO, duration::Duration};
+use finance::{coin::CoinDTO, duration::Duration, percent::Percent};
 use sdk::cosmwasm_std::{Env, QuerierWrapper, Timestamp};
 use timealarms::stub::TimeAlarmsRef;
 
@@ -88,11 +88,15 @@ where
         self.lease.lease.loan.lpp().lpn()
     }
 
+    fn max_slippage(&self) -> Option<Percent> {
+        self.repayable.max_slippage(&self.lease)
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<GIn = Self::InG, Result = IterNext>,
     {
-        dex::on_coin(self.repayable.amount(&self.lease), visitor)
+        dex::on_coins_seq(&self.repayable.coins(&self.lease), visitor)
     }
 
     fn finish(
@@ -1,9 +1,12 @@
-use finance::liability::Level;
+use finance::{liability::Level, percent::Percent};
 use platform::batch::{Emit, Emitter};
 use sdk::cosmwasm_std::{Addr, Env};
 
 use crate::{
-    api::DownpaymentCoin,
+    api::{
+        position::{ChangeCmd, ClosePolicyChange},
+        DownpaymentCoin, LeaseCoin, LpnCoinDTO, PaymentCoin,
+    },
     contract::{
         cmd::{OpenLoanRespResult, RepayEmitter},
         state::event as state_event,
@@ -11,6 +14,7 @@ use crate::{
     event::Type,
     lease::LeaseDTO,
     loan::RepayReceipt,
+    position::{CloseStrategy, OverdueWarning},
 };
 
 pub(super) fn emit_lease_opened(
@@ -45,15 +49,221 @@ impl RepayEmitter for PaymentEmitter<'_> {
     }
 }
 
+pub(super) fn emit_position_increased(
+    env: &Env,
+    lease: &LeaseDTO,
+    payment: &PaymentCoin,
+    borrow: Option<&LpnCoinDTO>,
+    amount: &LeaseCoin,
+) -> Emitter {
+    let emitter = Emitter::of_type(Type::IncreasePosition)
+        .emit_tx_info(env)
+        .emit("to", &lease.addr)
+        .emit_coin_dto("payment", payment);
+    match borrow {
+        Some(borrow) => emitter.emit_coin_dto("borrow", borrow),
+        None => emitter,
+    }
+    .emit_coin_dto("amount", amount)
+}
+
 pub(super) fn emit_liquidation_warning(lease: &LeaseDTO, level: &Level) -> Emitter {
     emit_lease(Emitter::of_type(Type::LiquidationWarning), lease)
         .emit_percent_amount("ltv", level.ltv())
         .emit_to_string_value("level", level.ordinal())
 }
 
+pub(super) fn emit_overdue_warning(lease: &LeaseDTO, warning: &OverdueWarning) -> Emitter {
+    emit_lease(Emitter::of_type(Type::OverdueWarning), lease)
+        .emit_coin("amount", warning.amount)
+        .emit_to_string_value("collect-in", warning.collect_in.secs())
+        .emit_to_string_value("level", warning.level)
+}
+
+pub(super) fn emit_close_trigger_dropped(lease: &LeaseDTO, trigger: &CloseStrategy) -> Emitter {
+    let emitter = emit_lease(Emitter::of_type(Type::DropCloseTrigger), lease);
+    match trigger {
+        CloseStrategy::TakeProfit(ltv) => emitter.emit_percent_amount("take-profit-ltv", *ltv),
+        CloseStrategy::StopLoss(ltv) => emitter.emit_percent_amount("stop-loss-ltv", *ltv),
+    }
+}
+
 fn emit_lease(emitter: Emitter, lease: &LeaseDTO) -> Emitter {
     emitter
         .emit("customer", lease.customer.clone())
         .emit("lease", lease.addr.clone())
         .emit_currency_dto("lease-asset", &lease.position.amount().currency())
 }
+
+pub(super) fn emit_close_policy_changed(
+    env: &Env,
+    lease: &LeaseDTO,
+    change: &ClosePolicyChange,
+) -> Emitter {
+    let emitter = Emitter::of_type(Type::ChangeClosePolicy)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer);
+    let emitter = if let Some(ref tp) = change.take_profit {
+        emit_change_cmd(emitter, "take-profit", tp)
+    } else {
+        emitter
+    };
+    if let Some(ref sl) = change.stop_loss {
+        emit_change_cmd(emitter, "stop-loss", sl)
+    } else {
+        emitter
+    }
+}
+
+fn emit_change_cmd(emitter: Emitter, trigger: &str, cmd: &ChangeCmd) -> Emitter {
+    match cmd {
+        ChangeCmd::Reset => emitter.emit(trigger, "reset"),
+        ChangeCmd::Set(ltv) => emitter.emit_percent_amount(format!("{trigger}-ltv"), *ltv),
+        ChangeCmd::SetPartial { ltv, close } => emitter
+            .emit_percent_amount(format!("{trigger}-ltv"), *ltv)
+            .emit_percent_amount(format!("{trigger}-close"), *close),
+        ChangeCmd::SetPrice(price) => {
+            emitter.emit_to_string_value(format!("{trigger}-price"), price)
+        }
+        ChangeCmd::Trail(distance) => {
+            emitter.emit_percent_amount(format!("{trigger}-trail"), *distance)
+        }
+    }
+}
+
+pub(super) fn emit_margin_buffer_deposit(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &PaymentCoin,
+) -> Emitter {
+    Emitter::of_type(Type::DepositMarginBuffer)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit_coin_dto("amount", amount)
+}
+
+pub(super) fn emit_margin_buffer_withdraw(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &PaymentCoin,
+) -> Emitter {
+    Emitter::of_type(Type::WithdrawMarginBuffer)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit_coin_dto("amount", amount)
+}
+
+pub(super) fn emit_margin_buffer_cover(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &PaymentCoin,
+    ltv: Percent,
+) -> Emitter {
+    Emitter::of_type(Type::CoverFromMarginBuffer)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit_coin_dto("amount", amount)
+        .emit_percent_amount("ltv", ltv)
+}
+
+pub(super) fn emit_auto_repay_deposit(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &PaymentCoin,
+) -> Emitter {
+    Emitter::of_type(Type::DepositAutoRepay)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit_coin_dto("amount", amount)
+}
+
+pub(super) fn emit_auto_repay_withdraw(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &PaymentCoin,
+) -> Emitter {
+    Emitter::of_type(Type::WithdrawAutoRepay)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit_coin_dto("amount", amount)
+}
+
+pub(super) fn emit_auto_repay(
+    env: &Env,
+    lease: &LeaseDTO,
+    amount: &PaymentCoin,
+    budget_left: Option<&PaymentCoin>,
+) -> Emitter {
+    let emitter = Emitter::of_type(Type::AutoRepay)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit_coin_dto("amount", amount)
+        .emit_to_string_value("budget-exhausted", budget_left.is_none());
+    if let Some(left) = budget_left {
+        emitter.emit_coin_dto("budget-left", left)
+    } else {
+        emitter
+    }
+}
+
+pub(super) fn emit_ownership_transfer_request(
+    env: &Env,
+    lease: &LeaseDTO,
+    new_owner: &Addr,
+) -> Emitter {
+    Emitter::of_type(Type::TransferOwnershipRequest)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit("new-owner", new_owner)
+}
+
+pub(super) fn emit_ownership_transferred(env: &Env, lease: &LeaseDTO, new_owner: &Addr) -> Emitter {
+    Emitter::of_type(Type::TransferOwnership)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit("new-owner", new_owner)
+}
+
+pub(super) fn emit_operator_granted(env: &Env, lease: &LeaseDTO, operator: &Addr) -> Emitter {
+    Emitter::of_type(Type::GrantOperator)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit("operator", operator)
+}
+
+pub(super) fn emit_operator_revoked(env: &Env, lease: &LeaseDTO, operator: &Addr) -> Emitter {
+    Emitter::of_type(Type::RevokeOperator)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit("operator", operator)
+}
+
+pub(super) fn emit_refinanced(
+    env: &Env,
+    lease: &LeaseDTO,
+    from_lpp: &Addr,
+    loan: OpenLoanRespResult,
+) -> Emitter {
+    Emitter::of_type(Type::Refinance)
+        .emit_tx_info(env)
+        .emit("id", &lease.addr)
+        .emit("customer", &lease.customer)
+        .emit("from-loan-pool-id", from_lpp)
+        .emit("loan-pool-id", lease.loan.lpp().addr())
+        .emit_percent_amount(
+            "air",
+            loan.annual_interest_rate + lease.loan.annual_margin_interest(),
+        )
+        .emit_coin_dto("loan", &loan.principal)
+}
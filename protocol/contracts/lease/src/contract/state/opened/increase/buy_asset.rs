@@ -0,0 +1,233 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use oracle::stub::SwapPath;
+use serde::{Deserialize, Serialize};
+
+use currency::CurrencyDTO;
+use dex::{
+    Account, CoinVisitor, ConnectionParams, Contract as DexContract, ContractInSwap,
+    DexConnectable, DexResult, IcaConnectee, IterNext, IterState, SwapState, SwapTask, TimeAlarm,
+    TransferOut, TransferOutState,
+};
+use finance::{coin::CoinDTO, duration::Duration, percent::Percent};
+use platform::batch::Batch;
+use sdk::cosmwasm_std::{Env, QuerierWrapper, Timestamp};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        query::{
+            opened::{IncreasePositionTrx, OngoingTrx},
+            StateResponse as QueryStateResponse,
+        },
+        LeaseAssetCurrencies, LeasePaymentCurrencies, LpnCoinDTO, PaymentCoin,
+    },
+    contract::{
+        state::{
+            opened,
+            resp_delivery::{ForwardToDexEntry, ForwardToDexEntryContinue},
+            SwapClient, SwapResult,
+        },
+        Lease,
+    },
+    error::ContractResult,
+    event::Type,
+};
+
+pub(in super::super) type StartState =
+    TransferOut<BuyAsset, DexState, LeasePaymentCurrencies, SwapClient>;
+
+/// The swapped lease asset remains on the Dex account
+///
+/// The ICA of the lease is already open, so the `BuyAsset` stands also for the ICA connectee
+/// of this remote-out flow. It is not expected to be ever called to open an ICA.
+pub(crate) type DexState = dex::StateRemoteOut<
+    BuyAsset,
+    BuyAsset,
+    LeasePaymentCurrencies,
+    SwapClient,
+    ForwardToDexEntry,
+    ForwardToDexEntryContinue,
+>;
+
+pub(super) fn start(lease: Lease, payment: PaymentCoin, borrow: Option<LpnCoinDTO>) -> StartState {
+    TransferOut::new(BuyAsset::new(lease, payment, borrow))
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct BuyAsset {
+    lease: Lease,
+    payment: PaymentCoin,
+    borrow: Option<LpnCoinDTO>,
+}
+
+impl BuyAsset {
+    fn new(lease: Lease, payment: PaymentCoin, borrow: Option<LpnCoinDTO>) -> Self {
+        debug_assert!(!borrow.is_some_and(|borrow| borrow.is_zero()));
+        Self {
+            lease,
+            payment,
+            borrow,
+        }
+    }
+
+    fn query(
+        self,
+        in_progress: IncreasePositionTrx,
+        now: Timestamp,
+        due_projection: Duration,
+        querier: QuerierWrapper<'_>,
+    ) -> ContractResult<QueryStateResponse> {
+        let in_progress = OngoingTrx::IncreasePosition {
+            payment: self.payment,
+            in_progress,
+        };
+
+        opened::lease_state(self.lease, Some(in_progress), now, due_projection, querier)
+    }
+}
+
+impl SwapTask for BuyAsset {
+    type InG = LeasePaymentCurrencies;
+    type OutG = LeaseAssetCurrencies;
+    type InOutG = LeasePaymentCurrencies;
+    type Label = Type;
+    type StateResponse = ContractResult<QueryStateResponse>;
+    type Result = SwapResult;
+
+    fn label(&self) -> Self::Label {
+        Type::IncreasePositionSwap
+    }
+
+    fn dex_account(&self) -> &Account {
+        &self.lease.dex
+    }
+
+    fn oracle(&self) -> &impl SwapPath<Self::InOutG> {
+        &self.lease.lease.oracle
+    }
+
+    fn time_alarm(&self) -> &TimeAlarmsRef {
+        &self.lease.lease.time_alarms
+    }
+
+    fn out_currency(&self) -> CurrencyDTO<Self::OutG> {
+        self.lease.lease.position.amount().currency()
+    }
+
+    fn max_slippage(&self) -> Option<Percent> {
+        None
+    }
+
+    fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
+    where
+        Visitor: CoinVisitor<GIn = Self::InG, Result = IterNext>,
+    {
+        if let Some(borrow) = &self.borrow {
+            dex::on_coins(&self.payment, borrow, visitor)
+        } else {
+            dex::on_coin(&self.payment, visitor)
+        }
+    }
+
+    fn finish(
+        self,
+        amount_out: CoinDTO<Self::OutG>,
+        env: &Env,
+        querier: QuerierWrapper<'_>,
+    ) -> Self::Result {
+        debug_assert_eq!(amount_out.currency(), self.out_currency());
+        debug_assert!(amount_out.amount() > 0);
+
+        super::increase(
+            self.lease,
+            self.payment,
+            self.borrow,
+            amount_out,
+            env,
+            querier,
+        )
+    }
+}
+
+impl IcaConnectee for BuyAsset {
+    type State = DexState;
+    type NextState = StartState;
+
+    fn connected(mut self, dex_account: Account) -> Self::NextState {
+        self.lease.dex = dex_account;
+        TransferOut::new(self)
+    }
+}
+
+impl DexConnectable for BuyAsset {
+    fn dex(&self) -> &ConnectionParams {
+        self.lease.dex()
+    }
+}
+
+impl DexContract for BuyAsset {
+    type StateResponse = <Self as SwapTask>::StateResponse;
+
+    fn state(
+        self,
+        now: Timestamp,
+        due_projection: Duration,
+        querier: QuerierWrapper<'_>,
+    ) -> Self::StateResponse {
+        self.query(
+            IncreasePositionTrx::TransferOut,
+            now,
+            due_projection,
+            querier,
+        )
+    }
+}
+
+impl TimeAlarm for BuyAsset {
+    fn setup_alarm(&self, forr: Timestamp) -> DexResult<Batch> {
+        self.lease
+            .lease
+            .time_alarms
+            .setup_alarm(forr)
+            .map_err(Into::into)
+    }
+}
+
+impl Display for BuyAsset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_fmt(format_args!("IncreasePosition"))
+    }
+}
+
+impl<DexState> ContractInSwap<DexState> for BuyAsset
+where
+    DexState: InProgressTrx,
+{
+    type StateResponse = <Self as SwapTask>::StateResponse;
+
+    fn state(
+        self,
+        now: Timestamp,
+        due_projection: Duration,
+        querier: QuerierWrapper<'_>,
+    ) -> Self::StateResponse {
+        self.query(DexState::trx_in_progress(), now, due_projection, querier)
+    }
+}
+
+trait InProgressTrx {
+    fn trx_in_progress() -> IncreasePositionTrx;
+}
+
+impl InProgressTrx for TransferOutState {
+    fn trx_in_progress() -> IncreasePositionTrx {
+        IncreasePositionTrx::TransferOut
+    }
+}
+
+impl InProgressTrx for SwapState {
+    fn trx_in_progress() -> IncreasePositionTrx {
+        IncreasePositionTrx::Swap
+    }
+}
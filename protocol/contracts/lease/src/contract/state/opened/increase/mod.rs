@@ -0,0 +1,138 @@
+use dex::Enterable;
+use platform::batch::Batch;
+use sdk::cosmwasm_std::{Env, QuerierWrapper};
+
+use crate::{
+    api::{LeaseCoin, LpnCoinDTO, PaymentCoin},
+    contract::{
+        cmd::{CloseStatusDTO, IncreaseLoan, IncreasePosition},
+        state::{
+            opened::{
+                active::Active,
+                alarm,
+                close::{customer_close, liquidation},
+                event,
+            },
+            Response,
+        },
+        Lease,
+    },
+    error::ContractResult,
+    position::CloseStrategy,
+};
+
+pub mod buy_asset;
+
+pub(super) fn start(
+    lease: Lease,
+    payment: PaymentCoin,
+    borrow: Option<LpnCoinDTO>,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    let (lease, borrowed, loan_messages) = match borrow {
+        Some(borrow) => increase_loan(lease, payment, borrow, env, querier)?,
+        None => (lease, None, Batch::default()),
+    };
+
+    let buy_asset = buy_asset::start(lease, payment, borrowed);
+    buy_asset
+        .enter(env.block.time, querier)
+        .map(|batch| {
+            Response::from(
+                loan_messages.merge(batch),
+                buy_asset::DexState::from(buy_asset),
+            )
+        })
+        .map_err(Into::into)
+}
+
+fn increase_loan(
+    lease: Lease,
+    payment: PaymentCoin,
+    borrow: LpnCoinDTO,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<(Lease, Option<LpnCoinDTO>, Batch)> {
+    let profit = lease.lease.loan.profit().clone();
+    let time_alarms = lease.lease.time_alarms.clone();
+    let reserve = lease.lease.reserve.clone();
+    lease
+        .update(
+            IncreaseLoan::new(
+                payment,
+                borrow,
+                &env.block.time,
+                profit,
+                time_alarms,
+                reserve,
+            ),
+            querier,
+        )
+        .map(|(lease, (borrowed, loan_messages))| {
+            (
+                lease,
+                (!borrowed.is_zero()).then_some(borrowed),
+                loan_messages,
+            )
+        })
+}
+
+fn increase(
+    lease: Lease,
+    payment: PaymentCoin,
+    borrow: Option<LpnCoinDTO>,
+    amount: LeaseCoin,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    let profit = lease.lease.loan.profit().clone();
+    let time_alarms = lease.lease.time_alarms.clone();
+    let price_alarms = lease.lease.oracle.clone();
+    let reserve = lease.lease.reserve.clone();
+    let (lease, close_status) = lease.update(
+        IncreasePosition::new(
+            amount,
+            &env.block.time,
+            profit,
+            time_alarms,
+            &price_alarms,
+            reserve,
+        ),
+        querier,
+    )?;
+
+    let events =
+        event::emit_position_increased(env, &lease.lease, &payment, borrow.as_ref(), &amount);
+
+    match close_status {
+        CloseStatusDTO::Paid => {
+            unimplemented!("an increased position should have the same due amount")
+        }
+        CloseStatusDTO::None {
+            current_liability,
+            overdue_warning,
+            dropped_triggers,
+            alarms,
+        } => {
+            let response = alarm::build_resp(
+                &lease,
+                current_liability,
+                overdue_warning,
+                dropped_triggers,
+                alarms,
+            )
+            .merge_with(events);
+            Ok(Response::from(response, Active::new(lease)))
+        }
+        CloseStatusDTO::NeedLiquidation(liquidation) => {
+            liquidation::start(lease, liquidation, events.into(), env, querier)
+        }
+        CloseStatusDTO::CloseAsked(strategy) => match strategy {
+            CloseStrategy::TakeProfit(_tp) => {
+                unimplemented!("a Take Profit past position increase should have been reset")
+            }
+            _ => customer_close::auto_start(strategy, lease, env, querier),
+        },
+    }
+}
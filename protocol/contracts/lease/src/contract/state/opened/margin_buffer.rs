@@ -0,0 +1,83 @@
+use finance::percent::Percent;
+use platform::message::Response as MessageResponse;
+use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper};
+
+use crate::{
+    contract::{cmd::ObtainPayment, funds, state::Response, Lease},
+    error::{ContractError, ContractResult},
+};
+
+use super::{event, repay};
+
+/// Add the funds attached by the customer to the margin buffer
+pub(super) fn deposit(
+    mut lease: Lease,
+    env: &Env,
+    info: MessageInfo,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            lease
+                .lease
+                .clone()
+                .execute(ObtainPayment::new(info.funds), querier)
+        })
+        .and_then(|payment| {
+            let events = event::emit_margin_buffer_deposit(env, &lease.lease, &payment);
+            lease
+                .margin_buffer
+                .deposit(payment)
+                .map(|()| (lease, events.into()))
+        })
+}
+
+/// Send the whole margin buffer back to the customer
+pub(super) fn withdraw(
+    mut lease: Lease,
+    env: &Env,
+    info: &MessageInfo,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            lease
+                .margin_buffer
+                .take()
+                .ok_or_else(ContractError::MarginBufferEmpty)
+        })
+        .and_then(|buffer| {
+            let events = event::emit_margin_buffer_withdraw(env, &lease.lease, &buffer);
+            funds::send(&buffer, lease.lease.customer.clone())
+                .map(|msgs| (lease, MessageResponse::messages_with_events(msgs, events)))
+        })
+}
+
+/// Repay the loan with the whole margin buffer instead of liquidating the position
+///
+/// A buffer in a currency other than the LPN is swapped first as a customer payment would be.
+/// The position liability is re-evaluated once the repayment completes, when the position is
+/// liquidated if it is still in breach.
+///
+/// Pre-condition: the buffer is not empty.
+pub(super) fn cover(
+    mut lease: Lease,
+    ltv: Percent,
+    curr_request_response: MessageResponse,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    let buffer = lease
+        .margin_buffer
+        .take()
+        .expect("the margin buffer should not be empty");
+    let response = curr_request_response.merge_with(event::emit_margin_buffer_cover(
+        env,
+        &lease.lease,
+        &buffer,
+        ltv,
+    ));
+
+    repay::repay_with(lease, buffer, response, env, querier)
+}
@@ -9,10 +9,16 @@ use crate::{
 
 pub mod active;
 mod alarm;
+mod auto_repay;
 mod balance;
 pub mod close;
 mod event;
+pub mod increase;
+mod margin_buffer;
+mod operators;
+mod ownership;
 mod payment;
+pub mod refinance;
 pub mod repay;
 
 fn lease_state(
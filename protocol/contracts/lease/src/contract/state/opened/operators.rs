@@ -0,0 +1,50 @@
+use platform::message::Response as MessageResponse;
+use sdk::cosmwasm_std::{Addr, Env, MessageInfo};
+
+use crate::{
+    api::OperatorPermission,
+    contract::Lease,
+    error::{ContractError, ContractResult},
+};
+
+use super::event;
+
+/// Grant `operator` the `permissions` to manage the lease, or all of them if not specified
+pub(super) fn grant(
+    mut lease: Lease,
+    operator: Addr,
+    permissions: Option<Vec<OperatorPermission>>,
+    env: &Env,
+    info: &MessageInfo,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            if operator == lease.lease.customer {
+                Err(ContractError::AlreadyOwner(operator))
+            } else {
+                let events = event::emit_operator_granted(env, &lease.lease, &operator);
+                lease
+                    .operators
+                    .grant(operator, permissions)
+                    .map(|()| (lease, events.into()))
+            }
+        })
+}
+
+pub(super) fn revoke(
+    mut lease: Lease,
+    operator: Addr,
+    env: &Env,
+    info: &MessageInfo,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            let events = event::emit_operator_revoked(env, &lease.lease, &operator);
+            lease
+                .operators
+                .revoke(operator)
+                .map(|()| (lease, events.into()))
+        })
+}
@@ -0,0 +1,71 @@
+use platform::message::Response as MessageResponse;
+use sdk::cosmwasm_std::{Addr, Env, MessageInfo};
+
+use crate::{
+    contract::Lease,
+    error::{ContractError, ContractResult},
+};
+
+use super::event;
+
+/// Request a transfer of the lease ownership to `new_owner`
+///
+/// The transfer is done right away unless `require_accept` is set.
+/// Then, it remains pending until the new owner accepts it.
+/// The lease operators are revoked on transfer.
+pub(super) fn request(
+    mut lease: Lease,
+    new_owner: Addr,
+    require_accept: bool,
+    env: &Env,
+    info: &MessageInfo,
+) -> ContractResult<(Lease, MessageResponse)> {
+    access_control::check(&lease.lease.customer, &info.sender)
+        .map_err(Into::into)
+        .and_then(|()| {
+            if new_owner == lease.lease.customer {
+                Err(ContractError::AlreadyOwner(new_owner))
+            } else if require_accept {
+                let events = event::emit_ownership_transfer_request(env, &lease.lease, &new_owner);
+                lease.pending_owner = Some(new_owner);
+                Ok((lease, events.into()))
+            } else {
+                transfer(lease, new_owner, env)
+            }
+        })
+}
+
+/// Accept a pending transfer of the lease ownership
+pub(super) fn accept(
+    lease: Lease,
+    env: &Env,
+    info: &MessageInfo,
+) -> ContractResult<(Lease, MessageResponse)> {
+    lease
+        .pending_owner
+        .clone()
+        .ok_or(ContractError::NoPendingOwnershipTransfer())
+        .and_then(|new_owner| {
+            access_control::check(&new_owner, &info.sender)
+                .map(|()| new_owner)
+                .map_err(Into::into)
+        })
+        .and_then(|new_owner| transfer(lease, new_owner, env))
+}
+
+fn transfer(
+    mut lease: Lease,
+    new_owner: Addr,
+    env: &Env,
+) -> ContractResult<(Lease, MessageResponse)> {
+    lease
+        .finalizer
+        .notify_owner_change(lease.lease.customer.clone(), new_owner.clone())
+        .map(|msgs| {
+            let events = event::emit_ownership_transferred(env, &lease.lease, &new_owner);
+            lease.lease.customer = new_owner;
+            lease.pending_owner = None;
+            lease.operators.revoke_all();
+            (lease, MessageResponse::messages_with_events(msgs, events))
+        })
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use finance::percent::Percent;
 use platform::bank::FixedAddressSender;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
@@ -67,6 +68,10 @@ where
         self.0.amount(lease)
     }
 
+    fn coins(&self, lease: &Lease) -> Vec<LeaseCoin> {
+        self.0.coins(lease)
+    }
+
     fn transaction(&self, lease: &Lease, in_progress: PositionCloseTrx) -> OngoingTrx {
         self.0.transaction(lease, in_progress)
     }
@@ -74,6 +79,10 @@ where
     fn event_type(&self) -> Type {
         self.0.event_type()
     }
+
+    fn max_slippage(&self, lease: &Lease) -> Option<Percent> {
+        self.0.max_slippage(lease)
+    }
 }
 
 impl<CloseAlgoT> Repayable for Close<CloseAlgoT>
@@ -82,12 +91,14 @@ where
 {
     fn try_repay(
         &self,
-        lease: Lease,
+        mut lease: Lease,
         amount: LpnCoinDTO,
         env: &Env,
         querier: QuerierWrapper<'_>,
     ) -> ContractResult<Response> {
         let customer = lease.lease.customer.clone();
+        let buffer_msgs = lease.margin_buffer.take_out_to(customer.clone())?;
+        let budget_msgs = lease.auto_repay.take_out_to(customer.clone())?;
 
         lease.finalizer.notify(customer).and_then(|finalizer_msgs| {
             let profit = self.0.profit_sender(&lease);
@@ -100,7 +111,12 @@ where
                     FullCloseCmd::new(amount, env.block.time, profit, reserve, change, emitter_fn),
                     querier,
                 )
-                .map(|liquidation_response| liquidation_response.merge_with(finalizer_msgs))
+                .map(|liquidation_response| {
+                    liquidation_response
+                        .merge_with(buffer_msgs)
+                        .merge_with(budget_msgs)
+                        .merge_with(finalizer_msgs)
+                })
                 //make sure the finalizer messages go out last
                 .map(|response| Response::from(response, CloseAlgoT::OutState::default()))
         })
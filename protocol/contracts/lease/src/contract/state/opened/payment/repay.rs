@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use finance::percent::Percent;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
@@ -57,6 +58,10 @@ where
         self.0.amount(lease)
     }
 
+    fn coins(&self, lease: &Lease) -> Vec<LeaseCoin> {
+        self.0.coins(lease)
+    }
+
     fn transaction(&self, lease: &Lease, in_progress: PositionCloseTrx) -> OngoingTrx {
         self.0.transaction(lease, in_progress)
     }
@@ -64,6 +69,10 @@ where
     fn event_type(&self) -> Type {
         self.0.event_type()
     }
+
+    fn max_slippage(&self, lease: &Lease) -> Option<Percent> {
+        self.0.max_slippage(lease)
+    }
 }
 
 impl<RepayAlgoT> Repayable for Repay<RepayAlgoT>
@@ -101,13 +110,37 @@ where
         )?;
 
         match close_status {
-            CloseStatusDTO::Paid => Ok(Response::from(response, paid::Active::new(lease))),
+            CloseStatusDTO::Paid => {
+                // neither the margin buffer, nor the auto-repay budget is needed anymore
+                let mut lease = lease;
+                let customer = lease.lease.customer.clone();
+                lease
+                    .margin_buffer
+                    .take_out_to(customer.clone())
+                    .and_then(|buffer_msgs| {
+                        lease
+                            .auto_repay
+                            .take_out_to(customer)
+                            .map(|budget_msgs| buffer_msgs.merge(budget_msgs))
+                    })
+                    .map(|funds_msgs| {
+                        Response::from(response.merge_with(funds_msgs), paid::Active::new(lease))
+                    })
+            }
             CloseStatusDTO::None {
                 current_liability,
+                overdue_warning,
+                dropped_triggers,
                 alarms,
             } => {
-                let response =
-                    alarm::build_resp(&lease, current_liability, alarms).merge_with(response);
+                let response = alarm::build_resp(
+                    &lease,
+                    current_liability,
+                    overdue_warning,
+                    dropped_triggers,
+                    alarms,
+                )
+                .merge_with(response);
                 Ok(Response::from(response, active::Active::new(lease)))
             }
             CloseStatusDTO::NeedLiquidation(liquidation) => {
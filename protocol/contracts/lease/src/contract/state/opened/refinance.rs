@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use finance::duration::Duration;
+use platform::{contract, message::Response as MessageResponse};
+use sdk::cosmwasm_std::{Addr, Env, QuerierWrapper, Reply, Timestamp};
+
+use crate::{
+    api::query::StateResponse,
+    contract::{
+        cmd::{OpenLoanResp, RefinanceAmount, RefinanceCmd, RefinanceLoanReq},
+        state::{Handler, Response},
+        Lease,
+    },
+    error::{ContractError, ContractResult},
+    finance::LppRef,
+};
+
+use super::{active::Active, event};
+
+/// Start moving the lease loan over to the `lpp`
+///
+/// The new Lpp should be a different instance of the same code as the current one.
+/// Otherwise, it could not be relied on to report the loan of the lease.
+pub(super) fn start(
+    lease: Lease,
+    lpp: Addr,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    check_lpp(lease.lease.loan.lpp(), &lpp, querier)
+        .and_then(|()| LppRef::try_new(lpp, querier).map_err(Into::into))
+        .and_then(|new_lpp| {
+            lease
+                .lease
+                .clone()
+                .execute(RefinanceAmount::new(&env.block.time), querier)
+                .and_then(|amount| {
+                    new_lpp
+                        .clone()
+                        .execute_lender(RefinanceLoanReq::new(amount), querier)
+                })
+                .map(|batch| Response::from(batch, Refinance { lease, new_lpp }))
+        })
+}
+
+fn check_lpp(current: &LppRef, lpp: &Addr, querier: QuerierWrapper<'_>) -> ContractResult<()> {
+    if current.addr() == lpp {
+        Err(ContractError::RefinanceLppMismatch(lpp.clone()))
+    } else {
+        contract::code_of(querier, current.addr())
+            .and_then(|current_code| {
+                contract::code_of(querier, lpp).map(|code| code == current_code)
+            })
+            .map_err(Into::into)
+            .and_then(|same_code| {
+                if same_code {
+                    Ok(())
+                } else {
+                    Err(ContractError::RefinanceLppMismatch(lpp.clone()))
+                }
+            })
+    }
+}
+
+/// The new loan has been requested and its proceeds are to repay the current one
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Refinance {
+    lease: Lease,
+    new_lpp: LppRef,
+}
+
+impl Refinance {
+    fn on_response(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: &Env,
+        msg: Reply,
+    ) -> ContractResult<Response> {
+        let loan = self
+            .new_lpp
+            .clone()
+            .execute_lender(OpenLoanResp::new(msg), querier)?;
+
+        let from_lpp = self.lease.lease.loan.lpp().addr().clone();
+        let profit = self.lease.lease.loan.profit().clone();
+        let time_alarms = self.lease.lease.time_alarms.clone();
+        let reserve = self.lease.lease.reserve.clone();
+        self.lease
+            .update(
+                RefinanceCmd::new(self.new_lpp, &env.block.time, profit, time_alarms, reserve),
+                querier,
+            )
+            .map(|(lease, batch)| {
+                let emitter = event::emit_refinanced(env, &lease.lease, &from_lpp, loan);
+                Response::from(
+                    MessageResponse::messages_with_events(batch, emitter),
+                    Active::new(lease),
+                )
+            })
+    }
+}
+
+impl Handler for Refinance {
+    fn state(
+        self,
+        _now: Timestamp,
+        _due_projection: Duration,
+        _querier: QuerierWrapper<'_>,
+    ) -> ContractResult<StateResponse> {
+        unreachable!("the refinancing completes in the same transaction")
+    }
+
+    fn reply(self, querier: QuerierWrapper<'_>, env: Env, msg: Reply) -> ContractResult<Response> {
+        self.on_response(querier, &env, msg)
+    }
+}
@@ -6,7 +6,7 @@ use dex::{
     Account, CoinVisitor, ContractInSwap, IterNext, IterState, StartLocalLocalState, SwapState,
     SwapTask, TransferInFinishState, TransferInInitState, TransferOutState,
 };
-use finance::{coin::CoinDTO, duration::Duration};
+use finance::{coin::CoinDTO, duration::Duration, percent::Percent};
 use sdk::cosmwasm_std::{Env, QuerierWrapper, Timestamp};
 use timealarms::stub::TimeAlarmsRef;
 
@@ -105,6 +105,10 @@ impl SwapTask for BuyLpn {
         self.lease.lease.loan.lpp().lpn()
     }
 
+    fn max_slippage(&self) -> Option<Percent> {
+        None
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<GIn = Self::InG, Result = IterNext>,
@@ -1,11 +1,17 @@
+use dex::Enterable;
+use finance::coin;
+use platform::message::Response as MessageResponse;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 
 use crate::{
+    api::PaymentCoin,
     contract::{cmd::RepayLeaseFn, state::Response, Lease},
     error::ContractResult,
     finance::LpnCoinDTO,
 };
 
+use self::buy_lpn::DexState as BuyLpnState;
+
 use super::{
     event::PaymentEmitter,
     payment::{Repay, RepayAlgo, Repayable},
@@ -13,6 +19,46 @@ use super::{
 
 pub mod buy_lpn;
 
+/// Repay the loan with funds already held by the lease
+///
+/// A payment in a currency other than the LPN is swapped first as a customer payment would be.
+pub(super) fn repay_with(
+    lease: Lease,
+    payment: PaymentCoin,
+    curr_request_response: MessageResponse,
+    env: &Env,
+    querier: QuerierWrapper<'_>,
+) -> ContractResult<Response> {
+    let lpn = lease.lease.loan.lpp().lpn();
+    if payment.currency() == lpn {
+        repay(
+            lease,
+            coin::from_amount_ticker(payment.amount(), lpn),
+            env,
+            querier,
+        )
+        .map(
+            |Response {
+                 response: repay_response,
+                 next_state,
+             }| {
+                Response::from(curr_request_response.merge_with(repay_response), next_state)
+            },
+        )
+    } else {
+        let buy_lpn = buy_lpn::start(lease, payment);
+        buy_lpn
+            .enter(env.block.time, querier)
+            .map(|batch| {
+                Response::from(
+                    curr_request_response.merge_with(batch),
+                    BuyLpnState::from(buy_lpn),
+                )
+            })
+            .map_err(Into::into)
+    }
+}
+
 pub(super) fn repay(
     lease: Lease,
     amount: LpnCoinDTO,
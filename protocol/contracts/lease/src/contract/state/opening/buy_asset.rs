@@ -2,12 +2,17 @@ use oracle::stub::SwapPath;
 use profit::stub::ProfitRef;
 use serde::{Deserialize, Serialize};
 
-use currency::CurrencyDTO;
+use currency::{CurrencyDTO, Group};
 use dex::{
-    Account, CoinVisitor, ContractInSwap, IterNext, IterState, StartLocalRemoteState, SwapState,
-    SwapTask, TransferOutState,
+    Account, CoinVisitor, ContractInSwap, Enterable, IterNext, IterState, StartLocalRemoteState,
+    SwapState, SwapTask, TransferOut, TransferOutState,
+};
+use finance::{
+    coin::{self, CoinDTO},
+    duration::Duration,
+    fraction::Fraction,
+    percent::Percent,
 };
-use finance::{coin::CoinDTO, duration::Duration};
 use platform::{
     ica::HostAccount, message::Response as MessageResponse,
     state_machine::Response as StateMachineResponse,
@@ -17,9 +22,9 @@ use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
     api::{
-        open::{NewLeaseContract, NewLeaseForm},
+        open::{BasketLeg, NewLeaseContract, NewLeaseForm},
         query::{opening::OngoingTrx, StateResponse as QueryStateResponse},
-        DownpaymentCoin, LeaseAssetCurrencies, LeasePaymentCurrencies,
+        DownpaymentCoin, LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies,
     },
     contract::{
         cmd::{CloseStatusDTO, LeaseFactory, OpenLeaseResult, OpenLoanRespResult},
@@ -27,7 +32,7 @@ use crate::{
         state::{
             opened::{active::Active, close::liquidation},
             resp_delivery::{ForwardToDexEntry, ForwardToDexEntryContinue},
-            SwapClient, SwapResult,
+            Response, SwapClient, SwapResult,
         },
         Lease,
     },
@@ -77,6 +82,11 @@ pub(crate) struct BuyAsset {
     loan: OpenLoanRespResult,
     deps: (LppRef, OracleRef, TimeAlarmsRef, FinalizerRef),
     start_opening_at: Timestamp,
+    /// The lease currencies bought so far, the lease currency first and the basket legs next
+    ///
+    /// Each currency is bought with its share of the downpayment and the loan in a swap of its own.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    bought: Vec<LeaseCoin>,
 }
 
 impl BuyAsset {
@@ -95,6 +105,104 @@ impl BuyAsset {
             loan,
             deps,
             start_opening_at,
+            bought: vec![],
+        }
+    }
+
+    fn basket_legs(&self) -> &[BasketLeg] {
+        self.form
+            .basket
+            .as_ref()
+            .map_or(&[], |basket| basket.legs.as_slice())
+    }
+
+    /// The basket leg in purchase, [`None`] stands for the lease currency
+    fn leg(&self) -> Option<&BasketLeg> {
+        self.bought
+            .len()
+            .checked_sub(1)
+            .map(|leg_idx| &self.basket_legs()[leg_idx])
+    }
+
+    /// The part of `coin` spent on the currency in purchase
+    ///
+    /// The lease currency gets what is left after the basket legs take their weights.
+    fn share<G>(&self, coin: &CoinDTO<G>) -> CoinDTO<G>
+    where
+        G: Group,
+    {
+        let amount = coin.amount();
+        let share = self.leg().map_or_else(
+            || {
+                self.basket_legs()
+                    .iter()
+                    .fold(amount, |left, leg| left - leg.weight.of(amount))
+            },
+            |leg| leg.weight.of(amount),
+        );
+        coin::from_amount_ticker(share, coin.currency())
+    }
+
+    /// Buy the next basket leg through the already open Dex account
+    fn buy_next(self, env: &Env, querier: QuerierWrapper<'_>) -> SwapResult {
+        let transfer_out = TransferOut::<_, DexState, _, _>::new(self);
+        transfer_out
+            .enter(env.block.time, querier)
+            .map(|batch| Response::from(batch, DexState::from(transfer_out)))
+            .map_err(Into::into)
+    }
+
+    /// Open the lease with all currencies bought
+    fn open(self, env: &Env, querier: QuerierWrapper<'_>) -> SwapResult {
+        let mut bought = self.bought.into_iter();
+        let position = PositionDTO::new(
+            bought.next().expect("the lease currency has been bought"),
+            self.form.position_spec.into(),
+        )
+        .with_basket(
+            bought,
+            self.form
+                .basket
+                .as_ref()
+                .map(|basket| basket.liquidation)
+                .unwrap_or_default(),
+        );
+        let profit = ProfitRef::new(self.form.loan.profit.clone(), &querier)?;
+        let reserve = ReserveRef::try_new(self.form.reserve.clone(), &querier)?;
+        let lease_addr = self.dex_account.owner().clone();
+        let cmd = LeaseFactory::new(
+            self.form,
+            lease_addr.clone(),
+            profit,
+            reserve,
+            (self.deps.2, self.deps.1.clone()),
+            self.start_opening_at,
+            &env.block.time,
+        );
+        let OpenLeaseResult { lease, status } =
+            with_lease_deps::execute(cmd, lease_addr, position, self.deps.0, self.deps.1, querier)?;
+
+        let lease = Lease::new(lease, self.dex_account, self.deps.3);
+        let active = Active::new(lease);
+        let emitter = active.emit_opened(env, self.downpayment, self.loan);
+
+        match status {
+            CloseStatusDTO::Paid => {
+                unimplemented!("a freshly open lease should have some due amount")
+            }
+            CloseStatusDTO::None {
+                current_liability: _, // TODO shouldn't we add warning zone events?
+                overdue_warning: _,
+                dropped_triggers: _,
+                alarms,
+            } => Ok(StateMachineResponse::from(
+                MessageResponse::messages_with_events(alarms, emitter),
+                active,
+            )),
+            CloseStatusDTO::NeedLiquidation(liquidation) => {
+                liquidation::start(active.into(), liquidation, emitter.into(), env, querier)
+            }
+            CloseStatusDTO::CloseAsked(_) => unimplemented!("no triggers have been set"),
         }
     }
 
@@ -107,6 +215,7 @@ impl BuyAsset {
             downpayment: self.downpayment,
             loan: self.loan.principal,
             loan_interest_rate: self.loan.annual_interest_rate,
+            basket: self.form.basket,
             in_progress: in_progress_fn(HostAccount::from(self.dex_account).into()),
         })
     }
@@ -137,60 +246,42 @@ impl SwapTask for BuyAsset {
     }
 
     fn out_currency(&self) -> CurrencyDTO<Self::OutG> {
-        self.form.currency
+        self.leg().map_or(self.form.currency, |leg| leg.currency)
+    }
+
+    fn max_slippage(&self) -> Option<Percent> {
+        self.form.max_slippage
     }
 
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<GIn = Self::InG, Result = IterNext>,
     {
-        dex::on_coins(&self.downpayment, &self.loan.principal, visitor)
+        let downpayment = self.share(&self.downpayment);
+        let loan = self.share(&self.loan.principal);
+        if downpayment.is_zero() {
+            dex::on_coin(&loan, visitor)
+        } else if loan.is_zero() {
+            dex::on_coin(&downpayment, visitor)
+        } else {
+            dex::on_coins(&downpayment, &loan, visitor)
+        }
     }
 
     fn finish(
-        self,
+        mut self,
         amount_out: CoinDTO<Self::OutG>,
         env: &Env,
         querier: QuerierWrapper<'_>,
     ) -> Self::Result {
-        debug_assert_eq!(amount_out.currency(), self.form.currency);
+        debug_assert_eq!(amount_out.currency(), self.out_currency());
         debug_assert!(amount_out.amount() > 0);
 
-        let position = PositionDTO::new(amount_out, self.form.position_spec.into());
-        let profit = ProfitRef::new(self.form.loan.profit.clone(), &querier)?;
-        let reserve = ReserveRef::try_new(self.form.reserve.clone(), &querier)?;
-        let lease_addr = self.dex_account.owner().clone();
-        let cmd = LeaseFactory::new(
-            self.form,
-            lease_addr.clone(),
-            profit,
-            reserve,
-            (self.deps.2, self.deps.1.clone()),
-            self.start_opening_at,
-            &env.block.time,
-        );
-        let OpenLeaseResult { lease, status } =
-            with_lease_deps::execute(cmd, lease_addr, position, self.deps.0, self.deps.1, querier)?;
-
-        let lease = Lease::new(lease, self.dex_account, self.deps.3);
-        let active = Active::new(lease);
-        let emitter = active.emit_opened(env, self.downpayment, self.loan);
-
-        match status {
-            CloseStatusDTO::Paid => {
-                unimplemented!("a freshly open lease should have some due amount")
-            }
-            CloseStatusDTO::None {
-                current_liability: _, // TODO shouldn't we add warning zone events?
-                alarms,
-            } => Ok(StateMachineResponse::from(
-                MessageResponse::messages_with_events(alarms, emitter),
-                active,
-            )),
-            CloseStatusDTO::NeedLiquidation(liquidation) => {
-                liquidation::start(active.into(), liquidation, emitter.into(), env, querier)
-            }
-            CloseStatusDTO::CloseAsked(_) => unimplemented!("no triggers have been set"),
+        self.bought.push(amount_out);
+        if self.bought.len() <= self.basket_legs().len() {
+            self.buy_next(env, querier)
+        } else {
+            self.open(env, querier)
         }
     }
 }
@@ -1,3 +1,24 @@
+use crate::{
+    api::open::NewLeaseForm,
+    error::{ContractError, ContractResult},
+};
+
 pub mod buy_asset;
 pub mod open_ica;
+pub mod pending;
 pub mod request_loan;
+
+/// Check the basket, if any, does not hold the lease currency
+pub(super) fn check_basket(form: &NewLeaseForm) -> ContractResult<()> {
+    if form
+        .basket
+        .as_ref()
+        .is_some_and(|basket| basket.legs.iter().any(|leg| leg.currency == form.currency))
+    {
+        Err(ContractError::BasketLeaseCurrency(
+            form.currency.to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
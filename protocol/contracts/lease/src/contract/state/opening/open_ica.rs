@@ -86,6 +86,7 @@ impl DexContract for OpenIcaAccount {
             downpayment: self.downpayment,
             loan: self.loan.principal,
             loan_interest_rate: self.loan.annual_interest_rate,
+            basket: self.new_lease.form.basket,
             in_progress: OngoingTrx::OpenIcaAccount {},
         })
     }
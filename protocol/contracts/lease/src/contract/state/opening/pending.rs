@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use currency::{BankSymbols, CurrencyDTO, CurrencyDef, MemberOf};
+use finance::{
+    coin::{Coin, WithCoin, WithCoinResult},
+    duration::Duration,
+    price,
+};
+use oracle::{
+    api::alarms::Alarm,
+    stub::{AsAlarms, PriceAlarms},
+};
+use platform::{
+    batch::{Batch, Emit, Emitter},
+    coin_legacy,
+    message::Response as MessageResponse,
+    state_machine::Response as StateMachineResponse,
+};
+use sdk::cosmwasm_std::{Env, MessageInfo, QuerierWrapper, Timestamp};
+use timealarms::stub::TimeAlarmsRef;
+
+use crate::{
+    api::{
+        open::{NewLeaseContract, OpenLimit},
+        query::StateResponse as QueryStateResponse,
+        DownpaymentCoin, LeaseAssetCurrencies, LeasePaymentCurrencies, PaymentCoin,
+    },
+    contract::{
+        finalize::FinalizerRef,
+        funds,
+        state::{closed::Closed, Handler, Response},
+    },
+    error::{ContractError, ContractResult},
+    event::Type,
+    finance::{LpnCoin, OracleRef},
+};
+
+use super::request_loan::RequestLoan;
+
+/// A lease holding the downpayment until the lease asset price drops to a limit
+///
+/// The loan is requested on the first price alarm. The downpayment is sent back to
+/// the customer if the limit order is cancelled or expires beforehand, or if the loan
+/// does not fit into the open interest caps.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Pending {
+    new_lease: NewLeaseContract,
+    open_at: OpenLimit,
+    downpayment: DownpaymentCoin,
+    deps: (OracleRef, TimeAlarmsRef, FinalizerRef),
+}
+
+impl Pending {
+    pub fn new(
+        querier: QuerierWrapper<'_>,
+        now: &Timestamp,
+        info: MessageInfo,
+        spec: NewLeaseContract,
+        open_at: OpenLimit,
+    ) -> ContractResult<(Batch, Self)> {
+        if open_at.expiry <= *now {
+            return Err(ContractError::OpenLimitExpired());
+        }
+
+        let oracle = OracleRef::try_from_base(spec.form.market_price_oracle.clone(), querier)?;
+
+        let timealarms = TimeAlarmsRef::new(spec.form.time_alarms.clone(), querier)?;
+
+        let finalizer = FinalizerRef::try_new(spec.finalizer.clone(), querier)?;
+
+        let downpayment = funds::receive(info.funds)?;
+
+        let price_alarm = open_at.price.base().with_coin(LimitAlarm {
+            lease_currency: spec.form.currency,
+            amount_quote: *open_at.price.quote(),
+            oracle: &oracle,
+        })?;
+        let time_alarm = timealarms.setup_alarm(open_at.expiry)?;
+
+        Ok((time_alarm.merge(price_alarm), {
+            Self {
+                new_lease: spec,
+                open_at,
+                downpayment,
+                deps: (oracle, timealarms, finalizer),
+            }
+        }))
+    }
+
+    fn expired(&self, now: &Timestamp) -> bool {
+        self.open_at.expiry <= *now
+    }
+
+    /// Request the loan if it fits into the open interest caps, otherwise, cancel the lease
+    fn request_loan(self, querier: QuerierWrapper<'_>, env: &Env) -> ContractResult<Response> {
+        coin_legacy::to_cosmwasm_on_network::<BankSymbols<LeasePaymentCurrencies>>(
+            &self.downpayment,
+        )
+        .map_err(Into::into)
+        .and_then(|downpayment| {
+            RequestLoan::new(querier, vec![downpayment], self.new_lease.clone())
+        })
+        .and_then(|(batch, borrow, next_state)| {
+            self.deps
+                .2
+                .open_interest_fits(
+                    self.new_lease.form.currency,
+                    self.new_lease.form.customer.clone(),
+                    borrow,
+                    querier,
+                )
+                .and_then(|fits| {
+                    if fits {
+                        // the finalizer is notified after the loan gets opened and the reply gets processed
+                        self.deps
+                            .2
+                            .notify_loan_opened(self.new_lease.form.loan.lpp.clone())
+                            .map(|finalizer_msgs| {
+                                Response::from(batch.merge(finalizer_msgs), next_state)
+                            })
+                    } else {
+                        self.cancel(env, true)
+                    }
+                })
+        })
+    }
+
+    fn cancel(self, env: &Env, cap_exceeded: bool) -> ContractResult<Response> {
+        let customer = self.new_lease.form.customer.clone();
+        let emitter = self.emit_cancel(env, cap_exceeded);
+
+        funds::send(&self.downpayment, customer.clone())
+            .and_then(|refund_msgs| {
+                self.deps
+                    .2
+                    .notify(customer)
+                    .map(|finalizer_msgs| refund_msgs.merge(finalizer_msgs)) //make sure the finalizer messages go out last
+            })
+            .map(|all_messages| MessageResponse::messages_with_events(all_messages, emitter))
+            .map(|response| StateMachineResponse::from(response, Closed::default()))
+    }
+
+    fn emit_cancel(&self, env: &Env, cap_exceeded: bool) -> Emitter {
+        Emitter::of_type(Type::CancelOpen)
+            .emit_tx_info(env)
+            .emit("id", &env.contract.address)
+            .emit("customer", &self.new_lease.form.customer)
+            .emit_coin_dto("downpayment", &self.downpayment)
+            .emit_to_string_value("expired", self.expired(&env.block.time))
+            .emit_to_string_value("open-interest-cap-exceeded", cap_exceeded)
+    }
+}
+
+impl Handler for Pending {
+    fn state(
+        self,
+        _now: Timestamp,
+        _due_projection: Duration,
+        _querier: QuerierWrapper<'_>,
+    ) -> ContractResult<QueryStateResponse> {
+        Ok(QueryStateResponse::Pending {
+            currency: self.new_lease.form.currency,
+            downpayment: self.downpayment,
+            open_at: self.open_at,
+        })
+    }
+
+    fn close(
+        self,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        access_control::check(&self.new_lease.form.customer, &info.sender)?;
+
+        self.cancel(&env, false)
+    }
+
+    fn on_time_alarm(
+        self,
+        _querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        if !self.deps.1.owned_by(&info.sender) {
+            return Err(ContractError::Unauthorized(
+                access_control::error::Error::Unauthorized {},
+            ));
+        }
+
+        if self.expired(&env.block.time) {
+            self.cancel(&env, false)
+        } else {
+            super::super::ignore_msg(self)
+        }
+    }
+
+    fn on_price_alarm(
+        self,
+        querier: QuerierWrapper<'_>,
+        env: Env,
+        info: MessageInfo,
+    ) -> ContractResult<Response> {
+        if !self.deps.0.owned_by(&info.sender) {
+            return Err(ContractError::Unauthorized(
+                access_control::error::Error::Unauthorized {},
+            ));
+        }
+
+        // the expiry time alarm may be delivered later than the price one
+        if self.expired(&env.block.time) {
+            self.cancel(&env, false)
+        } else {
+            self.request_loan(querier, &env)
+        }
+    }
+}
+
+/// Subscribe for a price alarm when the lease asset price drops below the limit
+struct LimitAlarm<'oracle> {
+    lease_currency: CurrencyDTO<LeaseAssetCurrencies>,
+    amount_quote: PaymentCoin,
+    oracle: &'oracle OracleRef,
+}
+
+impl WithCoin<LeasePaymentCurrencies> for LimitAlarm<'_> {
+    type Output = Batch;
+
+    type Error = ContractError;
+
+    fn on<C>(self, amount: Coin<C>) -> WithCoinResult<LeasePaymentCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        if currency::dto::<C, LeasePaymentCurrencies>()
+            != self
+                .lease_currency
+                .into_super_group::<LeasePaymentCurrencies>()
+        {
+            return Err(ContractError::OpenLimitCurrencyMismatch(
+                self.lease_currency.to_string(),
+            ));
+        }
+
+        LpnCoin::try_from(self.amount_quote)
+            .map_err(Into::into)
+            .and_then(|amount_quote| {
+                let mut price_alarms = self.oracle.as_alarms::<LeasePaymentCurrencies>();
+                price_alarms
+                    .add_alarm(Alarm::new(price::total_of(amount).is(amount_quote), None))
+                    .map(|()| price_alarms.into())
+                    .map_err(Into::into)
+            })
+    }
+}
@@ -1,4 +1,4 @@
-use finance::duration::Duration;
+use finance::{coin::Amount, duration::Duration, fraction::Fraction, zero::Zero};
 use serde::{Deserialize, Serialize};
 
 use platform::{
@@ -6,19 +6,23 @@ use platform::{
     message::Response as MessageResponse,
     state_machine::Response as StateMachineResponse,
 };
-use sdk::cosmwasm_std::{Addr, Env, MessageInfo, QuerierWrapper, Reply, Timestamp};
+use sdk::cosmwasm_std::{Addr, Coin as CwCoin, Env, QuerierWrapper, Reply, Timestamp};
 use timealarms::stub::TimeAlarmsRef;
 
 use crate::{
-    api::{open::NewLeaseContract, query::StateResponse as QueryStateResponse, DownpaymentCoin},
+    api::{
+        open::{NewLeaseContract, NewLeaseForm},
+        query::StateResponse as QueryStateResponse,
+        DownpaymentCoin,
+    },
     contract::{
         cmd::{OpenLoanReq, OpenLoanReqResult, OpenLoanResp},
         finalize::FinalizerRef,
         state::{Handler, Response},
     },
-    error::ContractResult,
+    error::{ContractError, ContractResult},
     event::Type,
-    finance::{LppRef, OracleRef},
+    finance::{LpnCoin, LppRef, OracleRef},
 };
 
 use super::buy_asset::DexState;
@@ -31,11 +35,14 @@ pub(crate) struct RequestLoan {
 }
 
 impl RequestLoan {
+    /// Request the loan
+    ///
+    /// Return the loan request messages, the amount to borrow, and the new state.
     pub fn new(
         querier: QuerierWrapper<'_>,
-        info: MessageInfo,
+        funds: Vec<CwCoin>,
         spec: NewLeaseContract,
-    ) -> ContractResult<(Batch, Self)> {
+    ) -> ContractResult<(Batch, LpnCoin, Self)> {
         let lpp = LppRef::try_new(spec.form.loan.lpp.clone(), querier)?;
 
         let oracle = OracleRef::try_from_base(spec.form.market_price_oracle.clone(), querier)?;
@@ -44,17 +51,22 @@ impl RequestLoan {
 
         let finalizer = FinalizerRef::try_new(spec.finalizer.clone(), querier)?;
 
-        let OpenLoanReqResult { batch, downpayment } = lpp.clone().execute_lender(
+        let OpenLoanReqResult {
+            batch,
+            downpayment,
+            borrow,
+        } = lpp.clone().execute_lender(
             OpenLoanReq::new(
                 spec.form.position_spec,
-                info.funds,
+                funds,
                 spec.form.max_ltd,
                 oracle.clone(),
                 querier,
             ),
             querier,
         )?;
-        Ok((batch, {
+        check_basket_shares(&spec.form, &downpayment)?;
+        Ok((batch, borrow, {
             Self {
                 new_lease: spec,
                 downpayment,
@@ -109,3 +121,14 @@ impl Handler for RequestLoan {
         self.on_response(querier, env, msg)
     }
 }
+
+/// Check each basket leg, if any, gets a share of the downpayment
+fn check_basket_shares(form: &NewLeaseForm, downpayment: &DownpaymentCoin) -> ContractResult<()> {
+    form.basket
+        .iter()
+        .flat_map(|basket| basket.legs.iter())
+        .find(|leg| leg.weight.of(downpayment.amount()) == Amount::ZERO)
+        .map_or(Ok(()), |leg| {
+            Err(ContractError::BasketLegTooSmall(leg.currency.to_string()))
+        })
+}
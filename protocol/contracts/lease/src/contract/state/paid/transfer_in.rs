@@ -3,10 +3,10 @@ use serde::{Deserialize, Serialize};
 
 use currency::CurrencyDTO;
 use dex::{
-    Account, CoinVisitor, ContractInSwap, IterNext, IterState, StartTransferInState, SwapState,
-    SwapTask, TransferInFinishState, TransferInInitState, TransferOutState,
+    Account, CoinVisitor, ContractInSwap, Enterable, IterNext, IterState, StartTransferInState,
+    SwapState, SwapTask, TransferInFinishState, TransferInInitState, TransferOutState,
 };
-use finance::{coin::CoinDTO, duration::Duration};
+use finance::{coin::CoinDTO, duration::Duration, percent::Percent};
 use platform::{
     bank,
     batch::{Emit, Emitter},
@@ -19,14 +19,14 @@ use timealarms::stub::TimeAlarmsRef;
 use crate::{
     api::{
         query::{paid::ClosingTrx, StateResponse as QueryStateResponse},
-        LeaseAssetCurrencies, LeasePaymentCurrencies,
+        LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies,
     },
     contract::{
         cmd::Close,
         state::{
             closed::Closed,
             resp_delivery::{ForwardToDexEntry, ForwardToDexEntryContinue},
-            SwapClient, SwapResult,
+            Response, SwapClient, SwapResult,
         },
         Lease,
     },
@@ -52,19 +52,58 @@ pub(in super::super) type DexState = dex::StateLocalOut<
 >;
 
 pub(in super::super) fn start(lease: Lease) -> StartState {
-    let transfer = TransferIn::new(lease);
-    let amount_in = *transfer.amount();
+    start_transfer(TransferIn::new(lease))
+}
+
+fn start_transfer(transfer: TransferIn) -> StartState {
+    let amount_in = transfer.amount();
     StartState::new(transfer, amount_in)
 }
 
 #[derive(Serialize, Deserialize)]
 pub(crate) struct TransferIn {
     lease: Lease,
+    /// The number of position coins transferred in so far, the lease currency comes first
+    #[serde(default)]
+    transferred: usize,
 }
 
 impl TransferIn {
     pub(in super::super) fn new(lease: Lease) -> Self {
-        Self { lease }
+        Self {
+            lease,
+            transferred: 0,
+        }
+    }
+
+    /// Transfer in the next coin of the position
+    fn transfer_next(self, env: &Env, querier: QuerierWrapper<'_>) -> SwapResult {
+        let start_transfer_in = start_transfer(Self {
+            lease: self.lease,
+            transferred: self.transferred + 1,
+        });
+        start_transfer_in
+            .enter(env.block.time, querier)
+            .map(|batch| Response::from(batch, DexState::from(start_transfer_in)))
+            .map_err(Into::into)
+    }
+
+    /// Send the position to the customer and close the lease
+    fn close(self, env: &Env, querier: QuerierWrapper<'_>) -> SwapResult {
+        let lease_addr = self.lease.lease.addr.clone();
+        let lease_account = bank::account(&lease_addr, querier);
+        let emitter = self.emit_ok(env, &self.lease.lease);
+        let customer = self.lease.lease.customer.clone();
+
+        with_lease_paid::execute(self.lease.lease, Close::new(lease_account))
+            .and_then(|close_msgs| {
+                self.lease
+                    .finalizer
+                    .notify(customer)
+                    .map(|finalizer_msgs| close_msgs.merge(finalizer_msgs)) //make sure the finalizer messages go out last
+            })
+            .map(|all_messages| MessageResponse::messages_with_events(all_messages, emitter))
+            .map(|response| StateMachineResponse::from(response, Closed::default()))
     }
 
     fn state(self, in_progress: ClosingTrx) -> <Self as SwapTask>::StateResponse {
@@ -74,8 +113,8 @@ impl TransferIn {
         ))
     }
 
-    fn amount(&self) -> &CoinDTO<LeaseAssetCurrencies> {
-        self.lease.lease.position.amount()
+    fn amount(&self) -> LeaseCoin {
+        self.lease.lease.position.coins()[self.transferred]
     }
 
     fn emit_ok(&self, env: &Env, lease: &LeaseDTO) -> Emitter {
@@ -113,11 +152,15 @@ impl SwapTask for TransferIn {
         self.amount().currency()
     }
 
+    fn max_slippage(&self) -> Option<Percent> {
+        None
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<GIn = Self::InG, Result = IterNext>,
     {
-        dex::on_coin(self.amount(), visitor)
+        dex::on_coin(&self.amount(), visitor)
     }
 
     fn finish(
@@ -126,21 +169,12 @@ impl SwapTask for TransferIn {
         env: &Env,
         querier: QuerierWrapper<'_>,
     ) -> Self::Result {
-        debug_assert!(&amount_out == self.amount());
-        let lease_addr = self.lease.lease.addr.clone();
-        let lease_account = bank::account(&lease_addr, querier);
-        let emitter = self.emit_ok(env, &self.lease.lease);
-        let customer = self.lease.lease.customer.clone();
-
-        with_lease_paid::execute(self.lease.lease, Close::new(lease_account))
-            .and_then(|close_msgs| {
-                self.lease
-                    .finalizer
-                    .notify(customer)
-                    .map(|finalizer_msgs| close_msgs.merge(finalizer_msgs)) //make sure the finalizer messages go out last
-            })
-            .map(|all_messages| MessageResponse::messages_with_events(all_messages, emitter))
-            .map(|response| StateMachineResponse::from(response, Closed::default()))
+        debug_assert!(amount_out == self.amount());
+        if self.transferred + 1 < self.lease.lease.position.coins().len() {
+            self.transfer_next(env, querier)
+        } else {
+            self.close(env, querier)
+        }
     }
 }
 
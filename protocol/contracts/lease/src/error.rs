@@ -11,7 +11,7 @@ use oracle_platform::error::Error as OracleError;
 use platform::error::Error as PlatformError;
 use profit::stub::Error as ProfitError;
 use reserve::error::Error as ReserveError;
-use sdk::cosmwasm_std::StdError;
+use sdk::cosmwasm_std::{Addr, StdError};
 use timealarms::error::ContractError as TimeAlarmsError;
 
 pub use crate::position::PositionError;
@@ -60,6 +60,9 @@ pub enum ContractError {
     #[error("[Lease] No payment sent")]
     NoPaymentError(),
 
+    #[error("[Lease] No extra amount may be borrowed while the interest is overdue")]
+    OverdueLoanIncrease(),
+
     #[error("[Lease] The operation '{0}' is not supported in the current state")]
     UnsupportedOperation(String),
 
@@ -68,6 +71,48 @@ pub enum ContractError {
 
     #[error("[Lease] Inconsistency not detected")]
     InconsistencyNotDetected(),
+
+    #[error("[Lease] The lease is already owned by '{0}'")]
+    AlreadyOwner(Addr),
+
+    #[error("[Lease] No pending ownership transfer")]
+    NoPendingOwnershipTransfer(),
+
+    #[error("[Lease] '{0}' is not an operator of the lease")]
+    OperatorNotFound(Addr),
+
+    #[error("[Lease] The number of lease operators may not exceed {0}")]
+    TooManyOperators(usize),
+
+    #[error("[Lease] The margin buffer is held in '{0}'")]
+    MarginBufferCurrencyMismatch(String),
+
+    #[error("[Lease] The margin buffer is empty")]
+    MarginBufferEmpty(),
+
+    #[error("[Lease] The auto-repay budget is held in '{0}'")]
+    AutoRepayCurrencyMismatch(String),
+
+    #[error("[Lease] The auto-repay budget is empty")]
+    AutoRepayEmpty(),
+
+    #[error("[Lease] The limit price should be of the lease currency '{0}'")]
+    OpenLimitCurrencyMismatch(String),
+
+    #[error("[Lease] The limit order expiry should be in the future")]
+    OpenLimitExpired(),
+
+    #[error("[Lease] The lease may not be refinanced into '{0}'")]
+    RefinanceLppMismatch(Addr),
+
+    #[error("[Lease] The lease may not be refinanced while there is interest due")]
+    RefinanceInterestDue(),
+
+    #[error("[Lease] The basket may not hold the lease currency '{0}'")]
+    BasketLeaseCurrency(String),
+
+    #[error("[Lease] The downpayment is too small to buy the basket currency '{0}'")]
+    BasketLegTooSmall(String),
 }
 
 impl ContractError {
@@ -1,4 +1,5 @@
 pub enum Type {
+    CancelOpen,
     RequestLoan,
     OpenIcaAccount,
     OpeningSwap,
@@ -8,16 +9,33 @@ pub enum Type {
     ClosingTransferIn,
     Closed,
     LiquidationWarning,
+    OverdueWarning,
     LiquidationSwap,
     Liquidation,
     ClosePosition,
     AutoClosePosition,
+    ChangeClosePolicy,
+    DropCloseTrigger,
+    IncreasePositionSwap,
+    IncreasePosition,
+    TransferOwnershipRequest,
+    TransferOwnership,
+    GrantOperator,
+    RevokeOperator,
+    DepositMarginBuffer,
+    WithdrawMarginBuffer,
+    CoverFromMarginBuffer,
+    DepositAutoRepay,
+    WithdrawAutoRepay,
+    AutoRepay,
+    Refinance,
 }
 
 impl Type {
     /// 'wasm-' is always prepended by the runtime
     pub const fn as_str(&self) -> &'static str {
         match self {
+            Self::CancelOpen => "ls-open-cancel",
             Self::RequestLoan => "ls-request-loan",
             Self::OpenIcaAccount => "ls-open-dex-account",
             Self::OpeningSwap => "ls-open-swap",
@@ -27,10 +45,26 @@ impl Type {
             Self::ClosingTransferIn => "ls-close-transfer-in",
             Self::Closed => "ls-close",
             Self::LiquidationWarning => "ls-liquidation-warning",
+            Self::OverdueWarning => "ls-overdue-warning",
             Self::LiquidationSwap => "ls-liquidation-swap",
             Self::Liquidation => "ls-liquidation",
             Self::ClosePosition => "ls-close-position",
             Self::AutoClosePosition => "ls-auto-close-position",
+            Self::ChangeClosePolicy => "ls-change-close-policy",
+            Self::DropCloseTrigger => "ls-close-trigger-drop",
+            Self::IncreasePositionSwap => "ls-increase-position-swap",
+            Self::IncreasePosition => "ls-increase-position",
+            Self::TransferOwnershipRequest => "ls-transfer-ownership-request",
+            Self::TransferOwnership => "ls-transfer-ownership",
+            Self::GrantOperator => "ls-grant-operator",
+            Self::RevokeOperator => "ls-revoke-operator",
+            Self::DepositMarginBuffer => "ls-margin-buffer-deposit",
+            Self::WithdrawMarginBuffer => "ls-margin-buffer-withdraw",
+            Self::CoverFromMarginBuffer => "ls-margin-buffer-cover",
+            Self::DepositAutoRepay => "ls-auto-repay-deposit",
+            Self::WithdrawAutoRepay => "ls-auto-repay-withdraw",
+            Self::AutoRepay => "ls-auto-repay",
+            Self::Refinance => "ls-refinance",
         }
     }
 }
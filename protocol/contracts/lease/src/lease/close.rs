@@ -1,7 +1,7 @@
 use platform::{bank::FixedAddressSender, batch::Batch};
 
 use currency::{CurrencyDef, MemberOf};
-use finance::{coin::Coin, duration::Duration};
+use finance::{coin::Coin, duration::Duration, fraction::Fraction, percent::Percent};
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
 use reserve::stub::Reserve as ReserveTrait;
@@ -47,6 +47,26 @@ where
         })
     }
 
+    /// Calculate the position amount that corresponds to `percent` of it
+    pub(crate) fn close_amount_of(&self, percent: Percent) -> Coin<Asset> {
+        percent.of(self.position.amount())
+    }
+
+    /// Calculate the position amount to close in order to bring the LTV down to `ltv`
+    pub(crate) fn close_amount_to_ltv(
+        &self,
+        ltv: Percent,
+        now: &Timestamp,
+    ) -> ContractResult<Coin<Asset>> {
+        let due = self.loan.state(now);
+
+        self.price_of_lease_currency().and_then(|asset_in_lpns| {
+            self.position
+                .close_amount_to_ltv(&due, asset_in_lpns, ltv)
+                .map_err(Into::into)
+        })
+    }
+
     pub(crate) fn close_partial<Profit>(
         &mut self,
         asset: Coin<Asset>,
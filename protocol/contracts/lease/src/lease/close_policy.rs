@@ -1,5 +1,5 @@
 use currency::{Currency, CurrencyDef, MemberOf};
-use finance::liability::Zone;
+use finance::{coin::Coin, liability::Zone};
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
 use sdk::cosmwasm_std::Timestamp;
@@ -8,7 +8,7 @@ use crate::{
     api::{position::ClosePolicyChange, LeaseAssetCurrencies, LeasePaymentCurrencies},
     error::ContractResult,
     finance::{LpnCurrencies, LpnCurrency, Price},
-    position::{CloseStrategy, Debt, Liquidation, Steadiness},
+    position::{CloseStrategy, Debt, Liquidation, OverdueWarning, PositionError, Steadiness},
 };
 
 use super::Lease;
@@ -34,6 +34,7 @@ where
                     Debt::No => CloseStatus::Paid,
                     Debt::Ok { zone, steadiness } => CloseStatus::None {
                         current_liability: zone,
+                        overdue_warning: self.position.overdue_warning(&due),
                         steadiness,
                     },
                     Debt::Bad(liquidation) => CloseStatus::NeedLiquidation(liquidation),
@@ -55,11 +56,61 @@ where
         })
     }
 
+    /// Bring the close policy up to date with the current debt and asset price
+    ///
+    /// The triggers set at a price follow the debt, and a trailing stop loss is ratcheted down.
+    /// Expected to be called before any check of the close policy.
+    /// Return the triggers dropped since their LTV has got out of range.
+    pub(crate) fn update_close_policy(
+        &mut self,
+        now: &Timestamp,
+    ) -> ContractResult<Vec<CloseStrategy>> {
+        let due = self.loan.state(now);
+
+        self.price_of_lease_currency()
+            .map(|asset_in_lpns| self.position.update_close_policy(&due, asset_in_lpns))
+    }
+
+    /// Determine the close on the triggered `strategy`
+    ///
+    /// A trigger closing a part of the position is consumed. The part is closed if it is
+    /// valid for closing, otherwise the close is skipped. A trigger without a part
+    /// closes the whole position.
+    pub(crate) fn partial_auto_close(
+        &mut self,
+        strategy: &CloseStrategy,
+    ) -> ContractResult<TriggeredClose<Asset>> {
+        self.position
+            .close_part(strategy)
+            .map_or(Ok(TriggeredClose::Full), |amount| {
+                self.price_of_lease_currency().map(|asset_in_lpns| {
+                    let close = self
+                        .position
+                        .validate_close_amount(amount, asset_in_lpns)
+                        .map_or_else(TriggeredClose::Skipped, |()| {
+                            TriggeredClose::Partial(amount)
+                        });
+                    self.position.consume_close_trigger(strategy);
+                    close
+                })
+            })
+    }
+
     pub(super) fn price_of_lease_currency(&self) -> ContractResult<Price<Asset>> {
         self.oracle.price_of::<Asset>().map_err(Into::into)
     }
 }
 
+pub(crate) enum TriggeredClose<Asset>
+where
+    Asset: Currency,
+{
+    Full,
+    Partial(Coin<Asset>),
+    /// The part is not valid for closing
+    Skipped(PositionError),
+}
+
 pub(crate) enum CloseStatus<Asset>
 where
     Asset: Currency,
@@ -67,6 +118,7 @@ where
     Paid,
     None {
         current_liability: Zone,
+        overdue_warning: Option<OverdueWarning>,
         steadiness: Steadiness<Asset>,
     },
     CloseAsked(CloseStrategy),
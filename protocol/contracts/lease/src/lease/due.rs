@@ -11,6 +11,13 @@ impl DueTrait for State {
         self.principal_due + self.total_due_interest()
     }
 
+    fn total_due_interest(&self) -> LpnCoin {
+        self.due_interest
+            + self.due_margin_interest
+            + self.overdue.interest()
+            + self.overdue.margin()
+    }
+
     fn overdue_collection(&self, min_amount: LpnCoin) -> OverdueCollection {
         let total_due_interest = self.total_due_interest();
         let time_to_accrue_min_amount = if total_due_interest >= min_amount {
@@ -38,15 +45,6 @@ impl DueTrait for State {
     }
 }
 
-impl State {
-    fn total_due_interest(&self) -> LpnCoin {
-        self.due_interest
-            + self.due_margin_interest
-            + self.overdue.interest()
-            + self.overdue.margin()
-    }
-}
-
 #[cfg(test)]
 mod test {
     use finance::{coin::Coin, duration::Duration, interest, percent::Percent, zero::Zero};
@@ -0,0 +1,160 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::{coin::Coin, price};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::bank::FixedAddressSender;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency},
+    lease::Lease,
+};
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+{
+    /// Add the `asset` to the position leaving the loan intact
+    ///
+    /// The position's LTV goes down and a subsequent check of the close policy is expected.
+    pub(crate) fn increase_position(&mut self, asset: Coin<Asset>) {
+        self.position.increase(asset)
+    }
+
+    /// Borrow an extra amount to increase the position with along the `payment`
+    ///
+    /// The `borrow` is capped so the position LTV past the increase stays at or below
+    /// the initial liability LTV. The due interests are paid off the borrowed amount.
+    /// Return what is left of it.
+    pub(crate) fn increase_loan<PaymentC, Profit>(
+        &mut self,
+        payment: Coin<PaymentC>,
+        borrow: LpnCoin,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<LpnCoin>
+    where
+        PaymentC: CurrencyDef,
+        PaymentC::Group: MemberOf<LeasePaymentCurrencies>,
+        Profit: FixedAddressSender,
+    {
+        let payment_in_lpns = self.oracle.price_of::<PaymentC>()?;
+        let asset_in_lpns = self.price_of_lease_currency()?;
+        let due = self.loan.state(now);
+        let max_borrow = self.position.max_increase_borrow(
+            price::total(payment, payment_in_lpns),
+            &due,
+            due.principal_due,
+            asset_in_lpns,
+        );
+
+        self.loan.increase(borrow.min(max_borrow), now, profit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use finance::{duration::Duration, percent::Percent};
+    use lpp::msg::LoanResponse;
+    use profit::stub::ProfitRef;
+
+    use crate::{error::ContractError, lease::tests};
+
+    #[test]
+    fn increase_position() {
+        let lease_amount = tests::coin(1000);
+        let increment = tests::coin(250);
+        let loan = LoanResponse {
+            principal_due: tests::lpn_coin(600),
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: tests::LEASE_START,
+        };
+        let mut lease = tests::open_lease(lease_amount, loan);
+
+        lease.increase_position(increment);
+        assert_eq!(
+            lease_amount + increment,
+            lease.state(tests::LEASE_START, Duration::default()).amount
+        );
+    }
+
+    #[test]
+    fn increase_loan_capped() {
+        let loan = LoanResponse {
+            principal_due: tests::lpn_coin(600_000),
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: tests::LEASE_START,
+        };
+        let mut lease = tests::open_lease(tests::coin(1_000_000), loan);
+        let mut profit = ProfitRef::unchecked("profit").into_stub();
+
+        // equity = 1_000_000 + 100_000 - 600_000, max debt = equity * 65 / 35
+        assert_eq!(
+            Ok(tests::lpn_coin(928_571 - 600_000)),
+            lease.increase_loan(
+                tests::lpn_coin(100_000),
+                tests::lpn_coin(1_000_000),
+                &tests::LEASE_START,
+                &mut profit
+            )
+        );
+        assert_eq!(
+            tests::lpn_coin(928_571),
+            lease
+                .state(tests::LEASE_START, Duration::default())
+                .principal_due
+        );
+    }
+
+    #[test]
+    fn increase_loan_pays_due() {
+        let principal = tests::lpn_coin(600_000);
+        let loan = LoanResponse {
+            principal_due: principal,
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: tests::LEASE_START,
+        };
+        let mut lease = tests::open_lease(tests::coin(1_000_000), loan);
+        let mut profit = ProfitRef::unchecked("profit").into_stub();
+
+        let now = tests::LEASE_START + Duration::from_days(10);
+        let state = lease.state(now, Duration::default());
+        let borrow = tests::lpn_coin(50_000);
+        assert_eq!(
+            Ok(borrow - state.due_interest - state.due_margin),
+            lease.increase_loan(tests::lpn_coin(0), borrow, &now, &mut profit)
+        );
+
+        let state = lease.state(now, Duration::default());
+        assert_eq!(principal + borrow, state.principal_due);
+        assert_eq!(tests::lpn_coin(0), state.due_interest);
+        assert_eq!(tests::lpn_coin(0), state.due_margin);
+    }
+
+    #[test]
+    fn increase_loan_overdue() {
+        let loan = LoanResponse {
+            principal_due: tests::lpn_coin(600_000),
+            annual_interest_rate: Percent::from_permille(50),
+            interest_paid: tests::LEASE_START,
+        };
+        let mut lease = tests::open_lease(tests::coin(1_000_000), loan);
+        let mut profit = ProfitRef::unchecked("profit").into_stub();
+
+        let now = tests::LEASE_START + tests::DUE_PERIOD;
+        assert_eq!(
+            Err(ContractError::OverdueLoanIncrease()),
+            lease.increase_loan(
+                tests::lpn_coin(0),
+                tests::lpn_coin(50_000),
+                &now,
+                &mut profit
+            )
+        );
+    }
+}
@@ -12,19 +12,26 @@ use crate::{
     error::{ContractError, ContractResult},
     finance::{LpnCurrencies, LpnCurrency, OracleRef, ReserveRef},
     loan::Loan,
-    position::Position,
+    position::{Debt, DueTrait, Position},
 };
 
 pub(super) use self::{
-    close_policy::CloseStatus, dto::LeaseDTO, paid::Lease as LeasePaid, state::State,
+    close_policy::{CloseStatus, TriggeredClose},
+    dto::LeaseDTO,
+    paid::Lease as LeasePaid,
+    simulate::Simulation,
+    state::{LiabilityStatus, Projection, State},
 };
 
 mod close;
 mod close_policy;
 mod dto;
 mod due;
+mod increase;
 mod paid;
+mod refinance;
 mod repay;
+mod simulate;
 mod state;
 pub(crate) mod with_lease;
 pub(crate) mod with_lease_deps;
@@ -100,7 +107,9 @@ where
         let overdue_collect_in = self.position.overdue_collection_in(&loan);
 
         State {
+            kind: self.position.kind(),
             amount: self.position.amount(),
+            basket: self.position.basket().copied().collect(),
             interest_rate: loan.annual_interest,
             interest_rate_margin: loan.annual_interest_margin,
             principal_due: loan.principal_due,
@@ -114,6 +123,57 @@ where
             validity: now,
         }
     }
+
+    pub(crate) fn projection(&self, now: Timestamp, due_projection: Duration) -> Projection<Asset>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        let estimate_at = now + due_projection;
+        let loan = self.loan.state(&estimate_at);
+
+        Projection {
+            amount: self.position.amount(),
+            total_due: loan.total_due(),
+            price_levels: self.position.price_levels(&loan),
+            due_projection,
+            validity: now,
+        }
+    }
+
+    /// Determine the position liability against the debt estimated at `now` plus `due_projection`
+    ///
+    /// Return `None` if there is no debt.
+    pub(crate) fn liability_status(
+        &self,
+        now: Timestamp,
+        due_projection: Duration,
+    ) -> ContractResult<Option<LiabilityStatus<Asset>>>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    {
+        let estimate_at = now + due_projection;
+        let due = self.loan.state(&estimate_at);
+
+        self.price_of_lease_currency().map(|asset_in_lpns| {
+            let ltv = self.position.ltv(&due, asset_in_lpns);
+            if self.position.check_close(&due, asset_in_lpns).is_some() {
+                Some(LiabilityStatus::CloseDue { ltv })
+            } else {
+                match self.position.debt(&due, asset_in_lpns) {
+                    Debt::No => None,
+                    Debt::Ok { zone, steadiness } => Some(LiabilityStatus::Steady {
+                        ltv,
+                        zone,
+                        next_check: estimate_at + steadiness.r#for(),
+                        steadiness,
+                    }),
+                    Debt::Bad(_) => Some(LiabilityStatus::CloseDue { ltv }),
+                }
+            }
+        })
+    }
 }
 
 impl<Asset, LppLoan, Oracle> Lease<Asset, LppLoan, Oracle>
@@ -164,8 +224,12 @@ pub mod tests {
     use currencies::{testing::PaymentC7, Lpn};
     use currency::{Currency, Group, MemberOf};
     use finance::{
-        coin::Coin, duration::Duration, fraction::Fraction, liability::Liability, percent::Percent,
-        price::Price,
+        coin::Coin,
+        duration::Duration,
+        fraction::Fraction,
+        liability::{Liability, Zone},
+        percent::Percent,
+        price::{self, Price},
     };
     use lpp::{
         error::{ContractError as LppError, Result as LppResult},
@@ -179,6 +243,7 @@ pub mod tests {
 
     use crate::{
         api::{
+            open::LeaseKind,
             position::{ChangeCmd, ClosePolicyChange},
             query::opened::ClosePolicy,
         },
@@ -187,7 +252,7 @@ pub mod tests {
         position::{Position, Spec as PositionSpec},
     };
 
-    use super::{Lease, State};
+    use super::{Lease, LiabilityStatus, State};
 
     const CUSTOMER: &str = "customer";
     const LEASE_ADDR: &str = "lease_addr";
@@ -229,6 +294,11 @@ pub mod tests {
             self.loan.repay(by, repayment)
         }
 
+        fn increase(&mut self, by: &Timestamp, amount: Coin<Lpn>) -> Coin<Lpn> {
+            self.loan
+                .increase(by, amount, self.loan.annual_interest_rate)
+        }
+
         fn annual_interest_rate(&self) -> Percent {
             self.loan.annual_interest_rate
         }
@@ -358,7 +428,9 @@ pub mod tests {
             let due_projection = Duration::default();
             assert_eq!(
                 State {
+                    kind: LeaseKind::Long,
                     amount: lease_amount,
+                    basket: vec![],
                     interest_rate,
                     interest_rate_margin: MARGIN_INTEREST_RATE,
                     principal_due: loan.principal_due,
@@ -398,6 +470,57 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn liability_status_steady() {
+        let lease_amount = coin(1000);
+        let principal_due = lpn_coin(700);
+        let lease = open_lease(
+            lease_amount,
+            LoanResponse {
+                principal_due,
+                annual_interest_rate: Percent::from_permille(50),
+                interest_paid: LEASE_START,
+            },
+        );
+
+        let Some(LiabilityStatus::Steady {
+            ltv,
+            zone,
+            steadiness,
+            next_check,
+        }) = lease
+            .liability_status(LEASE_START, Duration::default())
+            .unwrap()
+        else {
+            unreachable!("the position should be steady")
+        };
+        assert_eq!(Percent::from_percent(70), ltv);
+        assert_eq!(Zone::no_warnings(FIRST_LIQ_WARN), zone);
+        assert_eq!(LEASE_START + RECHECK_TIME, next_check);
+        assert_eq!(
+            &price::total_of(FIRST_LIQ_WARN.of(lease_amount)).is(principal_due),
+            steadiness.within().above()
+        );
+        assert_eq!(None, steadiness.within().may_below_or_equal());
+    }
+
+    #[test]
+    fn liability_status_close_due() {
+        let lease = open_lease(
+            coin(100_000_000),
+            LoanResponse {
+                principal_due: lpn_coin(85_000_000),
+                annual_interest_rate: Percent::from_permille(50),
+                interest_paid: LEASE_START,
+            },
+        );
+
+        assert!(matches!(
+            lease.liability_status(LEASE_START, Duration::default()),
+            Ok(Some(LiabilityStatus::CloseDue { ltv })) if ltv == Percent::from_permille(850)
+        ));
+    }
+
     fn assert_state(
         principal_due: Coin<TestLpn>,
         interest_rate: Percent,
@@ -412,7 +535,9 @@ pub mod tests {
         let exp_due_interest = due_projection.annualized_slice_of(interest_rate.of(principal_due));
         assert_eq!(
             State {
+                kind: LeaseKind::Long,
                 amount: lease_amount,
+                basket: vec![],
                 interest_rate,
                 interest_rate_margin: MARGIN_INTEREST_RATE,
                 principal_due,
@@ -1,11 +1,18 @@
 use std::marker::PhantomData;
 
-use currency::{Currency, CurrencyDef, MemberOf};
+use currency::{
+    never::{self, Never},
+    Currency, CurrencyDef, MemberOf,
+};
+use finance::coin::{Coin, WithCoin, WithCoinResult};
 use platform::{bank::BankAccount, batch::Batch};
 use sdk::cosmwasm_std::Addr;
 
 use crate::{
-    api::LeaseAssetCurrencies, error::ContractResult, finance::LpnCurrencies, position::Position,
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    error::ContractResult,
+    finance::LpnCurrencies,
+    position::Position,
 };
 
 use super::LeaseDTO;
@@ -45,12 +52,41 @@ where
             lease_account.send(surplus, self.customer.clone());
         }
 
+        self.position.basket().for_each(|leg| {
+            never::safe_unwrap(leg.with_coin(SendLeg {
+                account: &mut lease_account,
+                to: &self.customer,
+            }))
+        });
         lease_account.send(self.position.amount(), self.customer);
 
         Ok(lease_account.into())
     }
 }
 
+struct SendLeg<'account, B> {
+    account: &'account mut B,
+    to: &'account Addr,
+}
+
+impl<B> WithCoin<LeaseAssetCurrencies> for SendLeg<'_, B>
+where
+    B: BankAccount,
+{
+    type Output = ();
+
+    type Error = Never;
+
+    fn on<C>(self, leg: Coin<C>) -> WithCoinResult<LeaseAssetCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    {
+        self.account.send(leg, self.to.clone());
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::marker::PhantomData;
@@ -0,0 +1,80 @@
+use currency::{Currency, MemberOf};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency},
+    lease::Lease,
+};
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Asset: Currency + MemberOf<LeaseAssetCurrencies>,
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+{
+    /// The amount of a new loan that would refinance the current one at `now`
+    pub(crate) fn refinance_amount(&self, now: &Timestamp) -> ContractResult<LpnCoin> {
+        self.loan.refinance_amount(now)
+    }
+
+    /// Repay in full the current loan with the proceeds of a new one
+    ///
+    /// The position and the margin due period remain intact.
+    pub(crate) fn refinance(&mut self, now: &Timestamp) -> LpnCoin {
+        self.loan.repay_lpp_loan(now)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use finance::{coin::Coin, duration::Duration, percent::Percent};
+    use lpp::msg::LoanResponse;
+
+    use crate::{
+        error::ContractError,
+        lease::tests::{self, TestCurrency, TestLease},
+    };
+
+    const PRINCIPAL: u128 = 600;
+
+    #[test]
+    fn refinance() {
+        let lease_amount = tests::coin(1000);
+        let mut lease = open_lease(lease_amount);
+        let now = tests::LEASE_START;
+
+        let principal = tests::lpn_coin(PRINCIPAL);
+        assert_eq!(Ok(principal), lease.refinance_amount(&now));
+        assert_eq!(principal, lease.refinance(&now));
+        assert_eq!(Ok(Coin::default()), lease.refinance_amount(&now));
+
+        let state = lease.state(now, Duration::default());
+        assert_eq!(lease_amount, state.amount);
+        assert_eq!(Coin::default(), state.principal_due);
+    }
+
+    #[test]
+    fn refinance_interest_due() {
+        let lease = open_lease(tests::coin(1000));
+
+        assert_eq!(
+            Err(ContractError::RefinanceInterestDue()),
+            lease.refinance_amount(&(tests::LEASE_START + Duration::YEAR))
+        );
+    }
+
+    fn open_lease(amount: Coin<TestCurrency>) -> TestLease {
+        tests::open_lease(
+            amount,
+            LoanResponse {
+                principal_due: tests::lpn_coin(PRINCIPAL),
+                annual_interest_rate: Percent::from_permille(50),
+                interest_paid: tests::LEASE_START,
+            },
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use currency::{Currency, CurrencyDef, MemberOf};
-use finance::coin::Coin;
+use finance::{coin::Coin, price};
 use lpp::stub::loan::LppLoan as LppLoanTrait;
 use oracle_platform::Oracle as OracleTrait;
 use platform::bank::FixedAddressSender;
@@ -11,6 +11,7 @@ use crate::{
     finance::{LpnCoin, LpnCurrencies, LpnCurrency},
     lease::Lease,
     loan::RepayReceipt,
+    position::DueTrait,
 };
 
 impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
@@ -34,6 +35,37 @@ where
             })
     }
 
+    /// Determine how much of the auto-repay `budget` to spend on the due interest
+    ///
+    /// Return `None` if no interest has got overdue yet, or the amount is less than
+    /// the minimum transaction.
+    pub(crate) fn auto_repay_amount<PaymentC>(
+        &self,
+        budget: Coin<PaymentC>,
+        now: &Timestamp,
+    ) -> ContractResult<Option<Coin<PaymentC>>>
+    where
+        PaymentC: CurrencyDef,
+        PaymentC::Group: MemberOf<LeasePaymentCurrencies>,
+    {
+        let due = self.loan.state(now);
+        if due.overdue.interest().is_zero() && due.overdue.margin().is_zero() {
+            Ok(None)
+        } else {
+            self.oracle
+                .price_of::<PaymentC>()
+                .map_err(Into::into)
+                .map(|payment_in_lpns| {
+                    let amount =
+                        price::total(due.total_due_interest(), payment_in_lpns.inv()).min(budget);
+                    self.position
+                        .validate_payment(amount, payment_in_lpns)
+                        .ok()
+                        .map(|()| amount)
+                })
+        }
+    }
+
     pub(crate) fn repay<Profit>(
         &mut self,
         payment: LpnCoin,
@@ -0,0 +1,130 @@
+use currency::{CurrencyDef, MemberOf};
+use finance::{coin::Coin, duration::Duration, percent::Percent, price, zero::Zero};
+use lpp::stub::loan::LppLoan as LppLoanTrait;
+use oracle_platform::Oracle as OracleTrait;
+use platform::bank::FixedAddressSender;
+use sdk::cosmwasm_std::Timestamp;
+
+use crate::{
+    api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    error::ContractResult,
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency, OracleRef},
+    loan::RepayReceipt,
+};
+
+use super::Lease;
+
+/// The outcome of an action on the lease simulated against the current prices
+pub(crate) struct Simulation<Asset> {
+    pub amount: Coin<Asset>,
+    pub payment: LpnCoin,
+    pub receipt: RepayReceipt,
+    pub principal_due: LpnCoin,
+    pub ltv: Option<Percent>,
+}
+
+impl<Asset, Lpp, Oracle> Lease<Asset, Lpp, Oracle>
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    Lpp: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    Oracle: OracleTrait<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>
+        + Into<OracleRef>,
+{
+    /// Simulate a repayment
+    ///
+    /// The `payment` is estimated in the LPN at the current price.
+    pub(crate) fn simulate_repay<PaymentC, Profit>(
+        mut self,
+        payment: Coin<PaymentC>,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<Simulation<Asset>>
+    where
+        PaymentC: CurrencyDef,
+        PaymentC::Group: MemberOf<LeasePaymentCurrencies>,
+        Profit: FixedAddressSender,
+    {
+        self.validate_repay(payment)
+            .and_then(|()| self.oracle.price_of::<PaymentC>().map_err(Into::into))
+            .map(|payment_in_lpns| price::total(payment, payment_in_lpns))
+            .and_then(|payment| {
+                self.repay(payment, now, profit)
+                    .map(|receipt| (payment, receipt))
+            })
+            .and_then(|(payment, receipt)| self.into_simulation(payment, receipt, now))
+    }
+
+    /// Simulate a partial close of the position
+    ///
+    /// The proceeds of the `asset` sale are estimated in the LPN at the current price.
+    pub(crate) fn simulate_close_partial<Profit>(
+        mut self,
+        asset: Coin<Asset>,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<Simulation<Asset>>
+    where
+        Profit: FixedAddressSender,
+    {
+        self.validate_close(asset)
+            .and_then(|()| self.price_of_lease_currency())
+            .map(|asset_in_lpns| price::total(asset, asset_in_lpns))
+            .and_then(|payment| {
+                self.close_partial(asset, payment, now, profit)
+                    .map(|receipt| (payment, receipt))
+            })
+            .and_then(|(payment, receipt)| self.into_simulation(payment, receipt, now))
+    }
+
+    /// Simulate a full close of the position
+    ///
+    /// The proceeds of the position sale are estimated in the LPN at the current price.
+    /// Any shortfall against the total debt is covered by the reserve.
+    pub(crate) fn simulate_close_full<Profit>(
+        mut self,
+        now: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<Simulation<Asset>>
+    where
+        Profit: FixedAddressSender,
+    {
+        let total_due = self.state(*now, Duration::default()).total_due();
+        self.price_of_lease_currency()
+            .map(|asset_in_lpns| price::total(self.position.amount(), asset_in_lpns))
+            .and_then(|payment| {
+                self.repay(payment.max(total_due), now, profit)
+                    .map(|receipt| {
+                        debug_assert!(receipt.close());
+                        Simulation {
+                            amount: Coin::ZERO,
+                            payment,
+                            receipt,
+                            principal_due: LpnCoin::ZERO,
+                            ltv: None,
+                        }
+                    })
+            })
+    }
+
+    fn into_simulation(
+        self,
+        payment: LpnCoin,
+        receipt: RepayReceipt,
+        now: &Timestamp,
+    ) -> ContractResult<Simulation<Asset>> {
+        let state = self.state(*now, Duration::default());
+        let total_due = state.total_due();
+        self.price_of_lease_currency().map(|asset_in_lpns| {
+            let position_in_lpns = price::total(state.amount, asset_in_lpns);
+            Simulation {
+                amount: state.amount,
+                payment,
+                receipt,
+                principal_due: state.principal_due,
+                ltv: (!total_due.is_zero() && !position_in_lpns.is_zero())
+                    .then(|| Percent::from_ratio(total_due, position_in_lpns)),
+            }
+        })
+    }
+}
@@ -1,11 +1,17 @@
-use finance::{coin::Coin, duration::Duration, percent::Percent};
+use finance::{coin::Coin, duration::Duration, liability::Zone, percent::Percent};
 use sdk::cosmwasm_std::Timestamp;
 
-use crate::{api::query::opened::ClosePolicy, finance::LpnCoin};
+use crate::{
+    api::{open::LeaseKind, query::opened::ClosePolicy, LeaseCoin},
+    finance::LpnCoin,
+    position::{PriceLevels, Steadiness},
+};
 
 #[cfg_attr(any(test, feature = "testing"), derive(Debug, Eq, PartialEq))]
 pub struct State<Asset> {
+    pub kind: LeaseKind,
     pub amount: Coin<Asset>,
+    pub basket: Vec<LeaseCoin>,
     pub interest_rate: Percent,
     pub interest_rate_margin: Percent,
     pub principal_due: LpnCoin,
@@ -29,3 +35,31 @@ impl<Asset> State<Asset> {
             + self.due_interest
     }
 }
+
+pub struct Projection<Asset>
+where
+    Asset: 'static,
+{
+    pub amount: Coin<Asset>,
+    pub total_due: LpnCoin,
+    pub price_levels: Option<PriceLevels<Asset>>,
+    pub due_projection: Duration,
+    pub validity: Timestamp,
+}
+
+/// The position liability against the debt and the lease asset price
+pub(crate) enum LiabilityStatus<Asset>
+where
+    Asset: 'static,
+{
+    /// The position LTV is within a liability zone
+    Steady {
+        ltv: Percent,
+        zone: Zone,
+        steadiness: Steadiness<Asset>,
+        /// The latest time the position is checked again at
+        next_check: Timestamp,
+    },
+    /// The position is subject to a close, either triggered by the close policy or a liquidation
+    CloseDue { ltv: Percent },
+}
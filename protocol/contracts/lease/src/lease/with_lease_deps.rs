@@ -129,6 +129,7 @@ where
     Asset: CurrencyDef,
     Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
     LppLoan: LppLoanTrait<LpnCurrency, LpnCurrencies>,
+    oracle_platform::error::Error: Into<Cmd::Error>,
 {
     type G = LeasePaymentCurrencies;
 
@@ -140,7 +141,13 @@ where
         Oracle:
             OracleTrait<Self::G, QuoteC = LpnCurrency, QuoteG = LpnCurrencies> + Into<OracleRef>,
     {
-        self.cmd
-            .exec::<LpnCurrency, Asset, _, _>(self.position, self.lpp_loan, oracle)
+        let mut position = self.position;
+        position
+            .revalue_basket(&oracle)
+            .map_err(Into::into)
+            .and_then(|()| {
+                self.cmd
+                    .exec::<LpnCurrency, Asset, _, _>(position, self.lpp_loan, oracle)
+            })
     }
 }
@@ -47,6 +47,11 @@ impl LoanDTO {
     pub(crate) fn profit(&self) -> &ProfitRef {
         &self.profit
     }
+
+    /// Move the loan over to another Lpp keeping the margin due period intact
+    pub(crate) fn refinance(self, lpp: LppRef) -> Self {
+        Self { lpp, ..self }
+    }
 }
 
 #[cfg_attr(test, derive(Debug))]
@@ -169,6 +174,76 @@ where
         Ok(receipt)
     }
 
+    /// Borrow an extra amount by the given timestamp
+    ///
+    /// The due interest and margin interest are paid off the `amount`.
+    /// Return the rest of the `amount`, or zero, if it does not exceed the due interests
+    /// in which case nothing is borrowed. The loan should not be overdue.
+    pub(crate) fn increase<Profit>(
+        &mut self,
+        amount: LpnCoin,
+        by: &Timestamp,
+        profit: &mut Profit,
+    ) -> ContractResult<LpnCoin>
+    where
+        Profit: FixedAddressSender,
+    {
+        self.debug_check_start_due_before(by, "before the 'increase-by' time");
+
+        let state = self.state(by);
+        if let Overdue::Accrued { .. } = state.overdue {
+            return Err(ContractError::OverdueLoanIncrease());
+        }
+
+        let interest_due = state.due_interest;
+        let margin_due = state.due_margin_interest;
+        if amount <= interest_due + margin_due {
+            return Ok(Coin::ZERO);
+        }
+
+        self.repay_margin(state.principal_due, margin_due, by);
+        profit.send(margin_due);
+        let interest_paid = self.lpp_loan.increase(by, amount);
+        debug_assert_eq!(interest_due, interest_paid);
+
+        Ok(amount - interest_paid - margin_due)
+    }
+
+    /// The amount of a new loan that would refinance this one at the given timestamp
+    ///
+    /// The loan may be refinanced only if there is no interest due, either to the Lpp
+    /// or as margin. Otherwise, the interest would turn into principal of the new loan.
+    pub(crate) fn refinance_amount(&self, by: &Timestamp) -> ContractResult<LpnCoin> {
+        let state = self.state(by);
+        if state.overdue.interest().is_zero()
+            && state.overdue.margin().is_zero()
+            && state.due_interest.is_zero()
+            && state.due_margin_interest.is_zero()
+        {
+            Ok(state.principal_due)
+        } else {
+            Err(ContractError::RefinanceInterestDue())
+        }
+    }
+
+    /// Repay in full the Lpp loan by the given timestamp
+    ///
+    /// There should be no interest due. Return the repaid principal.
+    pub(crate) fn repay_lpp_loan(&mut self, by: &Timestamp) -> LpnCoin {
+        self.debug_check_start_due_before(by, "before the 'repay-by' time");
+
+        let principal_due = self.lpp_loan.principal_due();
+        let RepayShares {
+            interest,
+            principal,
+            excess,
+        } = self.lpp_loan.repay(by, principal_due);
+        debug_assert_eq!(interest, Coin::ZERO);
+        debug_assert_eq!(principal, principal_due);
+        debug_assert_eq!(excess, Coin::ZERO);
+        principal_due
+    }
+
     pub(crate) fn state(&self, now: &Timestamp) -> State {
         self.debug_check_start_due_before(now, "in the past. Now is ");
 
@@ -1071,6 +1146,89 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod test_refinance {
+        use finance::{coin::Coin, duration::Duration, zero::Zero};
+        use lpp::{msg::LoanResponse, stub::loan::LppLoan};
+        use sdk::cosmwasm_std::Timestamp;
+
+        use crate::{
+            error::ContractError,
+            loan::{
+                tests::{create_loan, profit_stub, LppLoanLocal},
+                Loan,
+            },
+        };
+
+        use super::{LEASE_START, LOAN_INTEREST_RATE};
+
+        const PRINCIPAL: u128 = 1000;
+
+        #[test]
+        fn no_interest_due() {
+            let mut loan = loan();
+
+            assert_eq!(Ok(PRINCIPAL.into()), loan.refinance_amount(&LEASE_START));
+            assert_eq!(Coin::new(PRINCIPAL), loan.repay_lpp_loan(&LEASE_START));
+
+            assert_eq!(Coin::ZERO, loan.lpp_loan.principal_due());
+            assert_eq!(LEASE_START, loan.margin_paid_by);
+        }
+
+        #[test]
+        fn interest_due() {
+            let loan = loan();
+
+            assert_eq!(
+                Err(ContractError::RefinanceInterestDue()),
+                loan.refinance_amount(&(LEASE_START + Duration::from_days(30)))
+            );
+        }
+
+        #[test]
+        fn interest_overdue() {
+            let loan = loan();
+
+            assert_eq!(
+                Err(ContractError::RefinanceInterestDue()),
+                loan.refinance_amount(&(LEASE_START + Duration::YEAR + Duration::YEAR))
+            );
+        }
+
+        #[test]
+        fn interest_paid() {
+            let mut loan = loan();
+            let now = LEASE_START + Duration::from_days(30);
+            pay_interest(&mut loan, &now);
+
+            assert_eq!(Ok(PRINCIPAL.into()), loan.refinance_amount(&now));
+            assert_eq!(Coin::new(PRINCIPAL), loan.repay_lpp_loan(&now));
+            assert_eq!(Coin::ZERO, loan.lpp_loan.principal_due());
+
+            assert_eq!(now, loan.margin_paid_by);
+        }
+
+        fn loan() -> Loan<LppLoanLocal> {
+            create_loan(LoanResponse {
+                principal_due: PRINCIPAL.into(),
+                annual_interest_rate: LOAN_INTEREST_RATE,
+                interest_paid: LEASE_START,
+            })
+        }
+
+        fn pay_interest(loan: &mut Loan<LppLoanLocal>, now: &Timestamp) {
+            let state = loan.state(now);
+            let interest_due = state.overdue.interest()
+                + state.overdue.margin()
+                + state.due_interest
+                + state.due_margin_interest;
+            assert_ne!(Coin::ZERO, interest_due);
+
+            let receipt = loan.repay(interest_due, now, &mut profit_stub()).unwrap();
+            assert_eq!(Coin::ZERO, receipt.principal_paid());
+        }
+    }
+
     #[cfg(test)]
     mod test_state {
         use finance::{duration::Duration, interest, percent::Percent, period::Period};
@@ -1199,6 +1357,11 @@ mod tests {
             self.loan.repay(by, repayment)
         }
 
+        fn increase(&mut self, by: &Timestamp, amount: LpnCoin) -> LpnCoin {
+            self.loan
+                .increase(by, amount, self.loan.annual_interest_rate)
+        }
+
         fn annual_interest_rate(&self) -> Percent {
             self.loan.annual_interest_rate
         }
@@ -0,0 +1,365 @@
+use std::mem;
+
+use serde::{Deserialize, Serialize};
+
+use currency::{Currency, CurrencyDTO, CurrencyDef, MemberOf};
+use finance::{
+    coin::{self, Amount, Coin, WithCoin, WithCoinResult},
+    fraction::Fraction,
+    price,
+    ratio::Rational,
+    zero::Zero,
+};
+use oracle_platform::{error::Error as OracleError, Oracle};
+
+use crate::{
+    api::{open::BasketLiquidation, LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies},
+    finance::{LpnCurrencies, LpnCurrency, Price},
+};
+
+/// Lease currencies a position holds on top of its lease currency
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct Basket {
+    legs: Vec<Leg>,
+    liquidation: BasketLiquidation,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Leg {
+    amount: LeaseCoin,
+    /// The leg valued in the lease currency as of the last store of the position
+    ///
+    /// Amounts are split over the basket at these values in order to come up with
+    /// the same parts on selling the basket and on closing the position afterwards.
+    value: LeaseCoin,
+}
+
+impl Basket {
+    /// Build a basket of not valued yet legs
+    pub(super) fn new<Legs>(
+        legs: Legs,
+        value_currency: CurrencyDTO<LeaseAssetCurrencies>,
+        liquidation: BasketLiquidation,
+    ) -> Self
+    where
+        Legs: IntoIterator<Item = LeaseCoin>,
+    {
+        Self {
+            legs: legs
+                .into_iter()
+                .map(|amount| Leg {
+                    amount,
+                    value: coin::from_amount_ticker(Amount::ZERO, value_currency),
+                })
+                .collect(),
+            liquidation,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.legs.is_empty()
+    }
+
+    pub fn coins(&self) -> impl Iterator<Item = &LeaseCoin> + '_ {
+        self.legs.iter().map(|leg| &leg.amount)
+    }
+
+    /// Split `asset`, valued in the lease currency, over the lease currency `amount` and the legs
+    ///
+    /// Return the non-zero coins to sell, the lease currency first.
+    pub(super) fn split_coins(&self, amount: &LeaseCoin, asset: Amount) -> Vec<LeaseCoin> {
+        let (part, leg_parts) = self.split(amount.amount(), asset);
+        std::iter::once(coin::from_amount_ticker(part, amount.currency()))
+            .chain(
+                self.legs.iter().zip(leg_parts).map(|(leg, leg_part)| {
+                    coin::from_amount_ticker(leg_part, leg.amount.currency())
+                }),
+            )
+            .filter(|coin| !coin.is_zero())
+            .collect()
+    }
+
+    /// Split `asset`, valued in the lease currency, over the lease currency `amount` and the legs
+    ///
+    /// Return the part of the lease currency and the parts of the legs in their order.
+    fn split(&self, amount: Amount, asset: Amount) -> (Amount, Vec<Amount>) {
+        match self.liquidation {
+            BasketLiquidation::Proportional => {
+                let total = self
+                    .legs
+                    .iter()
+                    .fold(amount, |total, leg| total + leg.value.amount());
+                if asset >= total {
+                    (amount, self.legs.iter().map(Leg::amount).collect())
+                } else {
+                    let part = Rational::new(asset, total);
+                    (
+                        Fraction::<Amount>::of(&part, amount),
+                        self.legs
+                            .iter()
+                            .map(|leg| Fraction::<Amount>::of(&part, leg.amount()))
+                            .collect(),
+                    )
+                }
+            }
+            BasketLiquidation::Priority => {
+                let mut left = asset;
+                let leg_parts = self
+                    .legs
+                    .iter()
+                    .map(|leg| {
+                        let value = leg.value.amount();
+                        if left >= value {
+                            left -= value;
+                            leg.amount()
+                        } else {
+                            let part =
+                                Fraction::<Amount>::of(&Rational::new(left, value), leg.amount());
+                            left = Amount::ZERO;
+                            part
+                        }
+                    })
+                    .collect();
+                (left.min(amount), leg_parts)
+            }
+        }
+    }
+}
+
+impl Leg {
+    fn amount(&self) -> Amount {
+        self.amount.amount()
+    }
+
+    /// Take `part` out of the leg
+    ///
+    /// Return the leg left, if any, along with its `value` in the lease currency.
+    fn close<Asset>(self, part: Amount, value: Coin<Asset>) -> Option<(Self, Coin<Asset>)> {
+        let amount = self.amount();
+        debug_assert!(part <= amount);
+
+        let left = amount - part;
+        (left != Amount::ZERO).then(|| {
+            let left_part = Rational::new(left, amount);
+            (
+                Self {
+                    amount: coin::from_amount_ticker(left, self.amount.currency()),
+                    value: coin::from_amount_ticker(
+                        Fraction::<Amount>::of(&left_part, self.value.amount()),
+                        self.value.currency(),
+                    ),
+                },
+                Fraction::<Amount>::of(&left_part, value),
+            )
+        })
+    }
+}
+
+/// A basket with its legs valued in the lease currency `Asset`
+///
+/// The values are as of the last store of the position until revalued at the current prices.
+#[cfg_attr(test, derive(Debug))]
+pub(super) struct ValuedBasket<Asset> {
+    basket: Basket,
+    values: Vec<Coin<Asset>>,
+}
+
+impl<Asset> ValuedBasket<Asset>
+where
+    Asset: Currency,
+{
+    pub fn new(basket: Basket) -> Self {
+        let values = basket
+            .legs
+            .iter()
+            .map(|leg| leg.value.amount().into())
+            .collect();
+        Self { basket, values }
+    }
+
+    pub fn basket(&self) -> &Basket {
+        &self.basket
+    }
+
+    pub fn value(&self) -> Coin<Asset> {
+        self.values
+            .iter()
+            .fold(Coin::ZERO, |total, value| total + *value)
+    }
+
+    pub fn revalue<OracleImpl>(&mut self, oracle: &OracleImpl) -> Result<(), OracleError>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        OracleImpl: Oracle<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    {
+        if self.basket.is_empty() {
+            Ok(())
+        } else {
+            oracle.price_of::<Asset>().and_then(|asset_in_lpns| {
+                self.basket
+                    .legs
+                    .iter()
+                    .map(|leg| {
+                        leg.amount.with_coin(LegValue {
+                            oracle,
+                            asset_in_lpns,
+                        })
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(|values| {
+                        self.values = values;
+                    })
+            })
+        }
+    }
+
+    /// Close the basket part of `asset`, valued in the lease currency
+    ///
+    /// Return the part left to close of the lease currency `amount`.
+    pub fn close(&mut self, amount: Coin<Asset>, asset: Coin<Asset>) -> Coin<Asset> {
+        let (part, leg_parts) = self.basket.split(amount.into(), asset.into());
+        let legs = mem::take(&mut self.basket.legs);
+        let values = mem::take(&mut self.values);
+        (self.basket.legs, self.values) = legs
+            .into_iter()
+            .zip(values)
+            .zip(leg_parts)
+            .filter_map(|((leg, value), leg_part)| leg.close(leg_part, value))
+            .unzip();
+        part.into()
+    }
+
+    /// Store the current values along with the basket
+    pub fn into_basket(self) -> Basket
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeaseAssetCurrencies>,
+    {
+        let Self { mut basket, values } = self;
+        basket
+            .legs
+            .iter_mut()
+            .zip(values)
+            .for_each(|(leg, value)| leg.value = value.into());
+        basket
+    }
+}
+
+struct LegValue<'oracle, OracleImpl, Asset> {
+    oracle: &'oracle OracleImpl,
+    asset_in_lpns: Price<Asset>,
+}
+
+impl<OracleImpl, Asset> WithCoin<LeaseAssetCurrencies> for LegValue<'_, OracleImpl, Asset>
+where
+    OracleImpl: Oracle<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    Asset: Currency,
+{
+    type Output = Coin<Asset>;
+
+    type Error = OracleError;
+
+    fn on<C>(self, leg: Coin<C>) -> WithCoinResult<LeaseAssetCurrencies, Self>
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
+    {
+        self.oracle.price_of::<C>().map(|leg_in_lpns| {
+            price::total(price::total(leg, leg_in_lpns), self.asset_in_lpns.inv())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use currencies::testing::{LeaseC1, LeaseC2, LeaseC3};
+    use currency::{CurrencyDef, MemberOf};
+    use finance::coin::{Amount, Coin};
+
+    use crate::api::{open::BasketLiquidation, LeaseAssetCurrencies, LeaseCoin};
+
+    use super::{Basket, ValuedBasket};
+
+    #[test]
+    fn proportional() {
+        let basket = basket(BasketLiquidation::Proportional);
+        assert_eq!(
+            vec![
+                lease_coin::<LeaseC1>(500),
+                lease_coin::<LeaseC2>(150),
+                lease_coin::<LeaseC3>(50)
+            ],
+            basket.split_coins(&lease_coin::<LeaseC1>(1000), 1000)
+        );
+    }
+
+    #[test]
+    fn priority() {
+        let basket = basket(BasketLiquidation::Priority);
+        assert_eq!(
+            vec![lease_coin::<LeaseC2>(300), lease_coin::<LeaseC3>(25)],
+            basket.split_coins(&lease_coin::<LeaseC1>(1000), 700)
+        );
+        assert_eq!(
+            vec![
+                lease_coin::<LeaseC1>(200),
+                lease_coin::<LeaseC2>(300),
+                lease_coin::<LeaseC3>(100)
+            ],
+            basket.split_coins(&lease_coin::<LeaseC1>(1000), 1200)
+        );
+    }
+
+    #[test]
+    fn close_proportional() {
+        let mut valued = ValuedBasket::<LeaseC1>::new(basket(BasketLiquidation::Proportional));
+        assert_eq!(Coin::new(1000), valued.value());
+
+        assert_eq!(
+            Coin::new(250),
+            valued.close(Coin::new(1000), Coin::new(500))
+        );
+        assert_eq!(Coin::new(750), valued.value());
+        assert_eq!(
+            vec![&lease_coin::<LeaseC2>(225), &lease_coin::<LeaseC3>(75)],
+            valued.basket().coins().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn close_priority() {
+        let mut valued = ValuedBasket::<LeaseC1>::new(basket(BasketLiquidation::Priority));
+
+        assert_eq!(
+            Coin::new(100),
+            valued.close(Coin::new(1000), Coin::new(1100))
+        );
+        assert_eq!(Coin::new(0), valued.value());
+        assert!(valued.basket().is_empty());
+    }
+
+    fn basket(liquidation: BasketLiquidation) -> Basket {
+        let mut basket = Basket::new(
+            [lease_coin::<LeaseC2>(300), lease_coin::<LeaseC3>(100)],
+            currency::dto::<LeaseC1, _>(),
+            liquidation,
+        );
+        // valued at 600 and 400 units of the lease currency
+        basket.legs[0].value = lease_coin::<LeaseC1>(600);
+        basket.legs[1].value = lease_coin::<LeaseC1>(400);
+        basket
+    }
+
+    fn lease_coin<C>(amount: Amount) -> LeaseCoin
+    where
+        C: CurrencyDef,
+        C::Group: MemberOf<LeaseAssetCurrencies>,
+    {
+        Coin::<C>::new(amount).into()
+    }
+}
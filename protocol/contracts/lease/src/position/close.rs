@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use crate::api::{
     position::{ChangeCmd, ClosePolicyChange},
     query::opened::ClosePolicy,
+    AssetPriceDTO,
 };
 
 use super::error::{Error as PositionError, Result as PositionResult};
@@ -20,20 +21,50 @@ use super::error::{Error as PositionError, Result as PositionResult};
 /// Not designed to be used as an input API component! Invariant checks are not done on deserialization!
 /// A position is subject to close if its LTV pertains to the right-open intervals (-inf., `take_profit`),
 /// or [`stop_loss`, +inf)
+///
+/// If the stop loss is trailing then `stop_loss` is kept at `trailing.distance` above
+/// the trailing high-water mark.
+///
+/// A trigger with a close part set closes only that part of the position and is consumed.
+/// Otherwise, the trigger closes the whole position.
+///
+/// A trigger set at a price is kept at the LTV equivalent to that price against the current debt.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(test, derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct Policy {
     take_profit: Option<Percent>,
     stop_loss: Option<Percent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trailing: Option<Trailing>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    take_profit_close: Option<Percent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stop_loss_close: Option<Percent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    take_profit_price: Option<AssetPriceDTO>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stop_loss_price: Option<AssetPriceDTO>,
+}
+
+/// Trailing stop loss
+///
+/// The `high_water_mark` is the lowest position LTV observed since the trailing stop loss
+/// has been set up. The stop loss ratchets down along with it and never goes up.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+struct Trailing {
+    distance: Percent,
+    high_water_mark: Percent,
 }
 
 /// A strategy triggered to close the position automatically
 ///
-/// If a recent price movement have the position's LTV trigger a full close as per the configured `Policy`
+/// If a recent price movement have the position's LTV trigger a close as per the configured `Policy`
 /// then the close strategy carries details.
 ///
-/// A full close of the position is triggered if:
+/// A close of the position, full or partial, is triggered if:
 /// - a Stop Loss is set up and a price decline have the position's LTV become higher than the specified percent, or
 /// - a Take Profit is set up and a price rise have the position's LTV become lower than the specified percent.
 #[derive(Debug, Eq, PartialEq)]
@@ -42,32 +73,186 @@ pub enum Strategy {
     TakeProfit(Percent),
 }
 
-impl From<ChangeCmd> for Option<Percent> {
-    fn from(cmd: ChangeCmd) -> Self {
-        match cmd {
-            ChangeCmd::Reset => None,
-            ChangeCmd::Set(new_value) => Some(new_value),
+impl Policy {
+    /// Apply the change command on the policy
+    ///
+    /// A trailing stop loss is set up at the current `lease_ltv` as a high-water mark.
+    /// Setting or resetting the stop loss cancels its trailing.
+    /// A trigger price is converted into the equivalent LTV with `price_ltv`.
+    pub fn change_policy<PriceLtv>(
+        self,
+        cmd: ClosePolicyChange,
+        lease_ltv: Percent,
+        price_ltv: PriceLtv,
+    ) -> PositionResult<Self>
+    where
+        PriceLtv: Fn(&AssetPriceDTO) -> PositionResult<Percent>,
+    {
+        match cmd.stop_loss {
+            None => Ok((
+                self.stop_loss,
+                self.stop_loss_close,
+                self.trailing,
+                self.stop_loss_price,
+            )),
+            Some(ChangeCmd::Reset) => Ok((None, None, None, None)),
+            Some(ChangeCmd::Set(stop_loss)) => Ok((Some(stop_loss), None, None, None)),
+            Some(ChangeCmd::SetPartial { ltv, close }) => Ok((Some(ltv), Some(close), None, None)),
+            Some(ChangeCmd::SetPrice(price)) => {
+                price_ltv(&price).map(|stop_loss| (Some(stop_loss), None, None, Some(price)))
+            }
+            Some(ChangeCmd::Trail(distance)) => Ok((
+                Some(lease_ltv + distance),
+                None,
+                Some(Trailing {
+                    distance,
+                    high_water_mark: lease_ltv,
+                }),
+                None,
+            )),
+        }
+        .and_then(|(stop_loss, stop_loss_close, trailing, stop_loss_price)| {
+            match cmd.take_profit {
+                None => Ok((
+                    self.take_profit,
+                    self.take_profit_close,
+                    self.take_profit_price,
+                )),
+                Some(ChangeCmd::Reset) => Ok((None, None, None)),
+                Some(ChangeCmd::Set(take_profit)) => Ok((Some(take_profit), None, None)),
+                Some(ChangeCmd::SetPartial { ltv, close }) => Ok((Some(ltv), Some(close), None)),
+                Some(ChangeCmd::SetPrice(price)) => {
+                    price_ltv(&price).map(|take_profit| (Some(take_profit), None, Some(price)))
+                }
+                Some(ChangeCmd::Trail(_)) => Err(PositionError::TrailingTakeProfit()),
+            }
+            .map(|(take_profit, take_profit_close, take_profit_price)| Self {
+                take_profit,
+                stop_loss,
+                trailing,
+                take_profit_close,
+                stop_loss_close,
+                take_profit_price,
+                stop_loss_price,
+            })
+        })
+        .and_then(Self::invariant_check)
+        .map(|this| this.trail(lease_ltv))
+    }
+
+    /// Determine the part of the position to close on the triggered `strategy`
+    ///
+    /// Return `None` if the whole position should be closed.
+    pub fn close_part(&self, strategy: &Strategy) -> Option<Percent> {
+        match strategy {
+            Strategy::StopLoss(_) => self.stop_loss_close,
+            Strategy::TakeProfit(_) => self.take_profit_close,
         }
     }
-}
 
-impl Policy {
-    pub fn change_policy(self, cmd: ClosePolicyChange) -> PositionResult<Self> {
-        Self {
-            stop_loss: cmd
-                .stop_loss
-                .map_or_else(|| self.stop_loss, Option::<Percent>::from),
-            take_profit: cmd
-                .take_profit
-                .map_or_else(|| self.take_profit, Option::<Percent>::from),
-        }
-        .invariant_check()
+    /// The position LTV below which the take profit is triggered, if set up
+    pub const fn take_profit(&self) -> Option<Percent> {
+        self.take_profit
+    }
+
+    /// The position LTV at or above which the stop loss is triggered, if set up
+    pub const fn stop_loss(&self) -> Option<Percent> {
+        self.stop_loss
+    }
+
+    /// Remove the triggered `strategy` from the policy
+    pub fn consume(self, strategy: &Strategy) -> Self {
+        match strategy {
+            Strategy::StopLoss(_) => Self {
+                stop_loss: None,
+                stop_loss_close: None,
+                trailing: None,
+                stop_loss_price: None,
+                ..self
+            },
+            Strategy::TakeProfit(_) => Self {
+                take_profit: None,
+                take_profit_close: None,
+                take_profit_price: None,
+                ..self
+            },
+        }
+    }
+
+    /// Move the triggers set at a price to the LTV equivalent to that price
+    ///
+    /// The LTVs are obtained with `price_ltv` against the current debt. A trigger that gets out of
+    /// range, at zero or at or above the `top_bound`, is dropped from the policy and returned
+    /// along with it. A trigger whose price cannot be converted is left at its LTV.
+    pub fn follow_prices<PriceLtv>(
+        self,
+        top_bound: Percent,
+        price_ltv: PriceLtv,
+    ) -> (Self, Vec<Strategy>)
+    where
+        PriceLtv: Fn(&AssetPriceDTO) -> PositionResult<Percent>,
+    {
+        let follow = |price: Option<AssetPriceDTO>, ltv: Option<Percent>| {
+            price.and_then(|price| price_ltv(&price).ok().or(ltv))
+        };
+        let in_range = |ltv: Percent| !ltv.is_zero() && ltv < top_bound;
+
+        [
+            follow(self.take_profit_price, self.take_profit).map(Strategy::TakeProfit),
+            follow(self.stop_loss_price, self.stop_loss).map(Strategy::StopLoss),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(
+            (self, vec![]),
+            |(policy, mut dropped), strategy| match strategy {
+                Strategy::TakeProfit(tp) if in_range(tp) => (
+                    Self {
+                        take_profit: Some(tp),
+                        ..policy
+                    },
+                    dropped,
+                ),
+                Strategy::StopLoss(sl) if in_range(sl) => (
+                    Self {
+                        stop_loss: Some(sl),
+                        ..policy
+                    },
+                    dropped,
+                ),
+                out_of_range => {
+                    let policy = policy.consume(&out_of_range);
+                    dropped.push(out_of_range);
+                    (policy, dropped)
+                }
+            },
+        )
+    }
+
+    /// Ratchet a trailing stop loss down if the `lease_ltv` is below its high-water mark
+    pub fn trail(self, lease_ltv: Percent) -> Self {
+        match self.trailing {
+            Some(trailing) if lease_ltv < trailing.high_water_mark => Self {
+                stop_loss: Some(lease_ltv + trailing.distance),
+                trailing: Some(Trailing {
+                    distance: trailing.distance,
+                    high_water_mark: lease_ltv,
+                }),
+                ..self
+            },
+            _ => self,
+        }
     }
 
     /// Determine the 'no-close' intersection with the provided range
     ///
     /// Pre: `self.may_trigger() == None` for an ltv contained in `during`.
     /// This implies that `during` is not a sub-range of any of the policy ranges.
+    /// Pre: `self.trail()` has been applied on the current LTV.
+    ///
+    /// The range a trailing distance below the stop loss high-water mark is excluded as well
+    /// so that the stop loss is ratcheted down once the LTV gets there. Smaller moves do not
+    /// raise an alarm, the stop loss gets ratcheted on the next check.
     pub fn no_close(
         &self,
         during: RightOpenRange<Percent, Ascending>,
@@ -77,8 +262,13 @@ impl Policy {
         let tp_cut = self
             .take_profit
             .map_or_else(|| during, |tp| during.cut_to(tp));
+        let trailing_cut = self
+            .trailing
+            .and_then(|trailing| trailing.high_water_mark.checked_sub(trailing.distance).ok())
+            .filter(|ratchet_at| ratchet_at != &Percent::ZERO)
+            .map_or_else(|| tp_cut, |ratchet_at| tp_cut.cut_to(ratchet_at));
         self.stop_loss
-            .map_or_else(|| tp_cut, |sl| tp_cut.cut_from(sl))
+            .map_or_else(|| trailing_cut, |sl| trailing_cut.cut_from(sl))
     }
 
     // TODO refactor to pass a 'current_ltv: Percent'
@@ -117,6 +307,23 @@ impl Policy {
             Some(sl) if sl == Percent::ZERO => Err(PositionError::zero_stop_loss()),
             _ => Ok(this),
         })
+        .and_then(|this| match this.trailing {
+            Some(trailing) if trailing.distance == Percent::ZERO => {
+                Err(PositionError::zero_trailing_stop_loss())
+            }
+            _ => Ok(this),
+        })
+        .and_then(|this| Self::close_part_check(this.take_profit_close).map(|()| this))
+        .and_then(|this| Self::close_part_check(this.stop_loss_close).map(|()| this))
+    }
+
+    fn close_part_check(may_close: Option<Percent>) -> PositionResult<()> {
+        match may_close {
+            Some(close) if close == Percent::ZERO || close >= Percent::HUNDRED => {
+                Err(PositionError::InvalidClosePart(close))
+            }
+            _ => Ok(()),
+        }
     }
 
     fn may_stop_loss<P>(&self, lease_asset: P, total_due: P) -> Option<Strategy>
@@ -141,6 +348,9 @@ impl Policy {
 impl From<Policy> for ClosePolicy {
     fn from(value: Policy) -> Self {
         Self::new(value.take_profit, value.stop_loss)
+            .with_stop_loss_trail(value.trailing.map(|trailing| trailing.distance))
+            .with_close_parts(value.take_profit_close, value.stop_loss_close)
+            .with_prices(value.take_profit_price, value.stop_loss_price)
     }
 }
 
@@ -159,6 +369,13 @@ impl Display for Strategy {
 
 #[cfg(test)]
 mod test {
+    use finance::percent::Percent;
+
+    use crate::{api::AssetPriceDTO, position::PositionResult};
+
+    fn no_price(_: &AssetPriceDTO) -> PositionResult<Percent> {
+        unreachable!("no trigger is set at a price")
+    }
 
     mod may_trigger {
         use finance::{coin::Amount, percent::Percent};
@@ -261,6 +478,11 @@ mod test {
             Policy {
                 stop_loss: sl,
                 take_profit: tp,
+                trailing: None,
+                take_profit_close: None,
+                stop_loss_close: None,
+                take_profit_price: None,
+                stop_loss_price: None,
             }
             .may_trigger(asset, due)
         }
@@ -271,35 +493,55 @@ mod test {
 
         use crate::{
             api::position::{ChangeCmd, ClosePolicyChange},
-            position::{close::Policy, error::Error as PositionError, CloseStrategy},
+            position::{
+                close::{Policy, Trailing},
+                error::Error as PositionError,
+                CloseStrategy,
+            },
         };
 
+        use super::no_price;
+
+        const LEASE_LTV: Percent = Percent::from_permille(500);
+
         #[test]
         fn none() {
             assert_eq!(
                 Ok(Policy::default()),
-                Policy::default().change_policy(ClosePolicyChange {
-                    stop_loss: None,
-                    take_profit: None,
-                },)
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        stop_loss: None,
+                        take_profit: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
             );
         }
 
         #[test]
         fn zero() {
             assert!(matches!(
-                Policy::default().change_policy(ClosePolicyChange {
-                    stop_loss: Some(ChangeCmd::Set(Percent::from_percent(24))),
-                    take_profit: Some(ChangeCmd::Set(Percent::ZERO)),
-                },),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        stop_loss: Some(ChangeCmd::Set(Percent::from_percent(24))),
+                        take_profit: Some(ChangeCmd::Set(Percent::ZERO)),
+                    },
+                    LEASE_LTV,
+                    no_price
+                ),
                 Err(PositionError::ZeroClosePolicy(_)),
             ));
 
             assert!(matches!(
-                Policy::default().change_policy(ClosePolicyChange {
-                    stop_loss: Some(ChangeCmd::Set(Percent::ZERO)),
-                    take_profit: Some(ChangeCmd::Set(Percent::from_percent(26))),
-                },),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        stop_loss: Some(ChangeCmd::Set(Percent::ZERO)),
+                        take_profit: Some(ChangeCmd::Set(Percent::from_percent(26))),
+                    },
+                    LEASE_LTV,
+                    no_price
+                ),
                 Err(PositionError::ZeroClosePolicy(_)),
             ));
         }
@@ -311,19 +553,32 @@ mod test {
                 Ok(Policy {
                     take_profit: None,
                     stop_loss: Some(sl),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
                 }),
-                Policy::default().change_policy(ClosePolicyChange {
-                    take_profit: None,
-                    stop_loss: Some(ChangeCmd::Set(sl)),
-                },)
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Set(sl)),
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
             );
 
             assert_eq!(
                 Ok(Policy::default()),
-                Policy::default().change_policy(ClosePolicyChange {
-                    stop_loss: Some(ChangeCmd::Reset),
-                    take_profit: None,
-                },)
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        stop_loss: Some(ChangeCmd::Reset),
+                        take_profit: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
             );
         }
 
@@ -334,19 +589,32 @@ mod test {
                 Ok(Policy {
                     take_profit: Some(tp),
                     stop_loss: None,
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
                 }),
-                Policy::default().change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Set(tp)),
-                    stop_loss: None,
-                },)
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(tp)),
+                        stop_loss: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
             );
 
             assert_eq!(
                 Ok(Policy::default()),
-                Policy::default().change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Reset),
-                    stop_loss: None,
-                },)
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Reset),
+                        stop_loss: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
             );
         }
 
@@ -356,30 +624,48 @@ mod test {
             let higher = Percent::from_percent(55);
 
             let may_p = Policy::default()
-                .change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Set(lower)),
-                    stop_loss: Some(ChangeCmd::Set(higher)),
-                })
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(lower)),
+                        stop_loss: Some(ChangeCmd::Set(higher)),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
                 .unwrap();
             assert_eq!(
                 Policy {
                     take_profit: Some(lower),
                     stop_loss: Some(higher),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
                 },
                 may_p
             );
             assert_eq!(None, may_p.may_trigger(Percent::HUNDRED, lower));
 
             let may_p_1 = may_p
-                .change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Reset),
-                    stop_loss: Some(ChangeCmd::Set(lower)),
-                })
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Reset),
+                        stop_loss: Some(ChangeCmd::Set(lower)),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
                 .unwrap();
             assert_eq!(
                 Policy {
                     take_profit: None,
                     stop_loss: Some(lower),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
                 },
                 may_p_1
             );
@@ -388,10 +674,14 @@ mod test {
                 may_p_1.may_trigger(Percent::HUNDRED, lower)
             );
 
-            let may_p_2 = may_p_1.change_policy(ClosePolicyChange {
-                take_profit: Some(ChangeCmd::Set(higher)),
-                stop_loss: None,
-            });
+            let may_p_2 = may_p_1.change_policy(
+                ClosePolicyChange {
+                    take_profit: Some(ChangeCmd::Set(higher)),
+                    stop_loss: None,
+                },
+                LEASE_LTV,
+                no_price,
+            );
             assert_eq!(
                 Some(CloseStrategy::StopLoss(lower)),
                 may_p_2.unwrap().may_trigger(Percent::HUNDRED, higher)
@@ -405,18 +695,26 @@ mod test {
             let lease_invalid1 = higher - Percent::from_permille(1);
 
             let p = Policy::default()
-                .change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Set(lower)),
-                    stop_loss: Some(ChangeCmd::Set(higher)),
-                })
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(lower)),
+                        stop_loss: Some(ChangeCmd::Set(higher)),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
                 .unwrap();
             assert_eq!(None, p.may_trigger(Percent::HUNDRED, lower));
             assert_eq!(
                 Some(CloseStrategy::TakeProfit(higher),),
-                p.change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Set(higher)),
-                    stop_loss: Some(ChangeCmd::Reset),
-                })
+                p.change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(higher)),
+                        stop_loss: Some(ChangeCmd::Reset),
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
                 .unwrap()
                 .may_trigger(Percent::HUNDRED, lease_invalid1)
             );
@@ -424,14 +722,609 @@ mod test {
             assert_eq!(
                 Some(CloseStrategy::StopLoss(lower)),
                 Policy::default()
-                    .change_policy(ClosePolicyChange {
-                        take_profit: None,
-                        stop_loss: Some(ChangeCmd::Set(lower)),
-                    },)
+                    .change_policy(
+                        ClosePolicyChange {
+                            take_profit: None,
+                            stop_loss: Some(ChangeCmd::Set(lower)),
+                        },
+                        LEASE_LTV,
+                        no_price
+                    )
                     .unwrap()
                     .may_trigger(Percent::HUNDRED, lower)
             );
         }
+
+        #[test]
+        fn stop_loss_trail() {
+            let distance = Percent::from_percent(10);
+            let p = Policy::default()
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Trail(distance)),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
+                .unwrap();
+            assert_eq!(
+                Policy {
+                    take_profit: None,
+                    stop_loss: Some(LEASE_LTV + distance),
+                    trailing: Some(Trailing {
+                        distance,
+                        high_water_mark: LEASE_LTV,
+                    }),
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                },
+                p
+            );
+
+            let sl = Percent::from_percent(65);
+            assert_eq!(
+                Ok(Policy {
+                    take_profit: None,
+                    stop_loss: Some(sl),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                }),
+                p.change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Set(sl)),
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+            assert_eq!(
+                Ok(Policy::default()),
+                p.change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Reset),
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+        }
+
+        #[test]
+        fn stop_loss_trail_kept_on_take_profit_change() {
+            let distance = Percent::from_percent(10);
+            let tp = Percent::from_percent(30);
+            let lease_ltv = LEASE_LTV - Percent::from_percent(5);
+            let p = Policy::default()
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Trail(distance)),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
+                .unwrap();
+            assert_eq!(
+                Ok(Policy {
+                    take_profit: Some(tp),
+                    stop_loss: Some(lease_ltv + distance),
+                    trailing: Some(Trailing {
+                        distance,
+                        high_water_mark: lease_ltv,
+                    }),
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                }),
+                p.change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(tp)),
+                        stop_loss: None,
+                    },
+                    lease_ltv,
+                    no_price
+                )
+            );
+        }
+
+        #[test]
+        fn zero_trail() {
+            assert_eq!(
+                Err(PositionError::zero_trailing_stop_loss()),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Trail(Percent::ZERO)),
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+        }
+
+        #[test]
+        fn take_profit_trail() {
+            assert_eq!(
+                Err(PositionError::TrailingTakeProfit()),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Trail(Percent::from_percent(10))),
+                        stop_loss: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+        }
+    }
+
+    mod partial {
+        use finance::percent::Percent;
+
+        use crate::{
+            api::position::{ChangeCmd, ClosePolicyChange},
+            position::{close::Policy, error::Error as PositionError, CloseStrategy},
+        };
+
+        use super::no_price;
+
+        const LEASE_LTV: Percent = Percent::from_permille(500);
+        const CLOSE: Percent = Percent::from_permille(300);
+
+        #[test]
+        fn set_partial() {
+            let tp = Percent::from_percent(40);
+            let sl = Percent::from_percent(60);
+            let p = Policy::default()
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::SetPartial {
+                            ltv: tp,
+                            close: CLOSE,
+                        }),
+                        stop_loss: Some(ChangeCmd::SetPartial {
+                            ltv: sl,
+                            close: CLOSE,
+                        }),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
+                .unwrap();
+            assert_eq!(
+                Policy {
+                    take_profit: Some(tp),
+                    stop_loss: Some(sl),
+                    trailing: None,
+                    take_profit_close: Some(CLOSE),
+                    stop_loss_close: Some(CLOSE),
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                },
+                p
+            );
+            assert_eq!(Some(CLOSE), p.close_part(&CloseStrategy::TakeProfit(tp)));
+            assert_eq!(Some(CLOSE), p.close_part(&CloseStrategy::StopLoss(sl)));
+
+            assert_eq!(
+                Ok(Policy {
+                    take_profit: Some(tp),
+                    stop_loss: Some(sl),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: Some(CLOSE),
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                }),
+                p.change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(tp)),
+                        stop_loss: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+        }
+
+        #[test]
+        fn invalid_part() {
+            assert_eq!(
+                Err(PositionError::InvalidClosePart(Percent::ZERO)),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::SetPartial {
+                            ltv: Percent::from_percent(60),
+                            close: Percent::ZERO
+                        }),
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+            assert_eq!(
+                Err(PositionError::InvalidClosePart(Percent::HUNDRED)),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::SetPartial {
+                            ltv: Percent::from_percent(40),
+                            close: Percent::HUNDRED
+                        }),
+                        stop_loss: None,
+                    },
+                    LEASE_LTV,
+                    no_price
+                )
+            );
+        }
+
+        #[test]
+        fn full_close() {
+            let tp = Percent::from_percent(40);
+            let p = Policy::default()
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(tp)),
+                        stop_loss: None,
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
+                .unwrap();
+            assert_eq!(None, p.close_part(&CloseStrategy::TakeProfit(tp)));
+        }
+
+        #[test]
+        fn consume() {
+            let tp = Percent::from_percent(40);
+            let sl = Percent::from_percent(60);
+            let p = Policy {
+                take_profit: Some(tp),
+                stop_loss: Some(sl),
+                trailing: None,
+                take_profit_close: Some(CLOSE),
+                stop_loss_close: Some(CLOSE),
+                take_profit_price: None,
+                stop_loss_price: None,
+            };
+            assert_eq!(
+                Policy {
+                    take_profit: None,
+                    stop_loss: Some(sl),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: Some(CLOSE),
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                },
+                p.consume(&CloseStrategy::TakeProfit(tp))
+            );
+            assert_eq!(
+                Policy {
+                    take_profit: Some(tp),
+                    stop_loss: None,
+                    trailing: None,
+                    take_profit_close: Some(CLOSE),
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                },
+                p.consume(&CloseStrategy::StopLoss(sl))
+            );
+        }
+    }
+
+    mod price {
+        use currencies::{testing::PaymentC4, Lpn};
+        use finance::{coin::Coin, percent::Percent, price};
+
+        use crate::{
+            api::{
+                position::{ChangeCmd, ClosePolicyChange},
+                AssetPriceDTO,
+            },
+            position::{
+                close::Policy, error::Error as PositionError, CloseStrategy, PositionResult,
+            },
+        };
+
+        const LEASE_LTV: Percent = Percent::from_permille(500);
+        const TP_LTV: Percent = Percent::from_permille(400);
+        const SL_LTV: Percent = Percent::from_permille(600);
+        const LIQUIDATION: Percent = Percent::from_permille(800);
+
+        #[test]
+        fn set_price() {
+            assert_eq!(
+                Ok(price_policy(TP_LTV, SL_LTV)),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::SetPrice(tp_price())),
+                        stop_loss: Some(ChangeCmd::SetPrice(sl_price())),
+                    },
+                    LEASE_LTV,
+                    price_ltv(TP_LTV, SL_LTV)
+                )
+            );
+        }
+
+        #[test]
+        fn set_ltv_cancels_price() {
+            assert_eq!(
+                Ok(Policy {
+                    take_profit: Some(TP_LTV),
+                    stop_loss: Some(LEASE_LTV + LEASE_LTV),
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: Some(tp_price()),
+                    stop_loss_price: None,
+                }),
+                price_policy(TP_LTV, SL_LTV).change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::Set(LEASE_LTV + LEASE_LTV)),
+                    },
+                    LEASE_LTV,
+                    price_ltv(TP_LTV, SL_LTV)
+                )
+            );
+        }
+
+        #[test]
+        fn follow_prices() {
+            let tp_ltv = TP_LTV - Percent::from_percent(5);
+            let sl_ltv = SL_LTV - Percent::from_percent(5);
+            assert_eq!(
+                (price_policy(tp_ltv, sl_ltv), vec![]),
+                price_policy(TP_LTV, SL_LTV).follow_prices(LIQUIDATION, price_ltv(tp_ltv, sl_ltv))
+            );
+
+            let no_price = Policy {
+                take_profit: Some(TP_LTV),
+                stop_loss: Some(SL_LTV),
+                trailing: None,
+                take_profit_close: None,
+                stop_loss_close: None,
+                take_profit_price: None,
+                stop_loss_price: None,
+            };
+            assert_eq!(
+                (no_price, vec![]),
+                no_price.follow_prices(LIQUIDATION, super::no_price)
+            );
+        }
+
+        #[test]
+        fn follow_prices_liquidation_conflict() {
+            assert_eq!(
+                (
+                    price_policy(TP_LTV, SL_LTV).consume(&CloseStrategy::StopLoss(SL_LTV)),
+                    vec![CloseStrategy::StopLoss(LIQUIDATION)]
+                ),
+                price_policy(TP_LTV, SL_LTV)
+                    .follow_prices(LIQUIDATION, price_ltv(TP_LTV, LIQUIDATION))
+            );
+            assert_eq!(
+                (
+                    Policy::default(),
+                    vec![
+                        CloseStrategy::TakeProfit(LIQUIDATION),
+                        CloseStrategy::StopLoss(LIQUIDATION + LEASE_LTV)
+                    ]
+                ),
+                price_policy(TP_LTV, SL_LTV)
+                    .follow_prices(LIQUIDATION, price_ltv(LIQUIDATION, LIQUIDATION + LEASE_LTV))
+            );
+        }
+
+        #[test]
+        fn follow_prices_zero() {
+            assert_eq!(
+                (
+                    price_policy(TP_LTV, SL_LTV).consume(&CloseStrategy::TakeProfit(TP_LTV)),
+                    vec![CloseStrategy::TakeProfit(Percent::ZERO)]
+                ),
+                price_policy(TP_LTV, SL_LTV)
+                    .follow_prices(LIQUIDATION, price_ltv(Percent::ZERO, SL_LTV))
+            );
+            assert_eq!(
+                (
+                    Policy::default(),
+                    vec![
+                        CloseStrategy::TakeProfit(Percent::ZERO),
+                        CloseStrategy::StopLoss(Percent::ZERO)
+                    ]
+                ),
+                price_policy(TP_LTV, SL_LTV)
+                    .follow_prices(LIQUIDATION, price_ltv(Percent::ZERO, Percent::ZERO))
+            );
+        }
+
+        #[test]
+        fn follow_prices_conversion_error() {
+            assert_eq!(
+                (price_policy(TP_LTV, SL_LTV), vec![]),
+                price_policy(TP_LTV, SL_LTV).follow_prices(LIQUIDATION, |_: &AssetPriceDTO| {
+                    Err(PositionError::zero_stop_loss())
+                })
+            );
+        }
+
+        #[test]
+        fn price_conversion_error() {
+            assert_eq!(
+                Err(PositionError::zero_stop_loss()),
+                Policy::default().change_policy(
+                    ClosePolicyChange {
+                        take_profit: None,
+                        stop_loss: Some(ChangeCmd::SetPrice(sl_price())),
+                    },
+                    LEASE_LTV,
+                    |_: &AssetPriceDTO| Err(PositionError::zero_stop_loss())
+                )
+            );
+        }
+
+        fn price_policy(tp_ltv: Percent, sl_ltv: Percent) -> Policy {
+            Policy {
+                take_profit: Some(tp_ltv),
+                stop_loss: Some(sl_ltv),
+                trailing: None,
+                take_profit_close: None,
+                stop_loss_close: None,
+                take_profit_price: Some(tp_price()),
+                stop_loss_price: Some(sl_price()),
+            }
+        }
+
+        fn price_ltv(
+            tp_ltv: Percent,
+            sl_ltv: Percent,
+        ) -> impl Fn(&AssetPriceDTO) -> PositionResult<Percent> {
+            move |price| {
+                if price == &tp_price() {
+                    Ok(tp_ltv)
+                } else {
+                    assert_eq!(&sl_price(), price);
+                    Ok(sl_ltv)
+                }
+            }
+        }
+
+        fn tp_price() -> AssetPriceDTO {
+            price::total_of(Coin::<PaymentC4>::new(2))
+                .is(Coin::<Lpn>::new(5))
+                .into()
+        }
+
+        fn sl_price() -> AssetPriceDTO {
+            price::total_of(Coin::<PaymentC4>::new(2))
+                .is(Coin::<Lpn>::new(3))
+                .into()
+        }
+    }
+
+    mod trail {
+        use finance::{percent::Percent, range::RightOpenRange};
+
+        use crate::position::{
+            close::{Policy, Trailing},
+            CloseStrategy,
+        };
+
+        const DISTANCE: Percent = Percent::from_permille(100);
+        const HIGH_WATER_MARK: Percent = Percent::from_permille(500);
+
+        #[test]
+        fn no_trailing() {
+            let p = Policy {
+                take_profit: None,
+                stop_loss: Some(HIGH_WATER_MARK),
+                trailing: None,
+                take_profit_close: None,
+                stop_loss_close: None,
+                take_profit_price: None,
+                stop_loss_price: None,
+            };
+            assert_eq!(p, p.trail(HIGH_WATER_MARK - DISTANCE));
+        }
+
+        #[test]
+        fn ratchet_down() {
+            let p = trailing_policy();
+            let lease_ltv = HIGH_WATER_MARK - Percent::from_permille(15);
+            let p_down = p.trail(lease_ltv);
+            assert_eq!(
+                Policy {
+                    take_profit: None,
+                    stop_loss: Some(lease_ltv + DISTANCE),
+                    trailing: Some(Trailing {
+                        distance: DISTANCE,
+                        high_water_mark: lease_ltv,
+                    }),
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
+                },
+                p_down
+            );
+            assert_eq!(None, p.may_trigger(Percent::HUNDRED, lease_ltv + DISTANCE));
+            assert_eq!(
+                Some(CloseStrategy::StopLoss(lease_ltv + DISTANCE)),
+                p_down.may_trigger(Percent::HUNDRED, lease_ltv + DISTANCE)
+            );
+        }
+
+        #[test]
+        fn never_up() {
+            let p = trailing_policy();
+            assert_eq!(p, p.trail(HIGH_WATER_MARK));
+            assert_eq!(p, p.trail(HIGH_WATER_MARK + Percent::from_permille(1)));
+            assert_eq!(p, p.trail(HIGH_WATER_MARK + DISTANCE));
+        }
+
+        #[test]
+        fn no_close() {
+            let range = RightOpenRange::up_to(Percent::from_percent(70));
+            assert_eq!(
+                range
+                    .cut_to(HIGH_WATER_MARK - DISTANCE)
+                    .cut_from(HIGH_WATER_MARK + DISTANCE),
+                trailing_policy().no_close(range)
+            );
+        }
+
+        #[test]
+        fn no_close_small_move() {
+            let range = RightOpenRange::up_to(Percent::from_percent(70));
+            let no_close = trailing_policy().no_close(range);
+
+            assert!(no_close.contains(&(HIGH_WATER_MARK - Percent::from_permille(1))));
+            assert!(no_close.contains(&(HIGH_WATER_MARK - DISTANCE + Percent::from_permille(1))));
+            assert!(!no_close.contains(&(HIGH_WATER_MARK - DISTANCE - Percent::from_permille(1))));
+        }
+
+        #[test]
+        fn no_close_distance_above_mark() {
+            let range = RightOpenRange::up_to(Percent::from_percent(70));
+            let distance = DISTANCE + DISTANCE;
+            let p = Policy {
+                stop_loss: Some(DISTANCE + distance),
+                trailing: Some(Trailing {
+                    distance,
+                    high_water_mark: DISTANCE,
+                }),
+                ..trailing_policy()
+            };
+            assert_eq!(range.cut_from(DISTANCE + distance), p.no_close(range));
+        }
+
+        fn trailing_policy() -> Policy {
+            Policy {
+                take_profit: None,
+                stop_loss: Some(HIGH_WATER_MARK + DISTANCE),
+                trailing: Some(Trailing {
+                    distance: DISTANCE,
+                    high_water_mark: HIGH_WATER_MARK,
+                }),
+                take_profit_close: None,
+                stop_loss_close: None,
+                take_profit_price: None,
+                stop_loss_price: None,
+            }
+        }
     }
 
     mod liquidation_check {
@@ -443,6 +1336,10 @@ mod test {
             position::{close::Policy, CloseStrategy},
         };
 
+        use super::no_price;
+
+        const LEASE_LTV: Percent = Percent::from_permille(500);
+
         #[test]
         fn check() {
             const DELTA: Percent = Percent::from_permille(1);
@@ -456,10 +1353,14 @@ mod test {
                 Policy::default().liquidation_check(Percent::from_percent(80))
             );
             let p = Policy::default()
-                .change_policy(ClosePolicyChange {
-                    take_profit: Some(ChangeCmd::Set(lower)),
-                    stop_loss: Some(ChangeCmd::Set(higher)),
-                })
+                .change_policy(
+                    ClosePolicyChange {
+                        take_profit: Some(ChangeCmd::Set(lower)),
+                        stop_loss: Some(ChangeCmd::Set(higher)),
+                    },
+                    LEASE_LTV,
+                    no_price,
+                )
                 .unwrap();
 
             assert_eq!(Ok(p), p.liquidation_check(liquidation));
@@ -590,6 +1491,11 @@ mod test {
                 Policy {
                     stop_loss: sl,
                     take_profit: tp,
+                    trailing: None,
+                    take_profit_close: None,
+                    stop_loss_close: None,
+                    take_profit_price: None,
+                    stop_loss_price: None,
                 }
                 .no_close(during)
             );
@@ -4,21 +4,24 @@ use currency::{CurrencyDef, MemberOf};
 use finance::{
     coin::{Coin, CoinDTO, WithCoin, WithCoinResult},
     error::Error as FinanceError,
+    percent::Percent,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    api::{LeaseAssetCurrencies, LeasePaymentCurrencies},
+    api::{open::BasketLiquidation, LeaseAssetCurrencies, LeaseCoin, LeasePaymentCurrencies},
     position::PositionError,
 };
 
-use super::{Position, Spec, SpecDTO};
+use super::{Basket, Position, Spec, SpecDTO};
 
 #[derive(Serialize, Deserialize, Clone)]
 #[cfg_attr(test, derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub struct PositionDTO {
     amount: CoinDTO<LeaseAssetCurrencies>,
+    #[serde(default, skip_serializing_if = "Basket::is_empty")]
+    basket: Basket,
     spec: SpecDTO,
 }
 
@@ -36,13 +39,55 @@ pub trait WithPosition {
 
 impl PositionDTO {
     pub(crate) fn new(amount: CoinDTO<LeaseAssetCurrencies>, spec: SpecDTO) -> Self {
-        Self { amount, spec }
+        Self {
+            amount,
+            basket: Basket::default(),
+            spec,
+        }
+    }
+
+    /// Put the `legs` in a basket held on top of the lease currency amount
+    pub(crate) fn with_basket<Legs>(self, legs: Legs, liquidation: BasketLiquidation) -> Self
+    where
+        Legs: IntoIterator<Item = LeaseCoin>,
+    {
+        Self {
+            basket: Basket::new(legs, self.amount.currency(), liquidation),
+            ..self
+        }
     }
 
     pub fn amount(&self) -> &CoinDTO<LeaseAssetCurrencies> {
         &self.amount
     }
 
+    pub fn basket(&self) -> &Basket {
+        &self.basket
+    }
+
+    /// All coins of the position, the lease currency first
+    pub fn coins(&self) -> Vec<LeaseCoin> {
+        std::iter::once(&self.amount)
+            .chain(self.basket.coins())
+            .copied()
+            .collect()
+    }
+
+    /// The coins to sell on closing `asset`, valued in the lease currency, out of the position
+    ///
+    /// The amount is split over the basket, if any, as per its liquidation policy.
+    pub fn sale_coins(&self, asset: &LeaseCoin) -> Vec<LeaseCoin> {
+        if self.basket.is_empty() {
+            vec![*asset]
+        } else {
+            self.basket.split_coins(&self.amount, asset.amount())
+        }
+    }
+
+    pub fn liquidation_max_slippage(&self) -> Option<Percent> {
+        self.spec.liquidation_max_slippage()
+    }
+
     pub fn with_position<V>(self, cmd: V) -> StdResult<V::Output, V::Error>
     where
         V: WithPosition,
@@ -51,6 +96,7 @@ impl PositionDTO {
     {
         struct WithAmount<V> {
             cmd: V,
+            basket: Basket,
             spec: SpecDTO,
         }
 
@@ -69,13 +115,14 @@ impl PositionDTO {
                 Asset::Group: MemberOf<LeaseAssetCurrencies> + MemberOf<LeasePaymentCurrencies>,
             {
                 Spec::try_from(self.spec)
-                    .map(|spec| Position::<Asset>::new(amount, spec))
+                    .map(|spec| Position::<Asset>::with_basket(amount, self.basket, spec))
                     .map_err(Into::into)
                     .and_then(|position| self.cmd.on(position))
             }
         }
         self.amount.with_coin(WithAmount {
             cmd,
+            basket: self.basket,
             spec: self.spec,
         })
     }
@@ -89,6 +136,7 @@ where
     fn from(value: Position<Asset>) -> Self {
         Self {
             amount: value.amount.into(),
+            basket: value.basket.into_basket(),
             spec: value.spec.into(),
         }
     }
@@ -24,6 +24,12 @@ pub enum Error {
     #[error("[Position] The position past this close should worth at least {0}")]
     PositionCloseAmountTooBig(LpnCoinDTO),
 
+    #[error("[Position] The target LTV '{target_ltv}' should be less than the current lease LTV '{lease_ltv}'!")]
+    CloseLtvNotBelowCurrent {
+        lease_ltv: Percent,
+        target_ltv: Percent,
+    },
+
     #[error("[Position] Invalid close policy! The current lease LTV '{lease_ltv}' would trigger '{strategy}'!")]
     TriggerClose {
         lease_ltv: Percent,
@@ -33,6 +39,12 @@ pub enum Error {
     #[error("[Position] The close policy '{0}' should not be zero!")]
     ZeroClosePolicy(&'static str),
 
+    #[error("[Position] Invalid close policy! The take profit cannot be trailing!")]
+    TrailingTakeProfit(),
+
+    #[error("[Position] Invalid close policy! The part of the position to close '{0}' should be above 0% and below 100%!")]
+    InvalidClosePart(Percent),
+
     #[error("[Position] Invalid close policy! The new strategy '{strategy}' is not less than the max lease liability LTV '{top_bound}'!")]
     LiquidationConflict {
         strategy: CloseStrategy,
@@ -48,6 +60,13 @@ impl Error {
         }
     }
 
+    pub fn close_ltv_not_below_current(lease_ltv: Percent, target_ltv: Percent) -> Self {
+        Self::CloseLtvNotBelowCurrent {
+            lease_ltv,
+            target_ltv,
+        }
+    }
+
     pub fn zero_take_profit() -> Self {
         Self::ZeroClosePolicy("take profit")
     }
@@ -56,6 +75,10 @@ impl Error {
         Self::ZeroClosePolicy("stop loss")
     }
 
+    pub fn zero_trailing_stop_loss() -> Self {
+        Self::ZeroClosePolicy("trailing stop loss")
+    }
+
     pub fn liquidation_conflict(liquidation_ltv: Percent, strategy: CloseStrategy) -> Self {
         Self::LiquidationConflict {
             top_bound: liquidation_ltv,
@@ -10,6 +10,11 @@ pub trait Due {
     /// The position stays open until there is some due amount.
     fn total_due(&self) -> LpnCoin;
 
+    /// The total due interest
+    ///
+    /// Includes the due and overdue interest and margin interest.
+    fn total_due_interest(&self) -> LpnCoin;
+
     /// When and how much due interest to collect
     ///
     /// Usually, the interest is accrued as per a fixed interest rate.
@@ -0,0 +1,17 @@
+use crate::finance::Price;
+
+/// The lease asset prices at which the position LTV reaches the liability zone
+/// boundaries and the close policy triggers, given the current debt
+#[derive(Clone, Copy)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct PriceLevels<Asset>
+where
+    Asset: 'static,
+{
+    pub first_liq_warn: Price<Asset>,
+    pub second_liq_warn: Price<Asset>,
+    pub third_liq_warn: Price<Asset>,
+    pub liquidation: Price<Asset>,
+    pub take_profit: Option<Price<Asset>>,
+    pub stop_loss: Option<Price<Asset>>,
+}
@@ -1,23 +1,35 @@
 use currency::{Currency, CurrencyDef, MemberOf};
-use finance::{coin::Coin, duration::Duration};
+use finance::{coin::Coin, duration::Duration, fraction::Fraction, percent::Percent};
+use oracle_platform::{error::Error as OracleError, Oracle};
 
 use crate::{
-    api::{position::ClosePolicyChange, query::opened::ClosePolicy, LeasePaymentCurrencies},
-    finance::Price,
+    api::{
+        open::LeaseKind, position::ClosePolicyChange, query::opened::ClosePolicy, LeaseCoin,
+        LeasePaymentCurrencies,
+    },
+    finance::{LpnCoin, LpnCurrencies, LpnCurrency, Price},
 };
 
+use self::basket::ValuedBasket;
+
+pub use basket::Basket;
 pub use close::Strategy as CloseStrategy;
 pub use dto::{PositionDTO, WithPosition, WithPositionResult};
 pub use error::{Error as PositionError, Result as PositionResult};
 pub use interest::{Due as DueTrait, OverdueCollection};
+pub use levels::PriceLevels;
+pub use overdue::OverdueWarning;
 pub use spec::{Spec, SpecDTO};
 pub(crate) use status::{Cause, Debt, Liquidation};
 pub(crate) use steady::Steadiness;
 
+mod basket;
 mod close;
 mod dto;
 mod error;
 mod interest;
+mod levels;
+mod overdue;
 mod spec;
 mod status;
 mod steady;
@@ -25,6 +37,7 @@ mod steady;
 #[cfg_attr(test, derive(Debug))]
 pub struct Position<Asset> {
     amount: Coin<Asset>,
+    basket: ValuedBasket<Asset>,
     spec: Spec,
 }
 
@@ -33,21 +46,87 @@ where
     Asset: Currency,
 {
     pub fn new(amount: Coin<Asset>, spec: Spec) -> Self {
+        Self::with_basket(amount, Basket::default(), spec)
+    }
+
+    fn with_basket(amount: Coin<Asset>, basket: Basket, spec: Spec) -> Self {
         debug_assert!(!amount.is_zero(), "The amount should be positive");
-        Self { amount, spec }
+        Self {
+            amount,
+            basket: ValuedBasket::new(basket),
+            spec,
+        }
     }
 
+    /// The amount of the lease currency
     pub(crate) fn amount(&self) -> Coin<Asset> {
         self.amount
     }
 
+    /// The direction of the position, refer to [`LeaseKind`]
+    pub(crate) fn kind(&self) -> LeaseKind {
+        self.spec.kind()
+    }
+
+    /// The amounts of the basket currencies, if any
+    pub(crate) fn basket(&self) -> impl Iterator<Item = &LeaseCoin> + '_ {
+        self.basket.basket().coins()
+    }
+
+    /// The whole position valued in the lease currency
+    ///
+    /// The basket is valued at the prices of its last revaluation.
+    pub(crate) fn value(&self) -> Coin<Asset> {
+        self.amount + self.basket.value()
+    }
+
+    /// Value the basket, if any, at the current prices
+    ///
+    /// The position LTV is calculated on the summed value of the lease currency and
+    /// the basket, so the latter is expected to be revalued before any checks.
+    pub fn revalue_basket<OracleImpl>(&mut self, oracle: &OracleImpl) -> Result<(), OracleError>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        OracleImpl: Oracle<LeasePaymentCurrencies, QuoteC = LpnCurrency, QuoteG = LpnCurrencies>,
+    {
+        self.basket.revalue(oracle)
+    }
+
+    pub fn increase(&mut self, asset: Coin<Asset>) {
+        debug_assert!(!asset.is_zero(), "The increment should be positive");
+
+        self.amount += asset
+    }
+
+    /// Calculate the maximum extra amount that may be borrowed on the position increase
+    ///
+    /// Refer to `Spec::max_increase_borrow`.
+    pub fn max_increase_borrow<Due>(
+        &self,
+        payment: LpnCoin,
+        due: &Due,
+        principal_due: LpnCoin,
+        asset_in_lpns: Price<Asset>,
+    ) -> LpnCoin
+    where
+        Due: DueTrait,
+    {
+        self.spec
+            .max_increase_borrow(self.value(), payment, due, principal_due, asset_in_lpns)
+    }
+
+    /// Close `asset`, valued in the lease currency, out of the position
+    ///
+    /// The amount is split over the basket, if any, as per its liquidation policy.
     pub fn close(&mut self, asset: Coin<Asset>) {
         debug_assert!(
-            asset < self.amount,
+            asset < self.value(),
             "Liquidated asset {asset} should be less than the available {0}",
-            self.amount
+            self.value()
         );
 
+        let asset = self.basket.close(self.amount, asset);
         self.amount -= asset
     }
 
@@ -61,6 +140,14 @@ where
         self.spec.overdue_collection_in(due)
     }
 
+    /// Determine the overdue interest warning, if any, the position is subject to
+    pub fn overdue_warning<Due>(&self, due: &Due) -> Option<OverdueWarning>
+    where
+        Due: DueTrait,
+    {
+        self.spec.overdue_warning(due)
+    }
+
     /// Determine the debt status of a position
     ///
     /// Pre: `self.check_close(...) == None`
@@ -68,7 +155,28 @@ where
     where
         Due: DueTrait,
     {
-        self.spec.debt(self.amount, due, asset_in_lpns)
+        self.spec.debt(self.value(), due, asset_in_lpns)
+    }
+
+    /// Determine the position LTV against the debt at the lease asset price
+    pub fn ltv<Due>(&self, due: &Due, asset_in_lpns: Price<Asset>) -> Percent
+    where
+        Due: DueTrait,
+    {
+        Spec::ltv_at(self.value(), due, asset_in_lpns)
+    }
+
+    /// Determine the lease asset prices at which the position LTV reaches the liability zone
+    /// boundaries and the close policy triggers
+    ///
+    /// Return `None` if there is no debt.
+    pub fn price_levels<Due>(&self, due: &Due) -> Option<PriceLevels<Asset>>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        Due: DueTrait,
+    {
+        self.spec.price_levels(self.value(), due)
     }
 
     /// Export the close policy state for querying purposes
@@ -83,7 +191,7 @@ where
     where
         Due: DueTrait,
     {
-        self.spec.check_close(self.amount, due, asset_in_lpns)
+        self.spec.check_close(self.value(), due, asset_in_lpns)
     }
 
     pub fn change_close_policy<Due>(
@@ -93,16 +201,52 @@ where
         asset_in_lpns: Price<Asset>,
     ) -> PositionResult<()>
     where
-        Asset: Currency,
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
         Due: DueTrait,
     {
         self.spec
-            .change_close_policy(cmd, self.amount, due, asset_in_lpns)
+            .change_close_policy(cmd, self.value(), due, asset_in_lpns)
             .map(|spec| {
                 self.spec = spec;
             })
     }
 
+    /// Bring the close policy up to date with the current position
+    ///
+    /// Expected to be called before any check of the close policy.
+    /// Return the triggers dropped since their LTV has got out of range.
+    pub fn update_close_policy<Due>(
+        &mut self,
+        due: &Due,
+        asset_in_lpns: Price<Asset>,
+    ) -> Vec<CloseStrategy>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        Due: DueTrait,
+    {
+        let (spec, dropped) = self
+            .spec
+            .update_close_policy(self.value(), due, asset_in_lpns);
+        self.spec = spec;
+        dropped
+    }
+
+    /// Determine the part of the position to close on the triggered `strategy`
+    ///
+    /// Return `None` if the whole position should be closed.
+    pub fn close_part(&self, strategy: &CloseStrategy) -> Option<Coin<Asset>> {
+        self.spec
+            .close_part(strategy)
+            .map(|part| part.of(self.value()))
+    }
+
+    /// Remove the triggered `strategy` from the close policy
+    pub fn consume_close_trigger(&mut self, strategy: &CloseStrategy) {
+        self.spec = self.spec.consume_close_trigger(strategy);
+    }
+
     /// Check if the amount can be used for repayment.
     /// Return `error::PositionError::InsufficientTransactionAmount` when the payment amount
     /// is less than the minimum transaction amount.
@@ -133,6 +277,23 @@ where
         asset_in_lpns: Price<Asset>,
     ) -> PositionResult<()> {
         self.spec
-            .validate_close_amount(self.amount, close_amount, asset_in_lpns)
+            .validate_close_amount(self.value(), close_amount, asset_in_lpns)
+    }
+
+    /// Calculate the amount to close in order to bring the position LTV down to `ltv`
+    ///
+    /// Return `error::PositionError::CloseLtvNotBelowCurrent` when `ltv` is not less than
+    /// the current position LTV.
+    pub fn close_amount_to_ltv<Due>(
+        &self,
+        due: &Due,
+        asset_in_lpns: Price<Asset>,
+        ltv: Percent,
+    ) -> PositionResult<Coin<Asset>>
+    where
+        Due: DueTrait,
+    {
+        self.spec
+            .close_amount_to_ltv(self.value(), due, asset_in_lpns, ltv)
     }
 }
@@ -0,0 +1,73 @@
+use finance::duration::Duration;
+
+use crate::{api::open::OverdueWarnings, finance::LpnCoin};
+
+/// A warning of an approaching overdue interest collection
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct OverdueWarning {
+    /// The ordinal of the reached level, starting from 1
+    pub level: u8,
+    /// How much time is left until the collection
+    pub collect_in: Duration,
+    /// The due interest that is about to be collected
+    pub amount: LpnCoin,
+}
+
+/// Determine the most severe warning level reached `collect_in` ahead of the collection
+pub(super) fn level(warnings: &OverdueWarnings, collect_in: Duration) -> Option<u8> {
+    [
+        (3, warnings.third),
+        (2, warnings.second),
+        (1, warnings.first),
+    ]
+    .into_iter()
+    .find_map(|(level, ahead)| (collect_in <= ahead).then_some(level))
+}
+
+/// Determine in how much time the next warning level will be reached
+pub(super) fn next_in(warnings: &OverdueWarnings, collect_in: Duration) -> Option<Duration> {
+    [warnings.first, warnings.second, warnings.third]
+        .into_iter()
+        .find(|ahead| *ahead < collect_in)
+        .map(|ahead| collect_in - ahead)
+}
+
+#[cfg(test)]
+mod test {
+    use finance::duration::Duration;
+
+    use crate::api::open::OverdueWarnings;
+
+    const WARNINGS: OverdueWarnings = OverdueWarnings {
+        first: Duration::from_days(3),
+        second: Duration::from_days(1),
+        third: Duration::from_hours(2),
+    };
+
+    #[test]
+    fn level() {
+        assert_eq!(None, super::level(&WARNINGS, Duration::from_days(4)));
+        assert_eq!(Some(1), super::level(&WARNINGS, Duration::from_days(3)));
+        assert_eq!(Some(1), super::level(&WARNINGS, Duration::from_hours(25)));
+        assert_eq!(Some(2), super::level(&WARNINGS, Duration::from_hours(3)));
+        assert_eq!(Some(3), super::level(&WARNINGS, Duration::from_hours(1)));
+    }
+
+    #[test]
+    fn next_in() {
+        assert_eq!(
+            Some(Duration::from_days(1)),
+            super::next_in(&WARNINGS, Duration::from_days(4))
+        );
+        assert_eq!(
+            Some(Duration::from_days(2)),
+            super::next_in(&WARNINGS, Duration::from_days(3))
+        );
+        assert_eq!(
+            Some(Duration::from_hours(1)),
+            super::next_in(&WARNINGS, Duration::from_hours(3))
+        );
+        assert_eq!(None, super::next_in(&WARNINGS, Duration::from_hours(2)));
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use finance::percent::Percent;
+
 use crate::{
     api::open::PositionSpecDTO,
     position::{close::Policy as ClosePolicy, PositionError, PositionResult, Spec},
@@ -36,6 +38,10 @@ impl SpecDTO {
     fn new(r#const: PositionSpecDTO, close: ClosePolicy) -> Self {
         Self { r#const, close }
     }
+
+    pub fn liquidation_max_slippage(&self) -> Option<Percent> {
+        self.r#const.liquidation_max_slippage
+    }
 }
 
 impl From<PositionSpecDTO> for SpecDTO {
@@ -59,6 +65,9 @@ impl From<Spec> for SpecDTO {
                 spec.liability,
                 spec.min_asset.into(),
                 spec.min_transaction.into(),
+                spec.overdue_warnings,
+                spec.liquidation_max_slippage,
+                spec.kind,
             ),
             spec.close,
         )
@@ -78,6 +87,9 @@ impl TryFrom<SpecDTO> for Spec {
                     .try_into()
                     .map(|min_transaction| {
                         Self::new(dto.r#const.liability, dto.close, min_asset, min_transaction)
+                            .with_overdue_warnings(dto.r#const.overdue_warnings)
+                            .with_liquidation_max_slippage(dto.r#const.liquidation_max_slippage)
+                            .with_kind(dto.r#const.kind)
                     })
             })
             .map_err(Into::into)
@@ -7,22 +7,26 @@ use finance::{
     fraction::Fraction,
     fractionable::Fractionable,
     liability::Liability,
-    percent::Percent,
+    percent::{Percent, Units},
     price::{self},
+    ratio::Rational,
     zero::Zero,
 };
 
 use crate::{
     api::{
-        position::ClosePolicyChange, query::opened::ClosePolicy as APIClosePolicy,
-        LeasePaymentCurrencies,
+        open::{LeaseKind, OverdueWarnings},
+        position::ClosePolicyChange,
+        query::opened::ClosePolicy as APIClosePolicy,
+        AssetPriceDTO, LeasePaymentCurrencies,
     },
     finance::{LpnCoin, Price},
 };
 
 use super::{
-    close::Policy as ClosePolicy, interest::OverdueCollection, steady::Steadiness, Cause,
-    CloseStrategy, Debt, DueTrait, Liquidation, PositionError, PositionResult,
+    close::Policy as ClosePolicy, interest::OverdueCollection, overdue, steady::Steadiness, Cause,
+    CloseStrategy, Debt, DueTrait, Liquidation, OverdueWarning, PositionError, PositionResult,
+    PriceLevels,
 };
 pub use dto::SpecDTO;
 
@@ -38,6 +42,9 @@ pub struct Spec {
     close: ClosePolicy,
     min_asset: LpnCoin,
     min_transaction: LpnCoin,
+    overdue_warnings: Option<OverdueWarnings>,
+    liquidation_max_slippage: Option<Percent>,
+    kind: LeaseKind,
 }
 
 impl Spec {
@@ -58,9 +65,30 @@ impl Spec {
             close,
             min_asset,
             min_transaction,
+            overdue_warnings: None,
+            liquidation_max_slippage: None,
+            kind: LeaseKind::Long,
         }
     }
 
+    pub fn with_overdue_warnings(self, overdue_warnings: Option<OverdueWarnings>) -> Self {
+        Self {
+            overdue_warnings,
+            ..self
+        }
+    }
+
+    pub fn with_liquidation_max_slippage(self, liquidation_max_slippage: Option<Percent>) -> Self {
+        Self {
+            liquidation_max_slippage,
+            ..self
+        }
+    }
+
+    pub fn with_kind(self, kind: LeaseKind) -> Self {
+        Self { kind, ..self }
+    }
+
     #[cfg(test)]
     pub fn no_close(liability: Liability, min_asset: LpnCoin, min_transaction: LpnCoin) -> Self {
         Self::new(
@@ -71,6 +99,10 @@ impl Spec {
         )
     }
 
+    pub const fn kind(&self) -> LeaseKind {
+        self.kind
+    }
+
     pub fn close_policy(&self) -> APIClosePolicy {
         self.close.into()
     }
@@ -83,13 +115,16 @@ impl Spec {
         asset_in_lpns: Price<Asset>,
     ) -> PositionResult<Self>
     where
-        Asset: Currency,
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
         Due: DueTrait,
     {
         let total_due = Self::to_assets(due.total_due(), asset_in_lpns);
 
         self.close
-            .change_policy(cmd)
+            .change_policy(cmd, Self::ltv(total_due, asset), |price| {
+                Self::price_ltv(asset, due, price)
+            })
             .and_then(|close_policy| close_policy.liquidation_check(self.liability.max()))
             .and_then(|close_policy| {
                 close_policy.may_trigger(asset, total_due).map_or_else(
@@ -102,16 +137,57 @@ impl Spec {
                     },
                 )
             })
-            .map(|close_policy| {
-                Self::new(
-                    self.liability,
-                    close_policy,
-                    self.min_asset,
-                    self.min_transaction,
-                )
+            .map(|close_policy| Self {
+                close: close_policy,
+                ..self
             })
     }
 
+    /// Bring the close policy up to date with the current position
+    ///
+    /// The triggers set at a price are moved to the equivalent LTV against the current debt,
+    /// and a trailing stop loss is ratcheted down if the position LTV has got below its high-water mark.
+    /// Return the triggers dropped since their LTV has got out of range along with the updated spec.
+    pub fn update_close_policy<Asset, Due>(
+        self,
+        asset: Coin<Asset>,
+        due: &Due,
+        asset_in_lpns: Price<Asset>,
+    ) -> (Self, Vec<CloseStrategy>)
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        Due: DueTrait,
+    {
+        let total_due = Self::to_assets(due.total_due(), asset_in_lpns);
+
+        let (close, dropped) = self.close.follow_prices(self.liability.max(), |price| {
+            Self::price_ltv(asset, due, price)
+        });
+        (
+            Self {
+                close: close.trail(Self::ltv(total_due, asset)),
+                ..self
+            },
+            dropped,
+        )
+    }
+
+    /// Determine the part of the position to close on the triggered `strategy`
+    ///
+    /// Return `None` if the whole position should be closed.
+    pub fn close_part(&self, strategy: &CloseStrategy) -> Option<Percent> {
+        self.close.close_part(strategy)
+    }
+
+    /// Remove the triggered `strategy` from the close policy
+    pub fn consume_close_trigger(self, strategy: &CloseStrategy) -> Self {
+        Self {
+            close: self.close.consume(strategy),
+            ..self
+        }
+    }
+
     /// Calculate the borrow amount.
     /// Return 'error::PositionError::InsufficientTransactionAmount' when either the downpayment
     /// or the borrow amount is less than the minimum transaction amount.
@@ -144,6 +220,31 @@ impl Spec {
         }
     }
 
+    /// Calculate the maximum extra amount that may be borrowed on a position increase
+    ///
+    /// The due interests are paid off the borrowed amount and the `payment` together with
+    /// the rest of it go to the position. The result keeps the position LTV at or below
+    /// the initial liability LTV.
+    pub fn max_increase_borrow<Asset, Due>(
+        &self,
+        asset: Coin<Asset>,
+        payment: LpnCoin,
+        due: &Due,
+        principal_due: LpnCoin,
+        asset_in_lpns: Price<Asset>,
+    ) -> LpnCoin
+    where
+        Asset: Currency,
+        Due: DueTrait,
+    {
+        debug_assert!(principal_due <= due.total_due());
+        let equity = (price::total(asset, asset_in_lpns) + payment).saturating_sub(due.total_due());
+
+        self.liability
+            .init_borrow_amount(equity, None)
+            .saturating_sub(principal_due)
+    }
+
     pub fn overdue_collection_in<Due>(&self, due: &Due) -> Duration
     where
         Due: DueTrait,
@@ -151,6 +252,28 @@ impl Spec {
         self.overdue_collection(due).start_in()
     }
 
+    /// Determine the overdue interest warning, if any, the position is subject to
+    ///
+    /// Return `None` if there are no warning levels specified, none of them has been reached yet,
+    /// or the overdue interest is already collectable.
+    pub fn overdue_warning<Due>(&self, due: &Due) -> Option<OverdueWarning>
+    where
+        Due: DueTrait,
+    {
+        self.overdue_warnings.and_then(|warnings| {
+            let collect_in = self.overdue_collection_in(due);
+            if collect_in == Duration::default() {
+                None
+            } else {
+                overdue::level(&warnings, collect_in).map(|level| OverdueWarning {
+                    level,
+                    collect_in,
+                    amount: due.total_due_interest(),
+                })
+            }
+        })
+    }
+
     /// Determine the debt status of a position
     ///
     /// Pre: `self.check_close(...) == None`
@@ -185,6 +308,52 @@ impl Spec {
             })
     }
 
+    /// Determine the position LTV against the debt at the lease asset price
+    pub fn ltv_at<Asset, Due>(asset: Coin<Asset>, due: &Due, asset_in_lpns: Price<Asset>) -> Percent
+    where
+        Asset: Currency,
+        Due: DueTrait,
+    {
+        Self::ltv(Self::to_assets(due.total_due(), asset_in_lpns), asset)
+    }
+
+    /// Determine the lease asset prices at which the position LTV reaches the liability zone
+    /// boundaries and the close policy triggers
+    ///
+    /// The triggers set at a price are brought up to date with the debt beforehand.
+    /// Return `None` if there is no debt.
+    pub fn price_levels<Asset, Due>(
+        &self,
+        asset: Coin<Asset>,
+        due: &Due,
+    ) -> Option<PriceLevels<Asset>>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        Due: DueTrait,
+    {
+        let total_due = due.total_due();
+        if total_due.is_zero() {
+            None
+        } else {
+            let ltv_to_price = move |ltv: Percent| {
+                debug_assert!(!ltv.is_zero());
+                price::total_of(ltv.of(asset)).is(total_due)
+            };
+            let (close, _dropped) = self.close.follow_prices(self.liability.max(), |price| {
+                Self::price_ltv(asset, due, price)
+            });
+            Some(PriceLevels {
+                first_liq_warn: ltv_to_price(self.liability.first_liq_warn()),
+                second_liq_warn: ltv_to_price(self.liability.second_liq_warn()),
+                third_liq_warn: ltv_to_price(self.liability.third_liq_warn()),
+                liquidation: ltv_to_price(self.liability.max()),
+                take_profit: close.take_profit().map(ltv_to_price),
+                stop_loss: close.stop_loss().map(ltv_to_price),
+            })
+        }
+    }
+
     /// Check if the position is subject of a full close due to trigerred close policy
     pub fn check_close<Asset, Due>(
         &self,
@@ -253,6 +422,41 @@ impl Spec {
         }
     }
 
+    /// Calculate the amount to close in order to bring the position LTV down to `ltv`
+    ///
+    /// The close amount is assumed to be sold and the proceeds used to repay the loan.
+    /// Return `error::PositionError::CloseLtvNotBelowCurrent` when `ltv` is not less than
+    /// the current position LTV.
+    pub fn close_amount_to_ltv<Asset, Due>(
+        &self,
+        asset: Coin<Asset>,
+        due: &Due,
+        asset_in_lpns: Price<Asset>,
+        ltv: Percent,
+    ) -> PositionResult<Coin<Asset>>
+    where
+        Asset: Currency,
+        Due: DueTrait,
+    {
+        let due_assets = Self::to_assets(due.total_due(), asset_in_lpns);
+        let target_due_assets = ltv.of(asset);
+
+        if ltv >= Percent::HUNDRED || target_due_assets >= due_assets {
+            Err(PositionError::close_ltv_not_below_current(
+                Percent::from_ratio(due_assets, asset),
+                ltv,
+            ))
+        } else {
+            // from 'due - close = ltv% of (asset - close)' follows
+            // close = 100% / (100% - ltv%) of (due - ltv% of asset)
+            let multiplier = Rational::new(Percent::HUNDRED, Percent::HUNDRED - ltv);
+            Ok(Fraction::<Units>::of(
+                &multiplier,
+                due_assets - target_due_assets,
+            ))
+        }
+    }
+
     fn valid_transaction<TransactionC>(
         &self,
         amount: Coin<TransactionC>,
@@ -366,7 +570,7 @@ impl Spec {
             Debt::Ok {
                 zone,
                 steadiness: Steadiness::new(
-                    self.overdue_collection_in(due)
+                    self.next_overdue_check_in(due)
                         .min(self.liability.recalculation_time()),
                     steady_within.invert(|ltv| {
                         debug_assert!(!ltv.is_zero());
@@ -377,6 +581,18 @@ impl Spec {
         }
     }
 
+    /// Determine in how much time either the overdue interest becomes collectable or
+    /// the next overdue warning level is reached, whichever comes first
+    fn next_overdue_check_in<Due>(&self, due: &Due) -> Duration
+    where
+        Due: DueTrait,
+    {
+        let collect_in = self.overdue_collection_in(due);
+        self.overdue_warnings
+            .and_then(|warnings| overdue::next_in(&warnings, collect_in))
+            .unwrap_or(collect_in)
+    }
+
     fn overdue_collection<Due>(&self, due: &Due) -> OverdueCollection
     where
         Due: DueTrait,
@@ -392,6 +608,26 @@ impl Spec {
         Percent::from_ratio(total_due, lease_asset)
     }
 
+    /// Convert a trigger `price` into the equivalent position LTV against the current debt
+    fn price_ltv<Asset, Due>(
+        asset: Coin<Asset>,
+        due: &Due,
+        price: &AssetPriceDTO,
+    ) -> PositionResult<Percent>
+    where
+        Asset: CurrencyDef,
+        Asset::Group: MemberOf<LeasePaymentCurrencies>,
+        Due: DueTrait,
+    {
+        Coin::<Asset>::try_from(*price.base())
+            .and_then(|amount| {
+                LpnCoin::try_from(*price.quote())
+                    .map(|amount_quote| price::total_of(amount).is(amount_quote))
+            })
+            .map_err(Into::into)
+            .map(|trigger_price| Self::ltv(Self::to_assets(due.total_due(), trigger_price), asset))
+    }
+
     fn to_assets<Asset>(lpn_coin: LpnCoin, asset_in_lpns: Price<Asset>) -> Coin<Asset>
     where
         Asset: Currency,
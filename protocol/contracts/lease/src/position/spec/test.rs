@@ -1,4 +1,4 @@
-use currencies::{testing::PaymentC3, Lpn};
+use currencies::{testing::PaymentC4, Lpn};
 use finance::{
     coin::Coin,
     duration::Duration,
@@ -16,7 +16,7 @@ use super::Spec;
 
 const MAX_DEBT: Percent = Percent::from_permille(800);
 
-type TestCurrency = PaymentC3;
+type TestCurrency = PaymentC4;
 type TestLpn = Lpn;
 
 struct TestDue {
@@ -28,6 +28,10 @@ impl DueTrait for TestDue {
         self.total_due
     }
 
+    fn total_due_interest(&self) -> LpnCoin {
+        self.overdue
+    }
+
     #[track_caller]
     fn overdue_collection(&self, min_amount: LpnCoin) -> OverdueCollection {
         if self.overdue.is_zero() || self.overdue < min_amount {
@@ -157,6 +161,47 @@ mod test_calc_borrow {
     }
 }
 
+mod test_max_increase_borrow {
+    use finance::{coin::Coin, zero::Zero};
+
+    use super::TestLpn;
+
+    #[test]
+    fn below_initial() {
+        // equity = 1000 + 200 - 660 = 540, max debt = 540 * 65 / 35 = 1002
+        let spec = super::spec(1_000, 300);
+        let due = super::due(660, 0);
+
+        assert_eq!(
+            Coin::<TestLpn>::new(1002 - 600),
+            spec.max_increase_borrow(
+                Coin::new(500),
+                200.into(),
+                &due,
+                600.into(),
+                super::price(1, 2)
+            )
+        );
+    }
+
+    #[test]
+    fn above_initial() {
+        let spec = super::spec(1_000, 300);
+        let due = super::due(720, 0);
+
+        assert_eq!(
+            Coin::<TestLpn>::ZERO,
+            spec.max_increase_borrow(
+                Coin::new(500),
+                Coin::ZERO,
+                &due,
+                700.into(),
+                super::price(1, 2)
+            )
+        );
+    }
+}
+
 mod test_debt {
 
     use currencies::Lpn;
@@ -993,6 +1038,84 @@ mod test_validate_close {
     }
 }
 
+mod test_close_amount_to_ltv {
+    use finance::percent::Percent;
+
+    use crate::position::PositionError;
+
+    #[test]
+    fn below_current() {
+        let spec = super::spec(100, 10);
+        let asset = 1000.into();
+
+        let result_1 = spec.close_amount_to_ltv(
+            asset,
+            &super::due(700, 0),
+            super::price(1, 1),
+            Percent::from_percent(50),
+        );
+        assert_eq!(Ok(400.into()), result_1);
+
+        let result_2 = spec.close_amount_to_ltv(
+            asset,
+            &super::due(700, 0),
+            super::price(1, 2),
+            Percent::from_percent(25),
+        );
+        assert_eq!(Ok(133.into()), result_2);
+
+        let result_3 = spec.close_amount_to_ltv(
+            asset,
+            &super::due(700, 0),
+            super::price(1, 1),
+            Percent::ZERO,
+        );
+        assert_eq!(Ok(700.into()), result_3);
+    }
+
+    #[test]
+    fn not_below_current() {
+        let spec = super::spec(100, 10);
+        let asset = 1000.into();
+
+        let result_1 = spec.close_amount_to_ltv(
+            asset,
+            &super::due(700, 0),
+            super::price(1, 1),
+            Percent::from_percent(70),
+        );
+        assert_eq!(
+            Err(PositionError::close_ltv_not_below_current(
+                Percent::from_percent(70),
+                Percent::from_percent(70)
+            )),
+            result_1
+        );
+
+        let result_2 = spec.close_amount_to_ltv(
+            asset,
+            &super::due(700, 0),
+            super::price(1, 2),
+            Percent::from_percent(40),
+        );
+        assert!(matches!(
+            result_2,
+            Err(PositionError::CloseLtvNotBelowCurrent { .. })
+        ));
+
+        let result_3 = spec.close_amount_to_ltv(
+            asset,
+            &super::due(1200, 0),
+            super::price(1, 1),
+            Percent::HUNDRED,
+        );
+        assert!(matches!(
+            result_3,
+            Err(PositionError::CloseLtvNotBelowCurrent { .. })
+        ));
+    }
+}
+
 mod test_check_close {
     use finance::percent::Percent;
 
@@ -1171,3 +1294,261 @@ mod test_check_close {
         );
     }
 }
+
+mod test_price_trigger {
+    use finance::percent::Percent;
+
+    use crate::{
+        api::{
+            position::{ChangeCmd, ClosePolicyChange},
+            query::opened::ClosePolicy,
+        },
+        position::CloseStrategy,
+    };
+
+    #[test]
+    fn stop_loss_follows_debt() {
+        let asset = 1000.into();
+        let sl_price = super::price(1, 1);
+
+        let spec = super::spec(40, 10)
+            .change_close_policy(
+                ClosePolicyChange {
+                    take_profit: None,
+                    stop_loss: Some(ChangeCmd::SetPrice(sl_price.into())),
+                },
+                asset,
+                &super::due(550, 0),
+                super::price(1, 2),
+            )
+            .unwrap();
+        assert_eq!(
+            ClosePolicy::new(None, Some(Percent::from_percent(55)))
+                .with_prices(None, Some(sl_price.into())),
+            spec.close_policy()
+        );
+
+        let (spec, dropped) =
+            spec.update_close_policy(asset, &super::due(500, 0), super::price(1, 2));
+        assert_eq!(Vec::<CloseStrategy>::new(), dropped);
+        assert_eq!(
+            ClosePolicy::new(None, Some(Percent::from_percent(50)))
+                .with_prices(None, Some(sl_price.into())),
+            spec.close_policy()
+        );
+        assert_eq!(
+            None,
+            spec.check_close(asset, &super::due(500, 0), super::price(100, 101))
+        );
+        assert_eq!(
+            Some(CloseStrategy::StopLoss(Percent::from_percent(50))),
+            spec.check_close(asset, &super::due(500, 0), sl_price)
+        );
+    }
+
+    #[test]
+    fn stop_loss_past_liquidation_dropped() {
+        let asset = 1000.into();
+        let sl_price = super::price(1, 1);
+
+        let spec = super::spec(40, 10)
+            .change_close_policy(
+                ClosePolicyChange {
+                    take_profit: None,
+                    stop_loss: Some(ChangeCmd::SetPrice(sl_price.into())),
+                },
+                asset,
+                &super::due(550, 0),
+                super::price(1, 2),
+            )
+            .unwrap();
+
+        // the accrued interest takes the stop loss past the liquidation
+        let (spec, dropped) =
+            spec.update_close_policy(asset, &super::due(800, 0), super::price(1, 2));
+        assert_eq!(
+            vec![CloseStrategy::StopLoss(Percent::from_percent(80))],
+            dropped
+        );
+        assert_eq!(ClosePolicy::default(), spec.close_policy());
+    }
+
+    #[test]
+    fn take_profit_paid_dropped() {
+        let asset = 1000.into();
+        let tp_price = super::price(1, 4);
+
+        let spec = super::spec(40, 10)
+            .change_close_policy(
+                ClosePolicyChange {
+                    take_profit: Some(ChangeCmd::SetPrice(tp_price.into())),
+                    stop_loss: Some(ChangeCmd::Set(Percent::from_percent(60))),
+                },
+                asset,
+                &super::due(800, 0),
+                super::price(1, 2),
+            )
+            .unwrap();
+
+        let (spec, dropped) =
+            spec.update_close_policy(asset, &super::due(0, 0), super::price(1, 2));
+        assert_eq!(vec![CloseStrategy::TakeProfit(Percent::ZERO)], dropped);
+        assert_eq!(
+            ClosePolicy::new(None, Some(Percent::from_percent(60))),
+            spec.close_policy()
+        );
+    }
+
+    #[test]
+    fn take_profit_above_current_price() {
+        let asset = 1000.into();
+        let spec = super::spec(40, 10);
+
+        let tp_price = super::price(1, 4);
+        let spec = spec
+            .change_close_policy(
+                ClosePolicyChange {
+                    take_profit: Some(ChangeCmd::SetPrice(tp_price.into())),
+                    stop_loss: None,
+                },
+                asset,
+                &super::due(800, 0),
+                super::price(1, 2),
+            )
+            .unwrap();
+        assert_eq!(
+            ClosePolicy::new(Some(Percent::from_percent(20)), None)
+                .with_prices(Some(tp_price.into()), None),
+            spec.close_policy()
+        );
+        assert_eq!(
+            Some(CloseStrategy::TakeProfit(Percent::from_percent(20))),
+            spec.check_close(asset, &super::due(800, 0), super::price(100, 401))
+        );
+    }
+}
+
+mod test_price_levels {
+    use finance::{coin::Coin, percent::Percent};
+
+    use crate::{
+        api::position::{ChangeCmd, ClosePolicyChange},
+        position::PriceLevels,
+    };
+
+    #[test]
+    fn no_debt() {
+        let spec = super::spec(40, 10);
+        assert_eq!(
+            None,
+            spec.price_levels(Coin::<super::TestCurrency>::new(1000), &super::due(0, 0))
+        );
+    }
+
+    #[test]
+    fn no_close_policy() {
+        let spec = super::spec(40, 10);
+        assert_eq!(
+            Some(PriceLevels {
+                first_liq_warn: super::price(730, 730),
+                second_liq_warn: super::price(750, 730),
+                third_liq_warn: super::price(780, 730),
+                liquidation: super::price(800, 730),
+                take_profit: None,
+                stop_loss: None,
+            }),
+            spec.price_levels(1000.into(), &super::due(730, 0))
+        );
+    }
+
+    #[test]
+    fn with_close_policy() {
+        let asset = 1000.into();
+        let due = super::due(730, 0);
+        let spec = super::spec(40, 10)
+            .change_close_policy(
+                ClosePolicyChange {
+                    take_profit: Some(ChangeCmd::Set(Percent::from_percent(30))),
+                    stop_loss: Some(ChangeCmd::Set(Percent::from_percent(60))),
+                },
+                asset,
+                &due,
+                super::price(1, 2),
+            )
+            .unwrap();
+        let levels = spec.price_levels(asset, &due).unwrap();
+        assert_eq!(Some(super::price(300, 730)), levels.take_profit);
+        assert_eq!(Some(super::price(600, 730)), levels.stop_loss);
+        assert!(levels.stop_loss.unwrap() > levels.liquidation);
+        assert!(levels.take_profit.unwrap() > levels.stop_loss.unwrap());
+    }
+
+    #[test]
+    fn price_trigger() {
+        let asset = 1000.into();
+        let sl_price = super::price(1, 1);
+        let spec = super::spec(40, 10)
+            .change_close_policy(
+                ClosePolicyChange {
+                    take_profit: None,
+                    stop_loss: Some(ChangeCmd::SetPrice(sl_price.into())),
+                },
+                asset,
+                &super::due(550, 0),
+                super::price(1, 2),
+            )
+            .unwrap();
+
+        let levels = spec.price_levels(asset, &super::due(500, 0)).unwrap();
+        assert_eq!(None, levels.take_profit);
+        assert_eq!(Some(sl_price), levels.stop_loss);
+    }
+}
+
+mod test_overdue_warning {
+    use finance::duration::Duration;
+
+    use crate::{api::open::OverdueWarnings, position::OverdueWarning};
+
+    #[test]
+    fn no_warnings() {
+        let spec = super::spec(1000, 10);
+        assert_eq!(None, spec.overdue_warning(&super::due(1000, 5)));
+    }
+
+    #[test]
+    fn not_reached() {
+        let spec =
+            super::spec(1000, 10).with_overdue_warnings(Some(warnings(Duration::from_days(4))));
+        assert_eq!(None, spec.overdue_warning(&super::due(1000, 5)));
+    }
+
+    #[test]
+    fn reached() {
+        let spec =
+            super::spec(1000, 10).with_overdue_warnings(Some(warnings(Duration::from_days(6))));
+        assert_eq!(
+            Some(OverdueWarning {
+                level: 1,
+                collect_in: Duration::from_days(5),
+                amount: 5.into(),
+            }),
+            spec.overdue_warning(&super::due(1000, 5))
+        );
+    }
+
+    #[test]
+    fn collectable() {
+        let spec =
+            super::spec(1000, 10).with_overdue_warnings(Some(warnings(Duration::from_days(6))));
+        assert_eq!(None, spec.overdue_warning(&super::due(1000, 15)));
+    }
+
+    fn warnings(first: Duration) -> OverdueWarnings {
+        OverdueWarnings {
+            first,
+            second: Duration::from_days(2),
+            third: Duration::from_days(1),
+        }
+    }
+}
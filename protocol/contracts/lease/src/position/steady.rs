@@ -34,6 +34,16 @@ where
     pub(super) fn new(r#for: Duration, within: RightOpenRange<Price<Asset>, Descending>) -> Self {
         Self { r#for, within }
     }
+
+    /// The period the position is guaranteed to stay steady for
+    pub fn r#for(&self) -> Duration {
+        self.r#for
+    }
+
+    /// The lease asset price range the position is steady within
+    pub fn within(&self) -> &RightOpenRange<Price<Asset>, Descending> {
+        &self.within
+    }
 }
 
 impl<Asset> Steadiness<Asset>
@@ -1,38 +1,60 @@
 use currency::CurrencyDTO;
 use finance::percent::Percent;
-use lease::api::open::{LoanForm, NewLeaseContract, NewLeaseForm};
+use lease::api::open::{BasketForm, LoanForm, NewLeaseContract, NewLeaseForm, OpenLimit};
 use platform::batch::Batch;
 use platform::message::Response as MessageResponse;
-use sdk::cosmwasm_std::{Addr, Coin, Storage};
+use sdk::cosmwasm_std::{Addr, Env, MessageInfo, Storage};
 
 use crate::{
     finance::LeaseCurrencies,
-    state::{config::Config, leases::Leases},
+    state::{
+        config::Config, customer_access::Customers, leases::Leases, open_interest::OpenInterest,
+    },
     ContractError,
 };
 
+/// The optional customer terms of a new lease
+#[derive(Default)]
+pub struct LeaseTerms {
+    pub max_ltd: Option<Percent>,
+    pub max_slippage: Option<Percent>,
+    pub open_at: Option<OpenLimit>,
+    pub basket: Option<BasketForm>,
+}
+
 pub struct Borrow {}
 impl Borrow {
     pub fn with(
         storage: &mut dyn Storage,
-        amount: Vec<Coin>,
-        customer: Addr,
-        admin: Addr,
-        finalizer: Addr,
+        info: MessageInfo,
+        env: Env,
         currency: CurrencyDTO<LeaseCurrencies>,
-        max_ltd: Option<Percent>,
+        terms: LeaseTerms,
     ) -> Result<MessageResponse, ContractError> {
-        Leases::cache_open_req(storage, &customer)
+        let MessageInfo {
+            sender: customer,
+            funds: amount,
+        } = info;
+        let leaser = env.contract.address;
+        Customers::check(storage, &customer)
+            .and_then(|()| Leases::cache_open_req(storage, &customer))
+            .and_then(|()| OpenInterest::cache_open_req(storage, &customer, &currency))
             .and_then(|()| Config::load(storage))
             .and_then(|config| {
                 let mut batch = Batch::default();
                 batch
                     .schedule_instantiate_wasm_reply_on_success(
                         config.lease_code,
-                        &Self::open_lease_msg(customer, config, currency, max_ltd, finalizer),
+                        &Self::open_lease_msg(
+                            customer,
+                            config,
+                            currency,
+                            terms,
+                            leaser.clone(), // the leases notify this contract when they get closed
+                        ),
                         Some(amount),
                         "lease".into(),
-                        Some(admin), // allows lease migrations from this contract
+                        Some(leaser), // allows lease migrations from this contract
                         Default::default(),
                     )
                     .map(|()| batch)
@@ -45,24 +67,29 @@ impl Borrow {
         customer: Addr,
         config: Config,
         currency: CurrencyDTO<LeaseCurrencies>,
-        max_ltd: Option<Percent>,
+        terms: LeaseTerms,
         finalizer: Addr,
     ) -> NewLeaseContract {
+        let position_spec = config.lease_position_spec_of(&currency);
+        let annual_margin_interest = config.lease_interest_rate_margin_of(&currency);
         NewLeaseContract {
             form: NewLeaseForm {
                 customer,
                 currency,
-                max_ltd,
-                position_spec: config.lease_position_spec,
+                max_ltd: terms.max_ltd,
+                max_slippage: terms.max_slippage,
+                position_spec,
                 loan: LoanForm {
                     lpp: config.lpp,
                     profit: config.profit,
-                    annual_margin_interest: config.lease_interest_rate_margin,
+                    annual_margin_interest,
                     due_period: config.lease_due_period,
                 },
                 reserve: config.reserve,
                 time_alarms: config.time_alarms,
                 market_price_oracle: config.market_price_oracle,
+                open_at: terms.open_at,
+                basket: terms.basket,
             },
             dex: config.dex,
             finalizer,
@@ -1,5 +1,5 @@
-pub use borrow::Borrow;
-pub use quote::Quote;
+pub use borrow::{Borrow, LeaseTerms};
+pub use quote::{Quote, QuoteTerms};
 
 mod borrow;
 mod quote;
@@ -1,17 +1,21 @@
 use std::marker::PhantomData;
 
-use currency::{AnyVisitor, AnyVisitorResult, Currency, CurrencyDTO, CurrencyDef, MemberOf};
+use currency::{AnyVisitor, AnyVisitorResult, Currency, CurrencyDTO, CurrencyDef, Group, MemberOf};
 use finance::{
     coin::{Coin, WithCoin, WithCoinResult},
+    duration::Duration,
+    fraction::Fraction,
+    interest,
     liability::Liability,
     percent::Percent,
-    price::total,
+    price::{self, dto::PriceDTO, total, Price},
 };
 use lease::api::DownpaymentCoin;
 use lpp::{
     msg::QueryQuoteResponse,
     stub::lender::{LppLender as LppLenderTrait, WithLppLender},
 };
+use oracle::{api::swap::SwapPath as SwapPathDTO, stub::SwapPath};
 use oracle_platform::{Oracle as OracleTrait, WithOracle};
 use sdk::cosmwasm_std::{QuerierWrapper, StdResult};
 
@@ -21,13 +25,19 @@ use crate::{
     ContractError,
 };
 
+/// The terms a lease would be opened with
+pub struct QuoteTerms {
+    pub liability: Liability,
+    pub interest_rate_margin: Percent,
+    pub due_period: Duration,
+}
+
 pub struct Quote<'r> {
     querier: QuerierWrapper<'r>,
     lease_asset: CurrencyDTO<LeaseCurrencies>,
     downpayment: DownpaymentCoin,
     oracle: OracleRef,
-    liability: Liability,
-    lease_interest_rate_margin: Percent,
+    terms: QuoteTerms,
     max_ltd: Option<Percent>,
 }
 
@@ -37,8 +47,7 @@ impl<'r> Quote<'r> {
         downpayment: DownpaymentCoin,
         lease_asset: CurrencyDTO<LeaseCurrencies>,
         oracle: OracleRef,
-        liability: Liability,
-        lease_interest_rate_margin: Percent,
+        terms: QuoteTerms,
         max_ltd: Option<Percent>,
     ) -> Self {
         Self {
@@ -46,11 +55,28 @@ impl<'r> Quote<'r> {
             lease_asset,
             downpayment,
             oracle,
-            liability,
-            lease_interest_rate_margin,
+            terms,
             max_ltd,
         }
     }
+
+    fn swap_path<SwapIn>(
+        &self,
+        from: CurrencyDTO<SwapIn>,
+    ) -> Result<SwapPathDTO<PaymentCurrencies>, ContractError>
+    where
+        SwapIn: Group + MemberOf<PaymentCurrencies>,
+    {
+        if from.into_super_group::<PaymentCurrencies>()
+            == self.lease_asset.into_super_group::<PaymentCurrencies>()
+        {
+            Ok(vec![])
+        } else {
+            self.oracle
+                .swap_path(from, self.lease_asset, self.querier)
+                .map_err(Into::into)
+        }
+    }
 }
 
 impl WithLppLender<LpnCurrency, LpnCurrencies> for Quote<'_> {
@@ -61,14 +87,20 @@ impl WithLppLender<LpnCurrency, LpnCurrencies> for Quote<'_> {
     where
         Lpp: LppLenderTrait<LpnCurrency, LpnCurrencies>,
     {
+        let downpayment_swap_path = self.swap_path(self.downpayment.currency())?;
+        let borrow_swap_path = self.swap_path(currency::dto::<LpnCurrency, LpnCurrencies>())?;
+
         self.oracle.execute_as_oracle(
             QuoteStage2 {
                 downpayment: self.downpayment,
                 lease_asset: self.lease_asset,
                 lpp_quote: LppQuote::new(lpp)?,
-                liability: self.liability,
-                lease_interest_rate_margin: self.lease_interest_rate_margin,
+                terms: self.terms,
                 max_ltd: self.max_ltd,
+                swap_paths: SwapPaths {
+                    downpayment: downpayment_swap_path,
+                    borrow: borrow_swap_path,
+                },
             },
             self.querier,
         )
@@ -106,6 +138,12 @@ where
     }
 }
 
+/// The swap paths of the downpayment and the borrowed amount into the lease asset
+struct SwapPaths {
+    downpayment: SwapPathDTO<PaymentCurrencies>,
+    borrow: SwapPathDTO<PaymentCurrencies>,
+}
+
 struct QuoteStage2<Lpn, Lpp>
 where
     Lpp: LppLenderTrait<Lpn, LpnCurrencies>,
@@ -113,15 +151,15 @@ where
     downpayment: DownpaymentCoin,
     lease_asset: CurrencyDTO<LeaseCurrencies>,
     lpp_quote: LppQuote<Lpn, Lpp>,
-    liability: Liability,
-    lease_interest_rate_margin: Percent,
+    terms: QuoteTerms,
     max_ltd: Option<Percent>,
+    swap_paths: SwapPaths,
 }
 
 impl<Lpn, Lpp> WithOracle<Lpn, LpnCurrencies> for QuoteStage2<Lpn, Lpp>
 where
     Lpn: CurrencyDef,
-    Lpn::Group: MemberOf<LpnCurrencies>,
+    Lpn::Group: MemberOf<LpnCurrencies> + MemberOf<PaymentCurrencies>,
     Lpp: LppLenderTrait<Lpn, LpnCurrencies>,
 {
     type G = PaymentCurrencies;
@@ -136,9 +174,9 @@ where
             lease_asset: self.lease_asset,
             lpp_quote: self.lpp_quote,
             oracle,
-            liability: self.liability,
-            lease_interest_rate_margin: self.lease_interest_rate_margin,
+            terms: self.terms,
             max_ltd: self.max_ltd,
+            swap_paths: self.swap_paths,
         })
     }
 }
@@ -151,15 +189,15 @@ where
     lease_asset: CurrencyDTO<LeaseCurrencies>,
     lpp_quote: LppQuote<Lpn, Lpp>,
     oracle: Oracle,
-    liability: Liability,
-    lease_interest_rate_margin: Percent,
+    terms: QuoteTerms,
     max_ltd: Option<Percent>,
+    swap_paths: SwapPaths,
 }
 
 impl<Lpn, Lpp, Oracle> WithCoin<PaymentCurrencies> for QuoteStage3<Lpn, Lpp, Oracle>
 where
     Lpn: CurrencyDef,
-    Lpn::Group: MemberOf<LpnCurrencies>,
+    Lpn::Group: MemberOf<LpnCurrencies> + MemberOf<PaymentCurrencies>,
     Lpp: LppLenderTrait<Lpn, LpnCurrencies>,
     Oracle: OracleTrait<PaymentCurrencies, QuoteC = Lpn, QuoteG = LpnCurrencies>,
 {
@@ -175,9 +213,9 @@ where
             downpayment,
             lpp_quote: self.lpp_quote,
             oracle: self.oracle,
-            liability: self.liability,
-            lease_interest_rate_margin: self.lease_interest_rate_margin,
+            terms: self.terms,
             max_ltd: self.max_ltd,
+            swap_paths: self.swap_paths,
         })
     }
 }
@@ -191,15 +229,15 @@ where
     downpayment: Coin<Dpc>,
     lpp_quote: LppQuote<Lpn, Lpp>,
     oracle: Oracle,
-    liability: Liability,
-    lease_interest_rate_margin: Percent,
+    terms: QuoteTerms,
     max_ltd: Option<Percent>,
+    swap_paths: SwapPaths,
 }
 
 impl<Lpn, Dpc, Lpp, Oracle> AnyVisitor<LeaseCurrencies> for QuoteStage4<Lpn, Dpc, Lpp, Oracle>
 where
     Lpn: CurrencyDef,
-    Lpn::Group: MemberOf<LpnCurrencies>,
+    Lpn::Group: MemberOf<LpnCurrencies> + MemberOf<PaymentCurrencies>,
     Dpc: CurrencyDef,
     Dpc::Group: MemberOf<PaymentCurrencies>,
     Lpp: LppLenderTrait<Lpn, LpnCurrencies>,
@@ -220,20 +258,52 @@ where
         }
 
         let borrow = self
+            .terms
             .liability
             .init_borrow_amount(downpayment_lpn, self.max_ltd);
 
-        let asset_price = self.oracle.price_of::<Asset>()?.inv();
+        let asset_price = self.oracle.price_of::<Asset>()?;
 
-        let total_asset = total(downpayment_lpn + borrow, asset_price);
+        let total_asset = total(downpayment_lpn + borrow, asset_price.inv());
 
         let annual_interest_rate = self.lpp_quote.with(borrow)?;
 
+        let liquidation_price =
+            price::total_of(self.terms.liability.max().of(total_asset)).is(borrow);
+
+        let first_due_interest =
+            interest::interest(annual_interest_rate, borrow, self.terms.due_period)
+                + interest::interest(
+                    self.terms.interest_rate_margin,
+                    borrow,
+                    self.terms.due_period,
+                );
+
         Ok(QuoteResponse {
             total: total_asset.into(),
             borrow: borrow.into(),
             annual_interest_rate,
-            annual_interest_rate_margin: self.lease_interest_rate_margin,
+            annual_interest_rate_margin: self.terms.interest_rate_margin,
+            downpayment_swap_path: self.swap_paths.downpayment,
+            borrow_swap_path: self.swap_paths.borrow,
+            asset_price: price_into_api(asset_price),
+            initial_ltv: Percent::from_ratio(borrow, downpayment_lpn + borrow),
+            liquidation_price: price_into_api(liquidation_price),
+            first_due_interest: first_due_interest.into(),
         })
     }
 }
+
+fn price_into_api<Asset, Lpn>(price: Price<Asset, Lpn>) -> PriceDTO<PaymentCurrencies>
+where
+    Asset: CurrencyDef,
+    Asset::Group: MemberOf<PaymentCurrencies>,
+    Lpn: CurrencyDef,
+    Lpn::Group: MemberOf<PaymentCurrencies>,
+{
+    PriceDTO::from_price(
+        price,
+        currency::dto::<Asset, PaymentCurrencies>(),
+        currency::dto::<Lpn, PaymentCurrencies>(),
+    )
+}
@@ -16,18 +16,21 @@ use sdk::{
         QuerierWrapper, Reply,
     },
 };
-use versioning::{package_version, SemVer, Version, VersionSegment};
+use versioning::{package_version, FullUpdateOutput, SemVer, Version, VersionSegment};
 
 use crate::{
-    cmd::Borrow,
+    cmd::{Borrow, LeaseTerms},
     error::ContractError,
     leaser::{self, Leaser},
     msg::{ExecuteMsg, InstantiateMsg, MaxLeases, MigrateMsg, QueryMsg, SudoMsg},
     result::ContractResult,
-    state::{config::Config, leases::Leases},
+    state::{
+        config::Config, customer_access::Customers, leases::Leases, open_interest::OpenInterest,
+    },
 };
 
-const CONTRACT_STORAGE_VERSION: VersionSegment = 4;
+const CONTRACT_STORAGE_VERSION_FROM: VersionSegment = 4;
+const CONTRACT_STORAGE_VERSION: VersionSegment = CONTRACT_STORAGE_VERSION_FROM + 1;
 const PACKAGE_VERSION: SemVer = package_version!();
 const CONTRACT_VERSION: Version = Version::new(CONTRACT_STORAGE_VERSION, PACKAGE_VERSION);
 
@@ -64,9 +67,17 @@ pub fn migrate(
     _env: Env,
     MigrateMsg {}: MigrateMsg,
 ) -> ContractResult<Response> {
-    versioning::update_software(deps.storage, CONTRACT_VERSION, Into::into)
-        .and_then(response::response)
-        .inspect_err(platform_error::log(deps.api))
+    // The open interest of the already open leases is not seeded. It would take querying
+    // each lease and its loan, and the gas of a single transaction does not scale with
+    // the number of leases. The caps are enforced on the leases opened from now on.
+    versioning::update_software_and_storage::<CONTRACT_STORAGE_VERSION_FROM, _, _, _, _>(
+        deps.storage,
+        CONTRACT_VERSION,
+        Leases::init_count,
+        Into::into,
+    )
+    .and_then(|FullUpdateOutput { release_label, .. }| response::response(release_label))
+    .inspect_err(platform_error::log(deps.api))
 }
 
 #[entry_point]
@@ -77,14 +88,23 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> ContractResult<Response> {
     match msg {
-        ExecuteMsg::OpenLease { currency, max_ltd } => Borrow::with(
-            deps.storage,
-            info.funds,
-            info.sender,
-            env.contract.address.clone(),
-            finalizer(env),
+        ExecuteMsg::OpenLease {
             currency,
             max_ltd,
+            max_slippage,
+            open_at,
+            basket,
+        } => Borrow::with(
+            deps.storage,
+            info,
+            env,
+            currency,
+            LeaseTerms {
+                max_ltd,
+                max_slippage,
+                open_at,
+                basket,
+            },
         ),
         ExecuteMsg::FinalizeLease { customer } => {
             validate_customer(customer, deps.api, deps.querier)
@@ -92,13 +112,41 @@ pub fn execute(
                     validate_lease(info.sender, deps.as_ref()).map(|lease| (customer, lease))
                 })
                 .and_then(|(customer, lease)| {
-                    Leases::remove(deps.storage, customer, &lease).map_err(Into::into)
+                    Leases::remove(deps.storage, customer, &lease).and_then(|removed| {
+                        OpenInterest::remove(deps.storage, lease).map(|()| removed)
+                    })
                 })
                 .map(|removed| {
                     debug_assert!(removed);
                     MessageResponse::default()
                 })
         }
+        ExecuteMsg::ChangeLeaseOwner {
+            customer,
+            new_customer,
+        } => validate_customer(customer, deps.api, deps.querier)
+            .and_then(|customer| {
+                validate_customer(new_customer, deps.api, deps.querier)
+                    .map(|new_customer| (customer, new_customer))
+            })
+            .and_then(|(customer, new_customer)| {
+                validate_lease(info.sender, deps.as_ref())
+                    .map(|lease| (customer, new_customer, lease))
+            })
+            .and_then(|(customer, new_customer, lease)| {
+                Customers::check(deps.storage, &new_customer)
+                    .map(|()| (customer, new_customer, lease))
+            })
+            .and_then(|(customer, new_customer, lease)| {
+                Leases::change_owner(deps.storage, lease.clone(), customer, new_customer.clone())
+                    .and_then(|()| OpenInterest::change_owner(deps.storage, lease, new_customer))
+            })
+            .map(|()| MessageResponse::default()),
+        ExecuteMsg::LeaseLoanOpened { lpp } => validate_lease(info.sender, deps.as_ref())
+            .and_then(|lease| {
+                leaser::try_account_lease_loan(deps.storage, deps.querier, lease, lpp)
+            })
+            .map(|()| MessageResponse::default()),
         ExecuteMsg::MigrateLeases {
             new_code_id,
             max_leases,
@@ -136,11 +184,15 @@ pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<Respon
             lease_interest_rate_margin,
             lease_position_spec,
             lease_due_period,
+            lease_asset_specs,
+            open_interest_caps,
         } => leaser::try_configure(
             deps.storage,
             lease_interest_rate_margin,
             lease_position_spec,
             lease_due_period,
+            lease_asset_specs,
+            open_interest_caps,
         ),
         SudoMsg::CloseProtocol {
             new_lease_code_id,
@@ -157,6 +209,12 @@ pub fn sudo(deps: DepsMut<'_>, _env: Env, msg: SudoMsg) -> ContractResult<Respon
                 force,
             )
         }),
+        SudoMsg::CustomerAccess { access } => leaser::try_set_customer_access(deps.storage, access),
+        SudoMsg::AddCustomers { customers } => validate_addrs(customers, deps.api)
+            .and_then(|customers| leaser::try_add_customers(deps.storage, customers)),
+        SudoMsg::RemoveCustomers { customers } => {
+            leaser::try_remove_customers(deps.storage, customers)
+        }
     }
     .map(response::response_only_messages)
     .inspect_err(platform_error::log(deps.api))
@@ -170,8 +228,32 @@ pub fn query(deps: Deps<'_>, _env: Env, msg: QueryMsg) -> ContractResult<Binary>
             downpayment,
             lease_asset,
             max_ltd,
-        } => to_json_binary(&Leaser::new(deps).quote(downpayment, lease_asset, max_ltd)?),
+            customer,
+        } => to_json_binary(&Leaser::new(deps).quote(
+            downpayment,
+            lease_asset,
+            max_ltd,
+            customer.as_ref(),
+        )?),
+        QueryMsg::OpenInterestFits {
+            currency,
+            customer,
+            borrow,
+        } => to_json_binary(&Leaser::new(deps).open_interest_fits(currency, customer, borrow)?),
         QueryMsg::Leases { owner } => to_json_binary(&Leaser::new(deps).customer_leases(owner)?),
+        QueryMsg::CustomerLeases {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&Leaser::new(deps).customer_leases_page(owner, start_after, limit)?),
+        QueryMsg::AllLeases { start_after, limit } => {
+            to_json_binary(&Leaser::new(deps).leases_page(start_after, limit)?)
+        }
+        QueryMsg::LeasesCount { owner } => to_json_binary(&Leaser::new(deps).leases_count(owner)?),
+        QueryMsg::CustomerAccess {} => to_json_binary(&Leaser::new(deps).customer_access()?),
+        QueryMsg::CustomerList { start_after, limit } => {
+            to_json_binary(&Leaser::new(deps).customer_list_page(start_after, limit)?)
+        }
     }
     .map_err(Into::into)
     .inspect_err(platform_error::log(deps.api))
@@ -189,6 +271,10 @@ pub fn reply(deps: DepsMut<'_>, _env: Env, msg: Reply) -> ContractResult<Respons
                 lease
             })
         })
+        .and_then(|lease| {
+            leaser::try_account_open_interest(deps.storage, deps.querier, lease.clone())
+                .map(|()| lease)
+        })
         .map(|lease| Response::new().add_attribute("lease_address", lease))
         .inspect_err(platform_error::log(deps.api))
 }
@@ -212,6 +298,13 @@ fn validate_customer(
         })
 }
 
+fn validate_addrs(addrs: Vec<Addr>, api: &dyn Api) -> ContractResult<Vec<Addr>> {
+    addrs
+        .into_iter()
+        .map(|addr| api.addr_validate(addr.as_str()).map_err(Into::into))
+        .collect()
+}
+
 fn validate_lease(lease: Addr, deps: Deps<'_>) -> ContractResult<Addr> {
     Leaser::new(deps)
         .config()
@@ -236,7 +329,3 @@ where
 fn migrate_msg() -> LeaseMigrateMsg {
     LeaseMigrateMsg {}
 }
-
-fn finalizer(env: Env) -> Addr {
-    env.contract.address
-}
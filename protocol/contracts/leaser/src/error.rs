@@ -2,7 +2,9 @@ use std::num::TryFromIntError;
 
 use thiserror::Error;
 
-use sdk::cosmwasm_std::StdError;
+use sdk::cosmwasm_std::{Addr, StdError};
+
+use crate::msg::PageSize;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -21,6 +23,9 @@ pub enum ContractError {
     #[error("[Leaser] {0}")]
     PriceOracle(#[from] oracle_platform::error::Error),
 
+    #[error("[Leaser] {0}")]
+    SwapPath(#[from] oracle::api::swap::Error),
+
     #[error("[Leaser] {0}")]
     Platform(#[from] platform::error::Error),
 
@@ -42,6 +47,21 @@ pub enum ContractError {
     #[error("[Leaser] Invalid continuation key, cause: {err} ")]
     InvalidContinuationKey { err: String },
 
+    #[error("[Leaser] The lease '{0}' is not found among the leases of '{1}'")]
+    UnknownLease(Addr, Addr),
+
+    #[error("[Leaser] More than one lease spec of the currency '{0}'")]
+    DuplicateAssetSpec(String),
+
+    #[error("[Leaser] The open interest cap of '{0}' would be exceeded")]
+    OpenInterestCapExceeded(String),
+
+    #[error("[Leaser] The page size {0} exceeds the maximum of {1}")]
+    PageSizeExceeded(PageSize, PageSize),
+
+    #[error("[Leaser] The customer '{0}' is not allowed to open leases")]
+    CustomerNotAllowed(Addr),
+
     #[error("[Leaser] The protocol is still in use. There are open leases")]
     ProtocolStillInUse(),
 
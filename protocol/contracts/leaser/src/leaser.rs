@@ -3,24 +3,34 @@ use std::collections::HashSet;
 use admin_contract::msg::{ExecuteMsg, MigrationSpec, ProtocolContracts};
 use currencies::LeaseGroup;
 use currency::CurrencyDTO;
-use finance::{duration::Duration, percent::Percent};
-use lease::api::{open::PositionSpecDTO, DownpaymentCoin, MigrateMsg};
-use lpp::{msg::ExecuteMsg as LppExecuteMsg, stub::LppRef};
+use finance::{coin::Amount, duration::Duration, percent::Percent};
+use lease::api::{open::PositionSpecDTO, DownpaymentCoin, LpnCoinDTO, MigrateMsg};
+use lpp::{
+    msg::{ExecuteMsg as LppExecuteMsg, QueryLoanResponse, QueryMsg as LppQueryMsg},
+    stub::LppRef,
+};
 use platform::{
     batch::{Batch, Emit, Emitter},
     contract::Code,
     message::Response as MessageResponse,
 };
 use reserve::api::ExecuteMsg as ReserveExecuteMsg;
-use sdk::cosmwasm_std::{Addr, Deps, Storage};
+use sdk::cosmwasm_std::{Addr, Deps, QuerierWrapper, Storage};
 
 use crate::{
-    cmd::Quote,
+    cmd::{Quote, QuoteTerms},
     finance::LpnCurrencies,
     migrate,
-    msg::{ConfigResponse, MaxLeases, QuoteResponse},
+    msg::{
+        AssetSpec, ConfigResponse, LeaseEntry, MaxLeases, OpenInterestCaps, PageSize, QuoteResponse,
+    },
     result::ContractResult,
-    state::{config::Config, leases::Leases},
+    state::{
+        config::Config,
+        customer_access::{CustomerAccess, Customers},
+        leases::Leases,
+        open_interest::OpenInterest,
+    },
 };
 use crate::{
     finance::{LpnCurrency, OracleRef},
@@ -28,6 +38,9 @@ use crate::{
     ContractError,
 };
 
+const DEFAULT_PAGE_SIZE: PageSize = 50;
+const MAX_PAGE_SIZE: PageSize = 200;
+
 pub struct Leaser<'a> {
     deps: Deps<'a>,
 }
@@ -44,14 +57,67 @@ impl<'a> Leaser<'a> {
         Leases::load_by_customer(self.deps.storage, customer)
     }
 
+    pub fn customer_leases_page(
+        &self,
+        customer: Addr,
+        start_after: Option<Addr>,
+        limit: Option<PageSize>,
+    ) -> ContractResult<Vec<Addr>> {
+        page_size(limit).and_then(|limit| {
+            Leases::load_page_by_customer(self.deps.storage, customer, start_after, limit)
+        })
+    }
+
+    pub fn leases_page(
+        &self,
+        start_after: Option<LeaseEntry>,
+        limit: Option<PageSize>,
+    ) -> ContractResult<Vec<LeaseEntry>> {
+        page_size(limit).and_then(|limit| Leases::load_page(self.deps.storage, start_after, limit))
+    }
+
+    pub fn leases_count(&self, customer: Option<Addr>) -> ContractResult<MaxLeases> {
+        customer.map_or_else(
+            || Leases::count(self.deps.storage),
+            |customer| {
+                Leases::load_by_customer(self.deps.storage, customer)
+                    .and_then(|leases| MaxLeases::try_from(leases.len()).map_err(Into::into))
+            },
+        )
+    }
+
+    pub fn customer_access(&self) -> ContractResult<CustomerAccess> {
+        Customers::access(self.deps.storage)
+    }
+
+    pub fn customer_list_page(
+        &self,
+        start_after: Option<Addr>,
+        limit: Option<PageSize>,
+    ) -> ContractResult<Vec<Addr>> {
+        page_size(limit)
+            .and_then(|limit| Customers::load_page(self.deps.storage, start_after, limit))
+    }
+
     pub fn quote(
         &self,
         downpayment: DownpaymentCoin,
         lease_asset: CurrencyDTO<LeaseGroup>,
         max_ltd: Option<Percent>,
+        customer: Option<&Addr>,
     ) -> ContractResult<QuoteResponse> {
+        if let Some(customer) = customer {
+            Customers::check(self.deps.storage, customer)?;
+        }
+
         let config = Config::load(self.deps.storage)?;
 
+        let terms = QuoteTerms {
+            liability: config.lease_position_spec_of(&lease_asset).liability,
+            interest_rate_margin: config.lease_interest_rate_margin_of(&lease_asset),
+            due_period: config.lease_due_period,
+        };
+
         let lpp = LppRef::<LpnCurrency, LpnCurrencies>::try_new(config.lpp, self.deps.querier)?;
 
         let oracle = OracleRef::try_from_base(config.market_price_oracle, self.deps.querier)?;
@@ -62,30 +128,138 @@ impl<'a> Leaser<'a> {
                 downpayment,
                 lease_asset,
                 oracle,
-                config.lease_position_spec.liability,
-                config.lease_interest_rate_margin,
+                terms,
                 max_ltd,
             ),
             self.deps.querier,
         )
+        .and_then(|quote| {
+            OpenInterest::check(
+                self.deps.storage,
+                &lease_asset,
+                customer,
+                quote.borrow.amount(),
+                &config.open_interest_caps,
+            )
+            .map(|()| quote)
+        })
+    }
+
+    pub fn open_interest_fits(
+        &self,
+        currency: CurrencyDTO<LeaseGroup>,
+        customer: Addr,
+        borrow: LpnCoinDTO,
+    ) -> ContractResult<bool> {
+        Config::load(self.deps.storage).and_then(|config| {
+            match OpenInterest::check(
+                self.deps.storage,
+                &currency,
+                Some(&customer),
+                borrow.amount(),
+                &config.open_interest_caps,
+            ) {
+                Ok(()) => Ok(true),
+                Err(ContractError::OpenInterestCapExceeded(_)) => Ok(false),
+                Err(err) => Err(err),
+            }
+        })
     }
 }
 
+fn page_size(limit: Option<PageSize>) -> ContractResult<PageSize> {
+    limit.map_or(Ok(DEFAULT_PAGE_SIZE), |limit| {
+        if limit <= MAX_PAGE_SIZE {
+            Ok(limit)
+        } else {
+            Err(ContractError::PageSizeExceeded(limit, MAX_PAGE_SIZE))
+        }
+    })
+}
+
 pub(super) fn try_configure(
     storage: &mut dyn Storage,
     lease_interest_rate_margin: Percent,
     lease_position_spec: PositionSpecDTO,
     lease_due_period: Duration,
+    lease_asset_specs: Vec<AssetSpec>,
+    open_interest_caps: OpenInterestCaps,
 ) -> ContractResult<MessageResponse> {
     Config::update(
         storage,
         lease_interest_rate_margin,
         lease_position_spec,
         lease_due_period,
+        lease_asset_specs,
+        open_interest_caps,
     )
     .map(|()| MessageResponse::default())
 }
 
+pub(super) fn try_set_customer_access(
+    storage: &mut dyn Storage,
+    access: CustomerAccess,
+) -> ContractResult<MessageResponse> {
+    Customers::set_access(storage, access).map(|()| MessageResponse::default())
+}
+
+pub(super) fn try_add_customers(
+    storage: &mut dyn Storage,
+    customers: Vec<Addr>,
+) -> ContractResult<MessageResponse> {
+    Customers::add(storage, customers).map(|()| MessageResponse::default())
+}
+
+pub(super) fn try_remove_customers(
+    storage: &mut dyn Storage,
+    customers: Vec<Addr>,
+) -> ContractResult<MessageResponse> {
+    Customers::remove(storage, customers);
+    Ok(MessageResponse::default())
+}
+
+/// Account the borrow of a just instantiated lease into the open interest
+///
+/// The lease has already requested its loan from the configured LPP unless it is to be opened
+/// at a limit price. Such a lease is not accounted for.
+pub(super) fn try_account_open_interest(
+    storage: &mut dyn Storage,
+    querier: QuerierWrapper<'_>,
+    lease: Addr,
+) -> ContractResult<()> {
+    let config = Config::load(storage)?;
+
+    query_borrow(querier, config.lpp, lease.clone())
+        .and_then(|borrow| OpenInterest::save(storage, lease, borrow, &config.open_interest_caps))
+}
+
+/// Account the borrow of a lease opened at a limit price into the open interest
+///
+/// The borrow is queried from the `lpp` the lease has got its loan from. The lease has checked
+/// beforehand that the loan fits into the caps.
+pub(super) fn try_account_lease_loan(
+    storage: &mut dyn Storage,
+    querier: QuerierWrapper<'_>,
+    lease: Addr,
+    lpp: Addr,
+) -> ContractResult<()> {
+    let config = Config::load(storage)?;
+
+    query_borrow(querier, lpp, lease.clone()).and_then(|borrow| {
+        OpenInterest::save_borrow(storage, lease, borrow, &config.open_interest_caps)
+    })
+}
+
+fn query_borrow(querier: QuerierWrapper<'_>, lpp: Addr, lease: Addr) -> ContractResult<Amount> {
+    querier
+        .query_wasm_smart::<QueryLoanResponse<LpnCurrency>>(
+            lpp,
+            &LppQueryMsg::<LpnCurrencies>::Loan { lease_addr: lease },
+        )
+        .map_err(Into::into)
+        .map(|may_loan| may_loan.map_or_else(Amount::default, |loan| loan.principal_due.into()))
+}
+
 pub(super) fn try_migrate_leases<MsgFactory>(
     storage: &mut dyn Storage,
     new_lease: Code,
@@ -198,7 +372,7 @@ mod test {
     use currencies::Lpn;
     use finance::{coin::Coin, duration::Duration, liability::Liability, percent::Percent};
     use lease::api::{
-        open::{ConnectionParams, Ics20Channel, PositionSpecDTO},
+        open::{ConnectionParams, Ics20Channel, LeaseKind, PositionSpecDTO},
         MigrateMsg,
     };
     use platform::{contract::Code, response};
@@ -275,6 +449,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn page_size() {
+        assert_eq!(Ok(super::DEFAULT_PAGE_SIZE), super::page_size(None));
+        assert_eq!(Ok(12), super::page_size(Some(12)));
+        assert_eq!(
+            Ok(super::MAX_PAGE_SIZE),
+            super::page_size(Some(super::MAX_PAGE_SIZE))
+        );
+        assert_eq!(
+            Err(ContractError::PageSizeExceeded(
+                super::MAX_PAGE_SIZE + 1,
+                super::MAX_PAGE_SIZE
+            )),
+            super::page_size(Some(super::MAX_PAGE_SIZE + 1))
+        );
+    }
+
     fn dummy_instantiate_msg() -> InstantiateMsg {
         InstantiateMsg {
             lease_code: 10u16.into(),
@@ -296,6 +487,9 @@ mod test {
                 ),
                 min_asset: Coin::<Lpn>::from(120_000).into(),
                 min_transaction: Coin::<Lpn>::from(12_000).into(),
+                overdue_warnings: None,
+                liquidation_max_slippage: None,
+                kind: LeaseKind::Long,
             },
             lease_interest_rate_margin: Percent::from_percent(3),
             lease_due_period: Duration::from_days(14),
@@ -2,18 +2,22 @@ use serde::{Deserialize, Serialize};
 
 use admin_contract::msg::{MigrationSpec, ProtocolContracts};
 use currency::CurrencyDTO;
-use finance::{duration::Duration, percent::Percent};
+use finance::{duration::Duration, percent::Percent, price::dto::PriceDTO};
 use lease::api::{
-    open::{ConnectionParams, PositionSpecDTO},
+    open::{BasketForm, ConnectionParams, OpenLimit, PositionSpecDTO},
     DownpaymentCoin, LeaseCoin, LpnCoinDTO,
 };
+use oracle::api::swap::SwapPath;
 use sdk::{
     cosmwasm_std::{Addr, Uint64},
     schemars::{self, JsonSchema},
 };
 
-use crate::finance::LeaseCurrencies;
-pub use crate::state::config::Config;
+use crate::finance::{LeaseCurrencies, PaymentCurrencies};
+pub use crate::state::{
+    config::{AssetSpec, Config, OpenInterestCaps},
+    customer_access::CustomerAccess,
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -38,6 +42,9 @@ pub struct MigrateMsg {}
 
 pub type MaxLeases = u32;
 
+/// The maximum number of entries of a page returned by a paginated query
+pub type PageSize = u32;
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
@@ -46,11 +53,34 @@ pub enum ExecuteMsg {
         currency: CurrencyDTO<LeaseCurrencies>,
         #[serde(default)]
         max_ltd: Option<Percent>,
+        /// Maximum slippage of the opening swaps against the oracle prices
+        #[serde(default)]
+        max_slippage: Option<Percent>,
+        /// Open the lease once the asset price drops to a limit instead of immediately
+        ///
+        /// Until then the downpayment is held by the lease. It is sent back
+        /// to the customer if the limit is not reached before the expiry.
+        #[serde(default)]
+        open_at: Option<OpenLimit>,
+        /// Split the position across further lease currencies by weight
+        ///
+        /// The `currency` takes the part not allocated to the basket.
+        #[serde(default)]
+        basket: Option<BasketForm>,
     },
     /// A callback from a lease that it has just entered a final state
     ///
     /// It matches the `lease::api::FinalizerExecuteMsg::FinalizeLease`.
     FinalizeLease { customer: Addr },
+    /// A callback from a lease that its ownership has just been transferred
+    ///
+    /// It matches the `lease::api::FinalizerExecuteMsg::ChangeLeaseOwner`.
+    ChangeLeaseOwner { customer: Addr, new_customer: Addr },
+    /// A callback from a lease opened at a limit price that it has just got its loan
+    ///
+    /// The lease borrow from the `lpp` is accounted for in the open interest only then.
+    /// It matches the `lease::api::FinalizerExecuteMsg::LeaseLoanOpened`.
+    LeaseLoanOpened { lpp: Addr },
     /// Start a Lease migration
     ///
     /// The consumed gas is a limitaton factor for the maximum lease instances that
@@ -81,6 +111,19 @@ pub enum SudoMsg {
         lease_interest_rate_margin: Percent,
         lease_position_spec: PositionSpecDTO,
         lease_due_period: Duration,
+        /// Overrides of the lease position spec and interest rate margin per lease currency
+        ///
+        /// They replace the current ones, so an override not listed here is removed.
+        /// Return `ContractError::DuplicateAssetSpec` if a currency is listed more than once.
+        #[serde(default)]
+        lease_asset_specs: Vec<AssetSpec>,
+        /// Limits of the aggregated borrow of the open leases
+        ///
+        /// A new lease fails to open with `ContractError::OpenInterestCapExceeded`
+        /// if its borrow makes any of them exceeded. The borrow of the leases opened
+        /// before the leaser started accounting it does not count against the caps.
+        #[serde(default)]
+        open_interest_caps: OpenInterestCaps,
     },
     CloseProtocol {
         // Since this is an external system API we should not use [Code].
@@ -94,6 +137,14 @@ pub enum SudoMsg {
         #[serde(default)]
         force: ForceClose,
     },
+    /// Set which customers may open new leases
+    ///
+    /// The customers with open leases may still manage them regardless of the access.
+    CustomerAccess { access: CustomerAccess },
+    /// Add the customers to the list the access is checked against
+    AddCustomers { customers: Vec<Addr> },
+    /// Remove the customers from the list the access is checked against
+    RemoveCustomers { customers: Vec<Addr> },
 }
 
 #[derive(Serialize, Deserialize, Default, Clone, PartialEq, Eq, JsonSchema)]
@@ -116,10 +167,72 @@ pub enum QueryMsg {
         // TODO get rid of the default-ness
         #[serde(default)]
         max_ltd: Option<Percent>,
+        /// The prospective customer to check the customer access and
+        /// the per customer open interest cap against
+        #[serde(default)]
+        customer: Option<Addr>,
+    },
+    /// Check whether a new loan of `borrow` fits into the open interest caps
+    /// of the lease `currency` and the `customer`
+    ///
+    /// Return `bool`. It matches the `lease::api::FinalizerQueryMsg::OpenInterestFits`.
+    OpenInterestFits {
+        currency: CurrencyDTO<LeaseCurrencies>,
+        customer: Addr,
+        borrow: LpnCoinDTO,
     },
     Leases {
         owner: Addr,
     },
+    /// Return up to `limit` leases of the `owner` ordered by address
+    ///
+    /// The page starts after the `start_after` lease, if provided. A page shorter
+    /// than the `limit` is the last one. The `limit` defaults to 50 and may not
+    /// exceed 200, otherwise the query fails.
+    CustomerLeases {
+        owner: Addr,
+        #[serde(default)]
+        start_after: Option<Addr>,
+        #[serde(default)]
+        limit: Option<PageSize>,
+    },
+    /// Return up to `limit` leases of all customers ordered by customer and lease address
+    ///
+    /// The page starts after the `start_after` entry, if provided. Pass the last entry
+    /// of a page to get the next one. A page shorter than the `limit` is the last one.
+    /// The `limit` bounds follow `CustomerLeases`.
+    AllLeases {
+        #[serde(default)]
+        start_after: Option<LeaseEntry>,
+        #[serde(default)]
+        limit: Option<PageSize>,
+    },
+    /// Return the number of leases of the `owner`, or of all customers if not provided
+    LeasesCount {
+        #[serde(default)]
+        owner: Option<Addr>,
+    },
+    /// Return which customers may open new leases
+    ///
+    /// Return `CustomerAccess`
+    CustomerAccess {},
+    /// Return up to `limit` customers of the list the access is checked against
+    ///
+    /// The customers are ordered by address. The paging follows `CustomerLeases`.
+    CustomerList {
+        #[serde(default)]
+        start_after: Option<Addr>,
+        #[serde(default)]
+        limit: Option<PageSize>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct LeaseEntry {
+    pub customer: Addr,
+    pub lease: Addr,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
@@ -137,12 +250,24 @@ pub struct QuoteResponse {
     pub borrow: LpnCoinDTO,
     pub annual_interest_rate: Percent,
     pub annual_interest_rate_margin: Percent,
+    /// The swap path of the downpayment into the lease asset
+    pub downpayment_swap_path: SwapPath<PaymentCurrencies>,
+    /// The swap path of the borrowed amount into the lease asset
+    pub borrow_swap_path: SwapPath<PaymentCurrencies>,
+    /// The oracle price of the lease asset the quote is calculated at
+    pub asset_price: PriceDTO<PaymentCurrencies>,
+    /// The position LTV once the lease is open
+    pub initial_ltv: Percent,
+    /// The lease asset price at which the position would become subject of liquidation
+    pub liquidation_price: PriceDTO<PaymentCurrencies>,
+    /// The interest due at the end of the first due period
+    pub first_due_interest: LpnCoinDTO,
 }
 
 #[cfg(test)]
 mod test {
-    use crate::msg::ExecuteMsg;
-    use lease::api::FinalizerExecuteMsg;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use lease::api::{FinalizerExecuteMsg, FinalizerQueryMsg};
     use sdk::cosmwasm_std::Addr;
 
     #[test]
@@ -157,4 +282,62 @@ mod test {
         let msg_out: FinalizerExecuteMsg = from_json(finalize_bin).expect("deserialization passed");
         assert_eq!(FinalizerExecuteMsg::FinalizeLease { customer }, msg_out);
     }
+
+    #[test]
+    fn change_owner_api_match() {
+        use sdk::cosmwasm_std::{from_json, to_json_vec};
+
+        let customer = Addr::unchecked("c");
+        let new_customer = Addr::unchecked("nc");
+        let change_bin = to_json_vec(&ExecuteMsg::ChangeLeaseOwner {
+            customer: customer.clone(),
+            new_customer: new_customer.clone(),
+        })
+        .expect("serialization passed");
+        let msg_out: FinalizerExecuteMsg = from_json(change_bin).expect("deserialization passed");
+        assert_eq!(
+            FinalizerExecuteMsg::ChangeLeaseOwner {
+                customer,
+                new_customer
+            },
+            msg_out
+        );
+    }
+
+    #[test]
+    fn loan_opened_api_match() {
+        use sdk::cosmwasm_std::{from_json, to_json_vec};
+
+        let lpp = Addr::unchecked("lpp");
+        let loan_opened_bin = to_json_vec(&ExecuteMsg::LeaseLoanOpened { lpp: lpp.clone() })
+            .expect("serialization passed");
+        let msg_out: FinalizerExecuteMsg =
+            from_json(loan_opened_bin).expect("deserialization passed");
+        assert_eq!(FinalizerExecuteMsg::LeaseLoanOpened { lpp }, msg_out);
+    }
+
+    #[test]
+    fn open_interest_fits_api_match() {
+        use currencies::{testing::LeaseC1, Lpn};
+        use finance::coin::Coin;
+        use sdk::cosmwasm_std::{from_json, to_json_vec};
+
+        let customer = Addr::unchecked("c");
+        let borrow = Coin::<Lpn>::new(100).into();
+        let fits_bin = to_json_vec(&FinalizerQueryMsg::OpenInterestFits {
+            currency: currency::dto::<LeaseC1, _>(),
+            customer: customer.clone(),
+            borrow,
+        })
+        .expect("serialization passed");
+        let msg_out: QueryMsg = from_json(fits_bin).expect("deserialization passed");
+        assert_eq!(
+            QueryMsg::OpenInterestFits {
+                currency: currency::dto::<LeaseC1, _>(),
+                customer,
+                borrow
+            },
+            msg_out
+        );
+    }
 }
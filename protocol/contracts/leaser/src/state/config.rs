@@ -1,9 +1,13 @@
-use std::mem;
+use std::{collections::BTreeMap, mem};
 
 use serde::{Deserialize, Serialize};
 
-use finance::{duration::Duration, percent::Percent};
-use lease::api::open::{ConnectionParams, PositionSpecDTO};
+use currency::CurrencyDTO;
+use finance::{coin::Amount, duration::Duration, percent::Percent};
+use lease::api::{
+    open::{ConnectionParams, PositionSpecDTO},
+    LpnCoinDTO,
+};
 use platform::contract::Code;
 use sdk::{
     cosmwasm_std::{Addr, Storage},
@@ -11,7 +15,9 @@ use sdk::{
     schemars::{self, JsonSchema},
 };
 
-use crate::{msg::InstantiateMsg, result::ContractResult};
+use crate::{
+    error::ContractError, finance::LeaseCurrencies, msg::InstantiateMsg, result::ContractResult,
+};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(any(test, feature = "testing"), derive(Debug))]
@@ -27,6 +33,68 @@ pub struct Config {
     pub lease_interest_rate_margin: Percent,
     pub lease_due_period: Duration,
     pub dex: ConnectionParams,
+    /// Overrides of the lease position spec and interest rate margin per lease currency
+    ///
+    /// The lease currencies not listed here fall back to the defaults above.
+    #[serde(default)]
+    pub lease_asset_specs: Vec<AssetSpec>,
+    /// Limits of the borrow concentrated in a lease currency or by a customer
+    #[serde(default)]
+    pub open_interest_caps: OpenInterestCaps,
+}
+
+/// The lease parameters applied to the leases of a given currency
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct AssetSpec {
+    pub currency: CurrencyDTO<LeaseCurrencies>,
+    pub position_spec: PositionSpecDTO,
+    pub interest_rate_margin: Percent,
+}
+
+/// Limits of the aggregated borrow of the open leases
+///
+/// A missing cap means no limit.
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct OpenInterestCaps {
+    /// The maximum borrow of all leases in a lease currency keyed by its ticker
+    ///
+    /// The currencies not listed here are not limited.
+    #[serde(default)]
+    pub per_currency: BTreeMap<CurrencyDTO<LeaseCurrencies>, LpnCoinDTO>,
+    /// The maximum borrow of all leases of a customer
+    #[serde(default)]
+    pub per_customer: Option<LpnCoinDTO>,
+}
+
+impl OpenInterestCaps {
+    pub fn check_currency(
+        &self,
+        currency: &CurrencyDTO<LeaseCurrencies>,
+        total: Amount,
+    ) -> ContractResult<()> {
+        Self::check(self.per_currency.get(currency), total, || {
+            currency.to_string()
+        })
+    }
+
+    pub fn check_customer(&self, customer: &Addr, total: Amount) -> ContractResult<()> {
+        Self::check(self.per_customer.as_ref(), total, || customer.to_string())
+    }
+
+    fn check<F>(cap: Option<&LpnCoinDTO>, total: Amount, subject: F) -> ContractResult<()>
+    where
+        F: FnOnce() -> String,
+    {
+        if cap.is_some_and(|cap| cap.amount() < total) {
+            Err(ContractError::OpenInterestCapExceeded(subject()))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl Config {
@@ -45,9 +113,37 @@ impl Config {
             lease_interest_rate_margin: msg.lease_interest_rate_margin,
             lease_due_period: msg.lease_due_period,
             dex: msg.dex,
+            lease_asset_specs: vec![],
+            open_interest_caps: OpenInterestCaps::default(),
         }
     }
 
+    /// The position spec of the leases in `currency`
+    pub fn lease_position_spec_of(
+        &self,
+        currency: &CurrencyDTO<LeaseCurrencies>,
+    ) -> PositionSpecDTO {
+        self.asset_spec(currency)
+            .map_or(self.lease_position_spec, |spec| spec.position_spec)
+    }
+
+    /// The interest rate margin of the leases in `currency`
+    pub fn lease_interest_rate_margin_of(
+        &self,
+        currency: &CurrencyDTO<LeaseCurrencies>,
+    ) -> Percent {
+        self.asset_spec(currency)
+            .map_or(self.lease_interest_rate_margin, |spec| {
+                spec.interest_rate_margin
+            })
+    }
+
+    fn asset_spec(&self, currency: &CurrencyDTO<LeaseCurrencies>) -> Option<&AssetSpec> {
+        self.lease_asset_specs
+            .iter()
+            .find(|spec| &spec.currency == currency)
+    }
+
     pub fn store(&self, storage: &mut dyn Storage) -> ContractResult<()> {
         Self::STORAGE.save(storage, self).map_err(Into::into)
     }
@@ -61,18 +157,23 @@ impl Config {
         lease_interest_rate_margin: Percent,
         lease_position_spec: PositionSpecDTO,
         lease_due_period: Duration,
+        lease_asset_specs: Vec<AssetSpec>,
+        open_interest_caps: OpenInterestCaps,
     ) -> ContractResult<()> {
-        Self::STORAGE
-            .update(storage, |c| {
-                ContractResult::Ok(Self {
-                    lease_interest_rate_margin,
-                    lease_position_spec,
-                    lease_due_period,
-                    ..c
+        Self::check_unique(&lease_asset_specs).and_then(|()| {
+            Self::STORAGE
+                .update(storage, |c| {
+                    ContractResult::Ok(Self {
+                        lease_interest_rate_margin,
+                        lease_position_spec,
+                        lease_due_period,
+                        lease_asset_specs,
+                        open_interest_caps,
+                        ..c
+                    })
                 })
-            })
-            .map(mem::drop)
-            .map_err(Into::into)
+                .map(mem::drop)
+        })
     }
 
     pub fn update_lease_code(storage: &mut dyn Storage, new_code: Code) -> ContractResult<()> {
@@ -86,4 +187,18 @@ impl Config {
             .map(mem::drop)
             .map_err(Into::into)
     }
+
+    fn check_unique(specs: &[AssetSpec]) -> ContractResult<()> {
+        specs
+            .iter()
+            .enumerate()
+            .find(|(idx, spec)| {
+                specs[..*idx]
+                    .iter()
+                    .any(|other| other.currency == spec.currency)
+            })
+            .map_or(Ok(()), |(_, spec)| {
+                Err(ContractError::DuplicateAssetSpec(spec.currency.to_string()))
+            })
+    }
 }
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use sdk::{
+    cosmwasm_std::{Addr, Order, Storage},
+    cw_storage_plus::{Bound, Item, Map},
+    schemars::{self, JsonSchema},
+};
+
+use crate::{error::ContractError, msg::PageSize, result::ContractResult};
+
+/// Which customers may open new leases
+///
+/// The listed customers are either the only ones allowed, or the only ones denied,
+/// depending on the mode. The already open leases are not affected.
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug))]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum CustomerAccess {
+    /// Any customer may open leases, the list is not taken into account
+    #[default]
+    Open,
+    /// Only the listed customers may open leases
+    AllowList,
+    /// Any but the listed customers may open leases
+    DenyList,
+}
+
+pub(crate) struct Customers {}
+
+impl Customers {
+    const ACCESS: Item<CustomerAccess> = Item::new("customer_access");
+    const LISTED: Map<Addr, ()> = Map::new("customer_list");
+
+    pub fn access(storage: &dyn Storage) -> ContractResult<CustomerAccess> {
+        Self::ACCESS
+            .may_load(storage)
+            .map(Option::unwrap_or_default)
+            .map_err(Into::into)
+    }
+
+    pub fn set_access(storage: &mut dyn Storage, access: CustomerAccess) -> ContractResult<()> {
+        Self::ACCESS.save(storage, &access).map_err(Into::into)
+    }
+
+    pub fn add(storage: &mut dyn Storage, customers: Vec<Addr>) -> ContractResult<()> {
+        customers.into_iter().try_for_each(|customer| {
+            Self::LISTED
+                .save(storage, customer, &())
+                .map_err(Into::into)
+        })
+    }
+
+    pub fn remove(storage: &mut dyn Storage, customers: Vec<Addr>) {
+        customers
+            .into_iter()
+            .for_each(|customer| Self::LISTED.remove(storage, customer))
+    }
+
+    /// Return up to `limit` listed customers ordered by address
+    ///
+    /// The page starts after the `start_after` customer, if provided.
+    pub fn load_page(
+        storage: &dyn Storage,
+        start_after: Option<Addr>,
+        limit: PageSize,
+    ) -> ContractResult<Vec<Addr>> {
+        let limit = usize::try_from(limit)?;
+        Self::LISTED
+            .keys(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<Result<_, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Check whether the `customer` may open a new lease
+    pub fn check(storage: &dyn Storage, customer: &Addr) -> ContractResult<()> {
+        Self::access(storage).and_then(|access| {
+            let allowed = match access {
+                CustomerAccess::Open => true,
+                CustomerAccess::AllowList => Self::LISTED.has(storage, customer.clone()),
+                CustomerAccess::DenyList => !Self::LISTED.has(storage, customer.clone()),
+            };
+            if allowed {
+                Ok(())
+            } else {
+                Err(ContractError::CustomerNotAllowed(customer.clone()))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sdk::cosmwasm_std::{testing::MockStorage, Addr};
+
+    use crate::ContractError;
+
+    use super::{CustomerAccess, Customers};
+
+    #[test]
+    fn open_by_default() {
+        let store = MockStorage::default();
+        assert_eq!(Ok(CustomerAccess::Open), Customers::access(&store));
+        assert_eq!(
+            Ok(()),
+            Customers::check(&store, &Addr::unchecked("customer1"))
+        );
+    }
+
+    #[test]
+    fn allow_list() {
+        let mut store = MockStorage::default();
+        let listed = Addr::unchecked("customer1");
+        let another = Addr::unchecked("customer2");
+
+        Customers::add(&mut store, vec![listed.clone()]).unwrap();
+        assert_eq!(Ok(()), Customers::check(&store, &another));
+
+        Customers::set_access(&mut store, CustomerAccess::AllowList).unwrap();
+        assert_eq!(Ok(()), Customers::check(&store, &listed));
+        assert_eq!(
+            Err(ContractError::CustomerNotAllowed(another.clone())),
+            Customers::check(&store, &another)
+        );
+
+        Customers::remove(&mut store, vec![listed.clone()]);
+        assert_eq!(
+            Err(ContractError::CustomerNotAllowed(listed.clone())),
+            Customers::check(&store, &listed)
+        );
+    }
+
+    #[test]
+    fn deny_list() {
+        let mut store = MockStorage::default();
+        let listed = Addr::unchecked("customer1");
+        let another = Addr::unchecked("customer2");
+
+        Customers::set_access(&mut store, CustomerAccess::DenyList).unwrap();
+        Customers::add(&mut store, vec![listed.clone()]).unwrap();
+        assert_eq!(
+            Err(ContractError::CustomerNotAllowed(listed.clone())),
+            Customers::check(&store, &listed)
+        );
+        assert_eq!(Ok(()), Customers::check(&store, &another));
+
+        Customers::set_access(&mut store, CustomerAccess::Open).unwrap();
+        assert_eq!(Ok(()), Customers::check(&store, &listed));
+    }
+
+    #[test]
+    fn load_page() {
+        let mut store = MockStorage::default();
+        let customer1 = Addr::unchecked("customer1");
+        let customer2 = Addr::unchecked("customer2");
+        let customer3 = Addr::unchecked("customer3");
+
+        Customers::add(
+            &mut store,
+            vec![customer3.clone(), customer1.clone(), customer2.clone()],
+        )
+        .unwrap();
+        assert_eq!(
+            Ok(vec![customer1.clone(), customer2.clone()]),
+            Customers::load_page(&store, None, 2)
+        );
+        assert_eq!(
+            Ok(vec![customer3.clone()]),
+            Customers::load_page(&store, Some(customer2), 2)
+        );
+        assert_eq!(Ok(vec![]), Customers::load_page(&store, Some(customer3), 2));
+    }
+}
@@ -6,7 +6,9 @@ use sdk::{
 };
 
 use crate::{
+    error::ContractError,
     migrate::{Customer, MaybeCustomer},
+    msg::{LeaseEntry, MaxLeases, PageSize},
     result::ContractResult,
 };
 
@@ -15,6 +17,7 @@ pub(crate) struct Leases {}
 impl Leases {
     const PENDING_CUSTOMER: Item<Addr> = Item::new("pending_customer");
     const CUSTOMER_LEASES: Map<Addr, HashSet<Addr>> = Map::new("loans");
+    const TOTAL: Item<MaxLeases> = Item::new("leases_total");
 
     pub fn cache_open_req(storage: &mut dyn Storage, customer: &Addr) -> ContractResult<()> {
         Self::PENDING_CUSTOMER
@@ -37,8 +40,9 @@ impl Leases {
             .load(storage)
             .inspect(|_| Self::PENDING_CUSTOMER.remove(storage))
             .and_then(|customer| Self::CUSTOMER_LEASES.update(storage, customer, update_fn))
-            .map(|_| stored)
             .map_err(Into::into)
+            .and_then(|_| Self::count_in(storage, stored))
+            .map(|()| stored)
     }
 
     pub fn load_by_customer(
@@ -62,8 +66,121 @@ impl Leases {
 
         Self::CUSTOMER_LEASES
             .update(storage, customer, update_fn)
-            .map(|_| removed)
             .map_err(Into::into)
+            .and_then(|_| Self::count_out(storage, removed))
+            .map(|()| removed)
+    }
+
+    /// Move the lease from the `customer` leases to the `new_customer` ones
+    ///
+    /// Fail if the lease is not present under the `customer`.
+    pub fn change_owner(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        customer: Addr,
+        new_customer: Addr,
+    ) -> ContractResult<()> {
+        Self::remove(storage, customer.clone(), &lease).and_then(|removed| {
+            if removed {
+                let mut inserted = false;
+                Self::CUSTOMER_LEASES
+                    .update(
+                        storage,
+                        new_customer,
+                        |may_leases: Option<HashSet<Addr>>| -> StdResult<HashSet<Addr>> {
+                            let mut leases = may_leases.unwrap_or_default();
+                            inserted = leases.insert(lease);
+                            Ok(leases)
+                        },
+                    )
+                    .map_err(Into::into)
+                    .and_then(|_| Self::count_in(storage, inserted))
+            } else {
+                Err(ContractError::UnknownLease(lease, customer))
+            }
+        })
+    }
+
+    /// Return up to `limit` leases of the `customer` ordered by address
+    ///
+    /// The page starts after the `start_after` lease, if provided.
+    pub fn load_page_by_customer(
+        storage: &dyn Storage,
+        customer: Addr,
+        start_after: Option<Addr>,
+        limit: PageSize,
+    ) -> ContractResult<Vec<Addr>> {
+        let limit = usize::try_from(limit)?;
+        Self::load_by_customer(storage, customer).map(|leases| {
+            Self::sorted_after(leases, start_after.as_ref())
+                .take(limit)
+                .collect()
+        })
+    }
+
+    /// Return up to `limit` leases of all customers ordered by customer and lease address
+    ///
+    /// The page starts after the `start_after` entry, if provided.
+    pub fn load_page(
+        storage: &dyn Storage,
+        start_after: Option<LeaseEntry>,
+        limit: PageSize,
+    ) -> ContractResult<Vec<LeaseEntry>> {
+        let limit = usize::try_from(limit)?;
+        let (start_customer, start_lease) = start_after
+            .map(|entry| (Some(entry.customer), Some(entry.lease)))
+            .unwrap_or_default();
+
+        let mut page = Vec::with_capacity(limit);
+        let customers = Self::CUSTOMER_LEASES.range(
+            storage,
+            start_customer.clone().map(Bound::inclusive),
+            None,
+            Order::Ascending,
+        );
+        for record in customers {
+            if page.len() == limit {
+                break;
+            }
+            let (customer, leases) = record?;
+            let after = start_lease
+                .as_ref()
+                .filter(|_| Some(&customer) == start_customer.as_ref());
+            let remaining = limit - page.len();
+            page.extend(
+                Self::sorted_after(leases, after)
+                    .take(remaining)
+                    .map(|lease| LeaseEntry {
+                        customer: customer.clone(),
+                        lease,
+                    }),
+            );
+        }
+        Ok(page)
+    }
+
+    pub fn count(storage: &dyn Storage) -> ContractResult<MaxLeases> {
+        Self::TOTAL
+            .may_load(storage)
+            .map(Option::unwrap_or_default)
+            .map_err(Into::into)
+    }
+
+    /// Count the leases stored so far
+    ///
+    /// Intended to initialize the total on the migration from a storage
+    /// that does not maintain it.
+    pub fn init_count(storage: &mut dyn Storage) -> ContractResult<()> {
+        let total = Self::CUSTOMER_LEASES
+            .range(storage, None, None, Order::Ascending)
+            .try_fold(0, |total: MaxLeases, record| -> ContractResult<MaxLeases> {
+                record.map_err(Into::into).and_then(|(_customer, leases)| {
+                    MaxLeases::try_from(leases.len())
+                        .map(|leases| total + leases)
+                        .map_err(Into::into)
+                })
+            })?;
+        Self::TOTAL.save(storage, &total).map_err(Into::into)
     }
 
     pub fn iter(
@@ -80,13 +197,47 @@ impl Leases {
                     .map_err(Into::into)
             })
     }
+
+    fn sorted_after(
+        leases: HashSet<Addr>,
+        start_after: Option<&Addr>,
+    ) -> impl Iterator<Item = Addr> {
+        let mut leases: Vec<Addr> = leases
+            .into_iter()
+            .filter(|lease| start_after.is_none_or(|start_after| lease > start_after))
+            .collect();
+        leases.sort();
+        leases.into_iter()
+    }
+
+    fn count_in(storage: &mut dyn Storage, counted: bool) -> ContractResult<()> {
+        Self::update_count(storage, counted, |total| total + 1)
+    }
+
+    fn count_out(storage: &mut dyn Storage, counted: bool) -> ContractResult<()> {
+        Self::update_count(storage, counted, |total| total.saturating_sub(1))
+    }
+
+    fn update_count<F>(storage: &mut dyn Storage, counted: bool, f: F) -> ContractResult<()>
+    where
+        F: FnOnce(MaxLeases) -> MaxLeases,
+    {
+        if counted {
+            Self::count(storage)
+                .and_then(|total| Self::TOTAL.save(storage, &f(total)).map_err(Into::into))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
     use sdk::cosmwasm_std::{testing::MockStorage, Addr, Storage};
 
-    use crate::{state::leases::Leases, ContractError};
+    use crate::{msg::LeaseEntry, state::leases::Leases, ContractError};
 
     #[test]
     fn test_save_customer_not_cached() {
@@ -162,11 +313,183 @@ mod test {
         assert_lease_not_exist(&storage);
     }
 
+    #[test]
+    fn test_change_owner() {
+        let mut storage = MockStorage::default();
+
+        Leases::cache_open_req(&mut storage, &test_customer()).unwrap();
+        Leases::save(&mut storage, test_lease()).unwrap();
+        Leases::cache_open_req(&mut storage, &test_customer()).unwrap();
+        Leases::save(&mut storage, test_another_lease()).unwrap();
+
+        assert_eq!(
+            Ok(()),
+            Leases::change_owner(
+                &mut storage,
+                test_lease(),
+                test_customer(),
+                test_another_customer()
+            )
+        );
+        assert_lease_not_exist(&storage);
+        assert!(lease_exist(&storage, &test_another_lease()));
+        assert_eq!(
+            HashSet::from([test_lease()]),
+            Leases::load_by_customer(&storage, test_another_customer()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_change_owner_not_exist() {
+        let mut storage = MockStorage::default();
+        Leases::cache_open_req(&mut storage, &test_customer()).unwrap();
+        Leases::save(&mut storage, test_another_lease()).unwrap();
+
+        assert_eq!(
+            Err(ContractError::UnknownLease(test_lease(), test_customer())),
+            Leases::change_owner(
+                &mut storage,
+                test_lease(),
+                test_customer(),
+                test_another_customer()
+            )
+        );
+        assert_eq!(
+            HashSet::from([test_another_lease()]),
+            Leases::load_by_customer(&storage, test_customer()).unwrap()
+        );
+        assert_eq!(
+            HashSet::new(),
+            Leases::load_by_customer(&storage, test_another_customer()).unwrap()
+        );
+        assert_eq!(Ok(1), Leases::count(&storage));
+    }
+
+    #[test]
+    fn test_count() {
+        let mut storage = MockStorage::default();
+        assert_eq!(Ok(0), Leases::count(&storage));
+
+        Leases::cache_open_req(&mut storage, &test_customer()).unwrap();
+        Leases::save(&mut storage, test_lease()).unwrap();
+        Leases::cache_open_req(&mut storage, &test_customer()).unwrap();
+        Leases::save(&mut storage, test_lease()).unwrap();
+        Leases::cache_open_req(&mut storage, &test_another_customer()).unwrap();
+        Leases::save(&mut storage, test_another_lease()).unwrap();
+        assert_eq!(Ok(2), Leases::count(&storage));
+
+        Leases::change_owner(
+            &mut storage,
+            test_lease(),
+            test_customer(),
+            test_another_customer(),
+        )
+        .unwrap();
+        assert_eq!(Ok(2), Leases::count(&storage));
+
+        Leases::remove(&mut storage, test_customer(), &test_lease()).unwrap();
+        assert_eq!(Ok(2), Leases::count(&storage));
+        Leases::remove(&mut storage, test_another_customer(), &test_lease()).unwrap();
+        assert_eq!(Ok(1), Leases::count(&storage));
+    }
+
+    #[test]
+    fn test_init_count() {
+        let mut storage = MockStorage::default();
+        Leases::init_count(&mut storage).unwrap();
+        assert_eq!(Ok(0), Leases::count(&storage));
+
+        save_leases(&mut storage);
+        Leases::init_count(&mut storage).unwrap();
+        assert_eq!(Ok(3), Leases::count(&storage));
+    }
+
+    #[test]
+    fn test_load_page_by_customer() {
+        let mut storage = MockStorage::default();
+        save_leases(&mut storage);
+
+        assert_eq!(
+            Ok(vec![test_lease(), test_another_lease()]),
+            Leases::load_page_by_customer(&storage, test_customer(), None, 10)
+        );
+        assert_eq!(
+            Ok(vec![test_lease()]),
+            Leases::load_page_by_customer(&storage, test_customer(), None, 1)
+        );
+        assert_eq!(
+            Ok(vec![test_another_lease()]),
+            Leases::load_page_by_customer(&storage, test_customer(), Some(test_lease()), 1)
+        );
+        assert_eq!(
+            Ok(vec![]),
+            Leases::load_page_by_customer(&storage, test_customer(), Some(test_another_lease()), 1)
+        );
+        assert_eq!(
+            Ok(vec![]),
+            Leases::load_page_by_customer(&storage, Addr::unchecked("customerZ"), None, 10)
+        );
+    }
+
+    #[test]
+    fn test_load_page() {
+        let mut storage = MockStorage::default();
+        assert_eq!(Ok(vec![]), Leases::load_page(&storage, None, 10));
+
+        save_leases(&mut storage);
+        let all = vec![
+            lease_entry(test_customer(), test_lease()),
+            lease_entry(test_customer(), test_another_lease()),
+            lease_entry(test_another_customer(), test_third_lease()),
+        ];
+        assert_eq!(Ok(all.clone()), Leases::load_page(&storage, None, 10));
+        assert_eq!(Ok(all[..2].to_vec()), Leases::load_page(&storage, None, 2));
+        assert_eq!(
+            Ok(all[1..].to_vec()),
+            Leases::load_page(&storage, Some(all[0].clone()), 2)
+        );
+        assert_eq!(
+            Ok(all[2..].to_vec()),
+            Leases::load_page(&storage, Some(all[1].clone()), 2)
+        );
+        assert_eq!(
+            Ok(vec![]),
+            Leases::load_page(&storage, Some(all[2].clone()), 2)
+        );
+    }
+
+    fn save_leases(storage: &mut dyn Storage) {
+        [
+            (test_customer(), test_another_lease()),
+            (test_another_customer(), test_third_lease()),
+            (test_customer(), test_lease()),
+        ]
+        .into_iter()
+        .for_each(|(customer, lease)| {
+            Leases::cache_open_req(storage, &customer).unwrap();
+            Leases::save(storage, lease).unwrap();
+        });
+    }
+
+    fn lease_entry(customer: Addr, lease: Addr) -> LeaseEntry {
+        LeaseEntry { customer, lease }
+    }
+
+    fn test_third_lease() -> Addr {
+        const LEASE: &str = "lease3";
+        Addr::unchecked(LEASE)
+    }
+
     fn test_customer() -> Addr {
         const CUSTOMER: &str = "customerX";
         Addr::unchecked(CUSTOMER)
     }
 
+    fn test_another_customer() -> Addr {
+        const CUSTOMER: &str = "customerY";
+        Addr::unchecked(CUSTOMER)
+    }
+
     fn test_lease() -> Addr {
         const LEASE: &str = "lease1";
         Addr::unchecked(LEASE)
@@ -1,2 +1,4 @@
 pub(crate) mod config;
+pub(crate) mod customer_access;
 pub(crate) mod leases;
+pub(crate) mod open_interest;
@@ -0,0 +1,653 @@
+use serde::{Deserialize, Serialize};
+
+use currency::CurrencyDTO;
+use finance::coin::Amount;
+use sdk::{
+    cosmwasm_std::{Addr, StdResult, Storage},
+    cw_storage_plus::{Item, Map, PrimaryKey},
+};
+
+use crate::{finance::LeaseCurrencies, result::ContractResult};
+
+use super::config::OpenInterestCaps;
+
+/// The borrow of the open leases aggregated per lease currency and per customer
+///
+/// A lease is accounted for with the amount borrowed on its opening. A lease opened
+/// at a limit price has not borrowed yet, so it is accounted for once it gets its loan.
+/// The leases opened before the introduction of the accounting are not part of it,
+/// and they are released from it without effect on closing.
+pub(crate) struct OpenInterest {}
+
+#[derive(Serialize, Deserialize)]
+struct OpenReq {
+    customer: Addr,
+    currency: CurrencyDTO<LeaseCurrencies>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LeaseBorrow {
+    customer: Addr,
+    currency: CurrencyDTO<LeaseCurrencies>,
+    borrow: Amount,
+}
+
+impl OpenInterest {
+    const PENDING_REQ: Item<OpenReq> = Item::new("pending_open_interest");
+    const LEASES: Map<Addr, LeaseBorrow> = Map::new("lease_borrow");
+    const PER_CURRENCY: Map<String, Amount> = Map::new("currency_open_interest");
+    const PER_CUSTOMER: Map<Addr, Amount> = Map::new("customer_open_interest");
+
+    pub fn cache_open_req(
+        storage: &mut dyn Storage,
+        customer: &Addr,
+        currency: &CurrencyDTO<LeaseCurrencies>,
+    ) -> ContractResult<()> {
+        Self::PENDING_REQ
+            .save(
+                storage,
+                &OpenReq {
+                    customer: customer.clone(),
+                    currency: *currency,
+                },
+            )
+            .map_err(Into::into)
+    }
+
+    /// Account the `borrow` of a just opened lease
+    ///
+    /// Return `ContractError::OpenInterestCapExceeded` if the new open interest
+    /// in the lease currency, or of the customer, exceeds the `caps`.
+    ///
+    /// A zero `borrow` denotes a lease waiting to get its loan. It is kept aside
+    /// until `save_borrow` is called.
+    pub fn save(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        borrow: Amount,
+        caps: &OpenInterestCaps,
+    ) -> ContractResult<()> {
+        let OpenReq { customer, currency } = Self::PENDING_REQ.load(storage)?;
+        Self::PENDING_REQ.remove(storage);
+
+        let lease_borrow = LeaseBorrow {
+            customer,
+            currency,
+            borrow,
+        };
+        if borrow == Amount::default() {
+            Self::LEASES
+                .save(storage, lease, &lease_borrow)
+                .map_err(Into::into)
+        } else {
+            Self::account(storage, lease, lease_borrow, caps)
+        }
+    }
+
+    /// Account the `borrow` of a lease that has just got its loan
+    ///
+    /// Return `ContractError::OpenInterestCapExceeded` if the new open interest
+    /// in the lease currency, or of the customer, exceeds the `caps`.
+    pub fn save_borrow(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        borrow: Amount,
+        caps: &OpenInterestCaps,
+    ) -> ContractResult<()> {
+        Self::LEASES
+            .may_load(storage, lease.clone())?
+            .filter(|lease_borrow| lease_borrow.borrow == Amount::default())
+            .map_or(Ok(()), |lease_borrow| {
+                Self::account(
+                    storage,
+                    lease,
+                    LeaseBorrow {
+                        borrow,
+                        ..lease_borrow
+                    },
+                    caps,
+                )
+            })
+    }
+
+    /// Release the borrow of a closed lease
+    pub fn remove(storage: &mut dyn Storage, lease: Addr) -> ContractResult<()> {
+        Self::LEASES
+            .may_load(storage, lease.clone())?
+            .map_or(Ok(()), |lease_borrow| {
+                Self::LEASES.remove(storage, lease);
+                Self::sub(
+                    storage,
+                    &Self::PER_CURRENCY,
+                    lease_borrow.currency.to_string(),
+                    lease_borrow.borrow,
+                )
+                .and_then(|()| {
+                    Self::sub(
+                        storage,
+                        &Self::PER_CUSTOMER,
+                        lease_borrow.customer,
+                        lease_borrow.borrow,
+                    )
+                })
+            })
+    }
+
+    /// Move the borrow of a lease to its new owner
+    ///
+    /// The per customer cap is not enforced on the `new_customer` since the transfer
+    /// has already been accepted by the lease.
+    pub fn change_owner(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        new_customer: Addr,
+    ) -> ContractResult<()> {
+        Self::LEASES
+            .may_load(storage, lease.clone())?
+            .map_or(Ok(()), |lease_borrow| {
+                Self::sub(
+                    storage,
+                    &Self::PER_CUSTOMER,
+                    lease_borrow.customer,
+                    lease_borrow.borrow,
+                )
+                .and_then(|()| {
+                    Self::add(
+                        storage,
+                        &Self::PER_CUSTOMER,
+                        new_customer.clone(),
+                        lease_borrow.borrow,
+                    )
+                })
+                .and_then(|_| {
+                    Self::LEASES
+                        .save(
+                            storage,
+                            lease,
+                            &LeaseBorrow {
+                                customer: new_customer,
+                                ..lease_borrow
+                            },
+                        )
+                        .map_err(Into::into)
+                })
+            })
+    }
+
+    /// Check whether a new lease borrowing `borrow` would fit into the `caps`
+    ///
+    /// The per customer cap is checked only if the `customer` is known.
+    pub fn check(
+        storage: &dyn Storage,
+        currency: &CurrencyDTO<LeaseCurrencies>,
+        customer: Option<&Addr>,
+        borrow: Amount,
+        caps: &OpenInterestCaps,
+    ) -> ContractResult<()> {
+        Self::PER_CURRENCY
+            .may_load(storage, currency.to_string())
+            .map_err(Into::into)
+            .and_then(|total| caps.check_currency(currency, total.unwrap_or_default() + borrow))
+            .and_then(|()| {
+                customer.map_or(Ok(()), |customer| {
+                    Self::PER_CUSTOMER
+                        .may_load(storage, customer.clone())
+                        .map_err(Into::into)
+                        .and_then(|total| {
+                            caps.check_customer(customer, total.unwrap_or_default() + borrow)
+                        })
+                })
+            })
+    }
+
+    fn account(
+        storage: &mut dyn Storage,
+        lease: Addr,
+        lease_borrow: LeaseBorrow,
+        caps: &OpenInterestCaps,
+    ) -> ContractResult<()> {
+        let currency_total = Self::add(
+            storage,
+            &Self::PER_CURRENCY,
+            lease_borrow.currency.to_string(),
+            lease_borrow.borrow,
+        )?;
+        let customer_total = Self::add(
+            storage,
+            &Self::PER_CUSTOMER,
+            lease_borrow.customer.clone(),
+            lease_borrow.borrow,
+        )?;
+        caps.check_currency(&lease_borrow.currency, currency_total)
+            .and_then(|()| caps.check_customer(&lease_borrow.customer, customer_total))
+            .and_then(|()| {
+                Self::LEASES
+                    .save(storage, lease, &lease_borrow)
+                    .map_err(Into::into)
+            })
+    }
+
+    fn add<'k, K>(
+        storage: &mut dyn Storage,
+        totals: &Map<K, Amount>,
+        key: K,
+        amount: Amount,
+    ) -> ContractResult<Amount>
+    where
+        K: PrimaryKey<'k>,
+    {
+        totals
+            .update(storage, key, |total| -> StdResult<Amount> {
+                Ok(total.unwrap_or_default() + amount)
+            })
+            .map_err(Into::into)
+    }
+
+    fn sub<'k, K>(
+        storage: &mut dyn Storage,
+        totals: &Map<K, Amount>,
+        key: K,
+        amount: Amount,
+    ) -> ContractResult<()>
+    where
+        K: PrimaryKey<'k> + Clone,
+    {
+        let total = totals.may_load(storage, key.clone())?.unwrap_or_default();
+        debug_assert!(amount <= total);
+        let rest = total.saturating_sub(amount);
+        if rest == Amount::default() {
+            totals.remove(storage, key);
+            Ok(())
+        } else {
+            totals.save(storage, key, &rest).map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use currencies::{
+        testing::{LeaseC1, LeaseC2},
+        Lpn,
+    };
+    use currency::CurrencyDTO;
+    use finance::coin::{Amount, Coin};
+    use sdk::cosmwasm_std::{testing::MockStorage, Addr, Storage};
+
+    use crate::{
+        finance::LeaseCurrencies,
+        state::{config::OpenInterestCaps, open_interest::OpenInterest},
+        ContractError,
+    };
+
+    const CURRENCY_CAP: Amount = 1_000;
+    const CUSTOMER_CAP: Amount = 600;
+
+    #[test]
+    fn save_customer_not_cached() {
+        let mut storage = MockStorage::default();
+        assert!(matches!(
+            OpenInterest::save(&mut storage, test_lease(), 100, &caps()),
+            Err(ContractError::Std { .. })
+        ));
+    }
+
+    #[test]
+    fn save_and_remove() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            400,
+        )
+        .unwrap();
+        open(
+            &mut storage,
+            test_another_customer(),
+            test_currency(),
+            test_another_lease(),
+            500,
+        )
+        .unwrap();
+        assert_check(&storage, test_currency(), None, 100);
+        assert_cap_exceeded(
+            &storage,
+            test_currency(),
+            None,
+            101,
+            test_currency().to_string(),
+        );
+        assert_check(&storage, test_currency(), Some(&test_customer()), 100);
+
+        OpenInterest::remove(&mut storage, test_lease()).unwrap();
+        assert_check(&storage, test_currency(), Some(&test_customer()), 500);
+        OpenInterest::remove(&mut storage, test_another_lease()).unwrap();
+        assert_check(&storage, test_currency(), None, CURRENCY_CAP);
+    }
+
+    #[test]
+    fn remove_not_accounted() {
+        let mut storage = MockStorage::default();
+        OpenInterest::remove(&mut storage, test_lease()).unwrap();
+        assert_check(&storage, test_currency(), None, CURRENCY_CAP);
+    }
+
+    #[test]
+    fn save_zero_borrow() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            0,
+        )
+        .unwrap();
+        assert_check(
+            &storage,
+            test_currency(),
+            Some(&test_customer()),
+            CUSTOMER_CAP,
+        );
+    }
+
+    #[test]
+    fn save_borrow_pending() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            0,
+        )
+        .unwrap();
+
+        OpenInterest::save_borrow(&mut storage, test_lease(), 500, &caps()).unwrap();
+        assert_check(&storage, test_currency(), Some(&test_customer()), 100);
+        assert_cap_exceeded(
+            &storage,
+            test_currency(),
+            Some(&test_customer()),
+            101,
+            test_customer().to_string(),
+        );
+
+        // the lease has already been accounted for
+        OpenInterest::save_borrow(&mut storage, test_lease(), 500, &caps()).unwrap();
+        assert_check(&storage, test_currency(), Some(&test_customer()), 100);
+
+        OpenInterest::remove(&mut storage, test_lease()).unwrap();
+        assert_check(
+            &storage,
+            test_currency(),
+            Some(&test_customer()),
+            CUSTOMER_CAP,
+        );
+    }
+
+    #[test]
+    fn save_borrow_cap_exceeded() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            Err(ContractError::OpenInterestCapExceeded(
+                test_customer().to_string()
+            )),
+            OpenInterest::save_borrow(&mut storage, test_lease(), CUSTOMER_CAP + 1, &caps())
+        );
+    }
+
+    #[test]
+    fn save_borrow_not_accounted() {
+        let mut storage = MockStorage::default();
+        OpenInterest::save_borrow(&mut storage, test_lease(), 500, &caps()).unwrap();
+        assert_check(
+            &storage,
+            test_currency(),
+            Some(&test_customer()),
+            CUSTOMER_CAP,
+        );
+    }
+
+    #[test]
+    fn currency_cap_exceeded() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            500,
+        )
+        .unwrap();
+        open(
+            &mut storage,
+            test_another_customer(),
+            test_another_currency(),
+            test_another_lease(),
+            500,
+        )
+        .unwrap();
+        assert_eq!(
+            Err(ContractError::OpenInterestCapExceeded(
+                test_currency().to_string()
+            )),
+            open(
+                &mut storage,
+                test_another_customer(),
+                test_currency(),
+                Addr::unchecked("lease3"),
+                501
+            )
+        );
+    }
+
+    #[test]
+    fn currency_not_capped() {
+        let mut storage = MockStorage::default();
+        let caps = OpenInterestCaps {
+            per_currency: BTreeMap::from([(
+                test_currency(),
+                Coin::<Lpn>::from(CURRENCY_CAP).into(),
+            )]),
+            per_customer: None,
+        };
+        OpenInterest::cache_open_req(&mut storage, &test_customer(), &test_another_currency())
+            .unwrap();
+        OpenInterest::save(&mut storage, test_lease(), CURRENCY_CAP + 1, &caps).unwrap();
+        assert_eq!(
+            Ok(()),
+            OpenInterest::check(
+                &storage,
+                &test_another_currency(),
+                None,
+                CURRENCY_CAP,
+                &caps
+            )
+        );
+        assert_eq!(
+            Err(ContractError::OpenInterestCapExceeded(
+                test_currency().to_string()
+            )),
+            OpenInterest::check(&storage, &test_currency(), None, CURRENCY_CAP + 1, &caps)
+        );
+    }
+
+    #[test]
+    fn customer_cap_exceeded() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            500,
+        )
+        .unwrap();
+        assert_cap_exceeded(
+            &storage,
+            test_another_currency(),
+            Some(&test_customer()),
+            101,
+            test_customer().to_string(),
+        );
+        assert_eq!(
+            Err(ContractError::OpenInterestCapExceeded(
+                test_customer().to_string()
+            )),
+            open(
+                &mut storage,
+                test_customer(),
+                test_another_currency(),
+                test_another_lease(),
+                101
+            )
+        );
+    }
+
+    #[test]
+    fn no_caps() {
+        let mut storage = MockStorage::default();
+        OpenInterest::cache_open_req(&mut storage, &test_customer(), &test_currency()).unwrap();
+        OpenInterest::save(
+            &mut storage,
+            test_lease(),
+            CURRENCY_CAP + 1,
+            &OpenInterestCaps::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            Ok(()),
+            OpenInterest::check(
+                &storage,
+                &test_currency(),
+                Some(&test_customer()),
+                CURRENCY_CAP,
+                &OpenInterestCaps::default()
+            )
+        );
+    }
+
+    #[test]
+    fn change_owner() {
+        let mut storage = MockStorage::default();
+        open(
+            &mut storage,
+            test_customer(),
+            test_currency(),
+            test_lease(),
+            500,
+        )
+        .unwrap();
+
+        OpenInterest::change_owner(&mut storage, test_lease(), test_another_customer()).unwrap();
+        assert_check(
+            &storage,
+            test_another_currency(),
+            Some(&test_customer()),
+            CUSTOMER_CAP,
+        );
+        assert_cap_exceeded(
+            &storage,
+            test_another_currency(),
+            Some(&test_another_customer()),
+            101,
+            test_another_customer().to_string(),
+        );
+
+        OpenInterest::remove(&mut storage, test_lease()).unwrap();
+        assert_check(
+            &storage,
+            test_currency(),
+            Some(&test_another_customer()),
+            CUSTOMER_CAP,
+        );
+    }
+
+    fn open(
+        storage: &mut dyn Storage,
+        customer: Addr,
+        currency: CurrencyDTO<LeaseCurrencies>,
+        lease: Addr,
+        borrow: Amount,
+    ) -> Result<(), ContractError> {
+        OpenInterest::cache_open_req(storage, &customer, &currency)
+            .and_then(|()| OpenInterest::save(storage, lease, borrow, &caps()))
+    }
+
+    #[track_caller]
+    fn assert_check(
+        storage: &dyn Storage,
+        currency: CurrencyDTO<LeaseCurrencies>,
+        customer: Option<&Addr>,
+        borrow: Amount,
+    ) {
+        assert_eq!(
+            Ok(()),
+            OpenInterest::check(storage, &currency, customer, borrow, &caps())
+        );
+    }
+
+    #[track_caller]
+    fn assert_cap_exceeded(
+        storage: &dyn Storage,
+        currency: CurrencyDTO<LeaseCurrencies>,
+        customer: Option<&Addr>,
+        borrow: Amount,
+        subject: String,
+    ) {
+        assert_eq!(
+            Err(ContractError::OpenInterestCapExceeded(subject)),
+            OpenInterest::check(storage, &currency, customer, borrow, &caps())
+        );
+    }
+
+    fn caps() -> OpenInterestCaps {
+        OpenInterestCaps {
+            per_currency: BTreeMap::from([
+                (test_currency(), Coin::<Lpn>::from(CURRENCY_CAP).into()),
+                (
+                    test_another_currency(),
+                    Coin::<Lpn>::from(CURRENCY_CAP).into(),
+                ),
+            ]),
+            per_customer: Some(Coin::<Lpn>::from(CUSTOMER_CAP).into()),
+        }
+    }
+
+    fn test_currency() -> CurrencyDTO<LeaseCurrencies> {
+        currency::dto::<LeaseC1, _>()
+    }
+
+    fn test_another_currency() -> CurrencyDTO<LeaseCurrencies> {
+        currency::dto::<LeaseC2, _>()
+    }
+
+    fn test_customer() -> Addr {
+        Addr::unchecked("customerX")
+    }
+
+    fn test_another_customer() -> Addr {
+        Addr::unchecked("customerY")
+    }
+
+    fn test_lease() -> Addr {
+        Addr::unchecked("lease1")
+    }
+
+    fn test_another_lease() -> Addr {
+        Addr::unchecked("lease2")
+    }
+}
@@ -1,9 +1,14 @@
+use std::collections::{BTreeMap, HashSet};
+
 use cosmwasm_std::{
     testing::{MockApi, MockQuerier, MockStorage},
     OwnedDeps,
 };
 
-use currencies::{testing::LeaseC1, LeaseGroup, Lpn};
+use currencies::{
+    testing::{LeaseC1, LeaseC2},
+    LeaseGroup, Lpn,
+};
 use currency::{CurrencyDTO, CurrencyDef as _};
 use finance::{
     coin::{Amount, Coin},
@@ -16,6 +21,7 @@ use lease::api::{
     LpnCoinDTO,
 };
 use platform::contract::{Code, CodeId};
+use versioning::{SemVer, Version};
 
 use sdk::{
     cosmwasm_std::{
@@ -26,10 +32,12 @@ use sdk::{
 };
 
 use crate::{
-    cmd::Borrow,
-    contract::{execute, instantiate, query, sudo},
-    msg::{ConfigResponse, ExecuteMsg, QueryMsg, SudoMsg},
-    state::config::Config,
+    cmd::{Borrow, LeaseTerms},
+    contract::{execute, instantiate, migrate, query, sudo},
+    error::ContractError,
+    msg::{AssetSpec, CustomerAccess, LeaseEntry, MaxLeases, OpenInterestCaps},
+    msg::{ConfigResponse, ExecuteMsg, MigrateMsg, QueryMsg, SudoMsg},
+    state::{config::Config, leases::Leases},
 };
 
 const CREATOR: &str = "creator";
@@ -159,6 +167,8 @@ fn test_update_config() {
         lease_interest_rate_margin: Percent::from_percent(5),
         lease_position_spec: expected_position_spec,
         lease_due_period: expected_due_period,
+        lease_asset_specs: vec![],
+        open_interest_caps: OpenInterestCaps::default(),
     };
 
     sudo(deps.as_mut(), testing::mock_env(), msg).unwrap();
@@ -168,6 +178,114 @@ fn test_update_config() {
     assert_eq!(expected_due_period, config.lease_due_period);
 }
 
+#[test]
+fn test_update_config_asset_specs() {
+    let mut deps = deps();
+
+    setup_test_case(deps.as_mut());
+    let default_config = query_config(deps.as_ref());
+
+    let asset_spec = AssetSpec {
+        currency: lease_currency(),
+        position_spec: PositionSpecDTO::new(
+            Liability::new(
+                Percent::from_percent(40),
+                Percent::from_percent(45),
+                Percent::from_percent(50),
+                Percent::from_percent(52),
+                Percent::from_percent(54),
+                Percent::from_percent(55),
+                Duration::from_hours(1),
+            ),
+            lpn_coin(10_000),
+            lpn_coin(100),
+        ),
+        interest_rate_margin: Percent::from_percent(7),
+    };
+    let msg = SudoMsg::Config {
+        lease_interest_rate_margin: default_config.lease_interest_rate_margin,
+        lease_position_spec: default_config.lease_position_spec,
+        lease_due_period: default_config.lease_due_period,
+        lease_asset_specs: vec![asset_spec.clone()],
+        open_interest_caps: OpenInterestCaps::default(),
+    };
+    sudo(deps.as_mut(), testing::mock_env(), msg).unwrap();
+
+    let config = query_config(deps.as_ref());
+    assert_eq!(vec![asset_spec.clone()], config.lease_asset_specs);
+    assert_eq!(
+        asset_spec.position_spec,
+        config.lease_position_spec_of(&lease_currency())
+    );
+    assert_eq!(
+        asset_spec.interest_rate_margin,
+        config.lease_interest_rate_margin_of(&lease_currency())
+    );
+
+    let other_currency = currency::dto::<LeaseC2, _>();
+    assert_eq!(
+        default_config.lease_position_spec,
+        config.lease_position_spec_of(&other_currency)
+    );
+    assert_eq!(
+        default_config.lease_interest_rate_margin,
+        config.lease_interest_rate_margin_of(&other_currency)
+    );
+}
+
+#[test]
+fn test_update_config_duplicate_asset_specs() {
+    let mut deps = deps();
+
+    setup_test_case(deps.as_mut());
+    let config = query_config(deps.as_ref());
+
+    let asset_spec = AssetSpec {
+        currency: lease_currency(),
+        position_spec: config.lease_position_spec,
+        interest_rate_margin: Percent::from_percent(7),
+    };
+    let msg = SudoMsg::Config {
+        lease_interest_rate_margin: config.lease_interest_rate_margin,
+        lease_position_spec: config.lease_position_spec,
+        lease_due_period: config.lease_due_period,
+        lease_asset_specs: vec![asset_spec.clone(), asset_spec],
+        open_interest_caps: OpenInterestCaps::default(),
+    };
+    assert_eq!(
+        ContractError::DuplicateAssetSpec(lease_currency().to_string()),
+        sudo(deps.as_mut(), testing::mock_env(), msg).unwrap_err()
+    );
+    assert_eq!(config, query_config(deps.as_ref()));
+}
+
+#[test]
+fn test_update_config_open_interest_caps() {
+    let mut deps = deps();
+
+    setup_test_case(deps.as_mut());
+    let default_config = query_config(deps.as_ref());
+    assert_eq!(
+        OpenInterestCaps::default(),
+        default_config.open_interest_caps
+    );
+
+    let caps = OpenInterestCaps {
+        per_currency: BTreeMap::from([(lease_currency(), lpn_coin(1_000_000))]),
+        per_customer: Some(lpn_coin(10_000)),
+    };
+    let msg = SudoMsg::Config {
+        lease_interest_rate_margin: default_config.lease_interest_rate_margin,
+        lease_position_spec: default_config.lease_position_spec,
+        lease_due_period: default_config.lease_due_period,
+        lease_asset_specs: vec![],
+        open_interest_caps: caps.clone(),
+    };
+    sudo(deps.as_mut(), testing::mock_env(), msg).unwrap();
+
+    assert_eq!(caps, query_config(deps.as_ref()).open_interest_caps);
+}
+
 fn open_lease_with(max_ltd: Option<Percent>) {
     let mut deps = deps();
 
@@ -178,6 +296,9 @@ fn open_lease_with(max_ltd: Option<Percent>) {
     let msg = ExecuteMsg::OpenLease {
         currency: lease_currency(),
         max_ltd,
+        max_slippage: None,
+        open_at: None,
+        basket: None,
     };
     let info = customer();
     let env = testing::mock_env();
@@ -185,7 +306,16 @@ fn open_lease_with(max_ltd: Option<Percent>) {
     let finalizer = admin.clone();
     let res = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
 
-    let msg = Borrow::open_lease_msg(info.sender, config, lease_currency(), max_ltd, finalizer);
+    let msg = Borrow::open_lease_msg(
+        info.sender,
+        config,
+        lease_currency(),
+        LeaseTerms {
+            max_ltd,
+            ..Default::default()
+        },
+        finalizer,
+    );
     assert_eq!(
         res.messages,
         vec![SubMsg::reply_on_success(
@@ -212,6 +342,261 @@ fn test_open_lease_with_max_ltd() {
     open_lease_with(Some(Percent::from_percent(5)));
 }
 
+#[test]
+fn test_open_lease_customer_access() {
+    let mut deps = deps();
+
+    setup_test_case(deps.as_mut());
+
+    let open_lease = || ExecuteMsg::OpenLease {
+        currency: lease_currency(),
+        max_ltd: None,
+        max_slippage: None,
+        open_at: None,
+        basket: None,
+    };
+    let customer = customer();
+
+    sudo(
+        deps.as_mut(),
+        testing::mock_env(),
+        SudoMsg::CustomerAccess {
+            access: CustomerAccess::AllowList,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        CustomerAccess::AllowList,
+        from_json::<CustomerAccess>(
+            query(
+                deps.as_ref(),
+                testing::mock_env(),
+                QueryMsg::CustomerAccess {}
+            )
+            .unwrap()
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        Err(ContractError::CustomerNotAllowed(customer.sender.clone())),
+        execute(
+            deps.as_mut(),
+            testing::mock_env(),
+            customer.clone(),
+            open_lease()
+        )
+    );
+
+    sudo(
+        deps.as_mut(),
+        testing::mock_env(),
+        SudoMsg::AddCustomers {
+            customers: vec![customer.sender.clone()],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![customer.sender.clone()],
+        from_json::<Vec<Addr>>(
+            query(
+                deps.as_ref(),
+                testing::mock_env(),
+                QueryMsg::CustomerList {
+                    start_after: None,
+                    limit: None,
+                }
+            )
+            .unwrap()
+        )
+        .unwrap()
+    );
+    execute(deps.as_mut(), testing::mock_env(), customer, open_lease()).unwrap();
+}
+
+#[test]
+fn test_change_lease_owner_customer_access() {
+    let lease = sdk_testing::user("lease");
+    let mut deps = sdk_testing::mock_deps_with_contracts([
+        sdk_testing::user(LPP_ADDR),
+        sdk_testing::user(TIMEALARMS_ADDR),
+        sdk_testing::user(PROFIT_ADDR),
+        sdk_testing::user(ORACLE_ADDR),
+        lease.clone(),
+    ]);
+
+    setup_test_case(deps.as_mut());
+    // the mock querier reports the contracts instantiated from code 2
+    Config::update_lease_code(deps.as_mut().storage, Code::unchecked(2)).unwrap();
+
+    let customer = customer().sender;
+    let new_customer = sdk_testing::user("new_customer");
+    Leases::cache_open_req(deps.as_mut().storage, &customer).unwrap();
+    Leases::save(deps.as_mut().storage, lease.clone()).unwrap();
+
+    sudo(
+        deps.as_mut(),
+        testing::mock_env(),
+        SudoMsg::CustomerAccess {
+            access: CustomerAccess::DenyList,
+        },
+    )
+    .unwrap();
+    sudo(
+        deps.as_mut(),
+        testing::mock_env(),
+        SudoMsg::AddCustomers {
+            customers: vec![new_customer.clone()],
+        },
+    )
+    .unwrap();
+
+    let change_owner = || ExecuteMsg::ChangeLeaseOwner {
+        customer: customer.clone(),
+        new_customer: new_customer.clone(),
+    };
+    let lease_info = MessageInfo {
+        sender: lease.clone(),
+        funds: vec![],
+    };
+    assert_eq!(
+        Err(ContractError::CustomerNotAllowed(new_customer.clone())),
+        execute(
+            deps.as_mut(),
+            testing::mock_env(),
+            lease_info.clone(),
+            change_owner()
+        )
+    );
+    assert_eq!(
+        Ok(HashSet::from([lease.clone()])),
+        Leases::load_by_customer(deps.as_ref().storage, customer.clone())
+    );
+
+    sudo(
+        deps.as_mut(),
+        testing::mock_env(),
+        SudoMsg::RemoveCustomers {
+            customers: vec![new_customer.clone()],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        testing::mock_env(),
+        lease_info,
+        change_owner(),
+    )
+    .unwrap();
+    assert_eq!(
+        Ok(HashSet::new()),
+        Leases::load_by_customer(deps.as_ref().storage, customer)
+    );
+    assert_eq!(
+        Ok(HashSet::from([lease])),
+        Leases::load_by_customer(deps.as_ref().storage, new_customer)
+    );
+}
+
+#[test]
+fn test_leases_pagination() {
+    let mut deps = deps();
+
+    setup_test_case(deps.as_mut());
+    let all = save_leases(deps.as_mut());
+
+    let all_leases = |start_after: Option<LeaseEntry>, limit| {
+        query(
+            deps.as_ref(),
+            testing::mock_env(),
+            QueryMsg::AllLeases { start_after, limit },
+        )
+        .and_then(|page| from_json::<Vec<LeaseEntry>>(page).map_err(Into::into))
+    };
+    assert_eq!(Ok(all.clone()), all_leases(None, None));
+    assert_eq!(Ok(all[..2].to_vec()), all_leases(None, Some(2)));
+    assert_eq!(
+        Ok(all[2..].to_vec()),
+        all_leases(Some(all[1].clone()), Some(2))
+    );
+    assert_eq!(
+        Err(ContractError::PageSizeExceeded(201, 200)),
+        all_leases(None, Some(201))
+    );
+
+    let customer_leases = |start_after: Option<Addr>, limit| {
+        query(
+            deps.as_ref(),
+            testing::mock_env(),
+            QueryMsg::CustomerLeases {
+                owner: all[0].customer.clone(),
+                start_after,
+                limit,
+            },
+        )
+        .and_then(|page| from_json::<Vec<Addr>>(page).map_err(Into::into))
+    };
+    assert_eq!(
+        Ok(vec![all[0].lease.clone()]),
+        customer_leases(None, Some(1))
+    );
+    assert_eq!(
+        Ok(vec![all[1].lease.clone()]),
+        customer_leases(Some(all[0].lease.clone()), Some(1))
+    );
+    assert_eq!(
+        Ok(vec![]),
+        customer_leases(Some(all[1].lease.clone()), Some(1))
+    );
+}
+
+#[test]
+fn test_migrate_init_leases_count() {
+    let mut deps = deps();
+
+    setup_test_case(deps.as_mut());
+    let all = save_leases(deps.as_mut());
+
+    // the storage before the migration neither maintains the total nor has the current version
+    deps.as_mut().storage.remove(b"leases_total");
+    versioning::initialize(&mut deps.storage, Version::new(4, SemVer::parse("0.9.4"))).unwrap();
+
+    migrate(deps.as_mut(), testing::mock_env(), MigrateMsg {}).unwrap();
+
+    assert_eq!(
+        MaxLeases::try_from(all.len()).unwrap(),
+        from_json::<MaxLeases>(
+            query(
+                deps.as_ref(),
+                testing::mock_env(),
+                QueryMsg::LeasesCount { owner: None }
+            )
+            .unwrap()
+        )
+        .unwrap()
+    );
+}
+
+/// Return the saved leases in the order they are paginated
+fn save_leases(deps: DepsMut<'_>) -> Vec<LeaseEntry> {
+    let all = vec![
+        lease_entry("customer1", "lease1"),
+        lease_entry("customer1", "lease2"),
+        lease_entry("customer2", "lease3"),
+    ];
+    for entry in all.iter().rev() {
+        Leases::cache_open_req(deps.storage, &entry.customer).unwrap();
+        assert_eq!(Ok(true), Leases::save(deps.storage, entry.lease.clone()));
+    }
+    all
+}
+
+fn lease_entry(customer: &str, lease: &str) -> LeaseEntry {
+    LeaseEntry {
+        customer: Addr::unchecked(customer),
+        lease: Addr::unchecked(lease),
+    }
+}
+
 fn deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
     sdk_testing::mock_deps_with_contracts([
         sdk_testing::user(LPP_ADDR),
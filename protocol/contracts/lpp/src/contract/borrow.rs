@@ -37,6 +37,29 @@ where
     Ok((loan, messages.into()))
 }
 
+pub(super) fn try_increase_loan<Lpn>(
+    mut deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    amount: Coin<Lpn>,
+) -> Result<(Coin<Lpn>, MessageResponse)>
+where
+    Lpn: 'static + CurrencyDef,
+{
+    let lease_addr = info.sender;
+    let mut lpp = LiquidityPool::<Lpn>::load(deps.storage)?;
+    lpp.validate_lease_addr(&deps.as_ref(), &lease_addr)?;
+
+    let sent_amount = lpp.try_increase_loan(&mut deps, &env, lease_addr.clone(), amount)?;
+
+    let mut bank = bank::account(&env.contract.address, deps.querier);
+    bank.send(sent_amount, lease_addr);
+
+    let messages: Batch = bank.into();
+
+    Ok((sent_amount, messages.into()))
+}
+
 pub(super) fn try_repay_loan<Lpn>(
     mut deps: DepsMut<'_>,
     env: Env,
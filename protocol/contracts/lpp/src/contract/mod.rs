@@ -110,6 +110,18 @@ pub fn execute(
             .and_then(|(loan_resp, message_response)| {
                 response::response_with_messages::<_, _, ContractError>(loan_resp, message_response)
             }),
+        ExecuteMsg::IncreaseLoan { amount } => amount
+            .try_into()
+            .map_err(Into::into)
+            .and_then(|amount_lpn| {
+                borrow::try_increase_loan::<LpnCurrency>(deps, env, info, amount_lpn)
+            })
+            .and_then(|(sent_amount, message_response)| {
+                response::response_with_messages::<_, _, ContractError>(
+                    sent_amount,
+                    message_response,
+                )
+            }),
         ExecuteMsg::RepayLoan() => borrow::try_repay_loan::<LpnCurrency>(deps, env, info).and_then(
             |(excess_amount, message_response)| {
                 response::response_with_messages::<_, _, ContractError>(
@@ -46,6 +46,9 @@ pub enum ContractError {
     #[error("[Lpp] Zero loan amount")]
     ZeroLoanAmount,
 
+    #[error("[Lpp] The loan increase does not cover the interest due")]
+    InsufficientLoanIncrease,
+
     #[error("[Lpp] Zero deposit")]
     ZeroDepositFunds,
 
@@ -6,7 +6,7 @@ use sdk::{
 };
 use serde::{Deserialize, Serialize};
 
-use finance::{coin::Coin, duration::Duration, interest, percent::Percent};
+use finance::{coin::Coin, duration::Duration, fraction::Fraction, interest, percent::Percent};
 use sdk::schemars::{self, JsonSchema};
 
 use crate::error::{ContractError, Result};
@@ -63,6 +63,38 @@ impl<Lpn> Loan<Lpn> {
         }
     }
 
+    /// Borrow an extra `amount` at `annual_interest_rate` by the specified time
+    ///
+    /// The interest due is paid off the `amount` first. The loan interest rate becomes
+    /// the average of the current and the new rate weighted by the principals.
+    /// Return the interest paid.
+    pub fn increase(
+        &mut self,
+        by: &Timestamp,
+        amount: Coin<Lpn>,
+        annual_interest_rate: Percent,
+    ) -> Coin<Lpn> {
+        let interest = self.interest_due(by);
+        debug_assert!(interest < amount);
+
+        let (paid_for, interest_change) = interest::pay(
+            self.annual_interest_rate,
+            self.principal_due,
+            interest,
+            self.due_period(by),
+        );
+        debug_assert!(interest_change.is_zero());
+        self.interest_paid += paid_for;
+
+        let principal_due = self.principal_due + amount;
+        self.annual_interest_rate = Percent::from_ratio(
+            self.annual_interest_rate.of(self.principal_due) + annual_interest_rate.of(amount),
+            principal_due,
+        );
+        self.principal_due = principal_due;
+        interest
+    }
+
     fn due_period(&self, by: &Timestamp) -> Duration {
         Duration::between(&self.interest_paid, by.max(&self.interest_paid))
     }
@@ -90,6 +122,7 @@ impl<Lpn> Loan<Lpn> {
                 .update(storage, addr, |loaded_loan| {
                     let mut loaded_loan = loaded_loan.ok_or(ContractError::NoLoan {})?;
                     loaded_loan.principal_due = loan.principal_due;
+                    loaded_loan.annual_interest_rate = loan.annual_interest_rate;
                     loaded_loan.interest_paid = loan.interest_paid;
 
                     Ok::<_, ContractError>(loaded_loan)
@@ -225,6 +258,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn increase() {
+        let principal_start = Coin::<Lpn>::from(1000);
+        let interest = Percent::from_percent(10);
+        let mut l = Loan {
+            principal_due: principal_start,
+            annual_interest_rate: interest,
+            interest_paid: Timestamp::from_nanos(200),
+        };
+
+        let at_first_year_end = l.interest_paid + Duration::YEAR;
+        let amount = 3000.into();
+        assert_eq!(
+            interest.of(principal_start),
+            l.increase(&at_first_year_end, amount, Percent::from_percent(30))
+        );
+        assert_eq!(
+            Loan {
+                principal_due: principal_start + amount,
+                annual_interest_rate: Percent::from_percent(25),
+                interest_paid: at_first_year_end
+            },
+            l
+        );
+    }
+
     mod persistence {
         use currencies::Lpn;
         use finance::{coin::Coin, duration::Duration, percent::Percent, zero::Zero};
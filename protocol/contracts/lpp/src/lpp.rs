@@ -256,6 +256,42 @@ where
         Ok(loan)
     }
 
+    /// return amount of lpp currency to send to lease_addr
+    ///
+    /// The interest due is paid off the borrowed `amount`.
+    pub(super) fn try_increase_loan(
+        &mut self,
+        deps: &mut DepsMut<'_>,
+        env: &Env,
+        lease_addr: Addr,
+        amount: Coin<Lpn>,
+    ) -> Result<Coin<Lpn>> {
+        let now = env.block.time;
+
+        let mut loan = Loan::load(deps.storage, lease_addr.clone())?;
+        if amount <= loan.interest_due(&now) {
+            return Err(ContractError::InsufficientLoanIncrease);
+        }
+
+        let annual_interest_rate =
+            match self.query_quote(amount, &env.contract.address, deps.querier, &now)? {
+                Some(rate) => Ok(rate),
+                None => Err(ContractError::NoLiquidity {}),
+            }?;
+
+        let loan_annual_interest_rate = loan.annual_interest_rate;
+        let interest_paid = loan.increase(&now, amount, annual_interest_rate);
+        Loan::save(deps.storage, lease_addr, loan)?;
+
+        self.total
+            .repay(now, interest_paid, Coin::ZERO, loan_annual_interest_rate);
+        self.total
+            .borrow(now, amount, annual_interest_rate)?
+            .store(deps.storage)?;
+
+        Ok(amount - interest_paid)
+    }
+
     /// return amount of lpp currency to pay back to lease_addr
     pub(super) fn try_repay_loan(
         &mut self,
@@ -336,6 +372,7 @@ mod test {
     use finance::{
         coin::{Amount, Coin},
         duration::Duration,
+        fraction::Fraction,
         percent::{bound::BoundToHundredPercent, Percent},
         price::{self, Price},
         zero::Zero,
@@ -584,6 +621,89 @@ mod test {
         assert_eq!(repay, 100u128.into());
     }
 
+    #[test]
+    fn increase_loan() {
+        let lpp_balance: Amount = 10_000_000;
+        let mut deps = testing::mock_dependencies_with_balance(&[coin_cw(lpp_balance)]);
+        let mut env = testing::mock_env();
+        let admin = Addr::unchecked("admin");
+        let lease_addr = Addr::unchecked("loan");
+        env.block.time = Timestamp::from_nanos(0);
+        let lease_code_id = Code::unchecked(123);
+
+        grant_admin_access(deps.as_mut(), &admin);
+
+        Config::new_unchecked(
+            lease_code_id,
+            InterestRate::new(
+                BASE_INTEREST_RATE,
+                UTILIZATION_OPTIMAL,
+                ADDON_OPTIMAL_INTEREST_RATE,
+            )
+            .expect("Couldn't construct interest rate value!"),
+            DEFAULT_MIN_UTILIZATION,
+        )
+        .store(deps.as_mut().storage)
+        .expect("Failed to store Config!");
+        Total::<TheCurrency>::new()
+            .store(deps.as_mut().storage)
+            .expect("can't initialize Total");
+
+        let mut lpp = LiquidityPool::<TheCurrency>::load(deps.as_mut().storage)
+            .expect("can't load LiquidityPool");
+
+        let amount = Coin::new(2_000_000);
+        let loan = lpp
+            .try_open_loan(&mut deps.as_mut(), &env, lease_addr.clone(), amount)
+            .expect("can't open loan");
+        deps.querier
+            .bank
+            .update_balance(MOCK_CONTRACT_ADDR, vec![coin_cw(lpp_balance - 2_000_000)]);
+
+        env.block.time = Timestamp::from_nanos(Duration::YEAR.nanos() / 10);
+        let interest = loan.interest_due(&env.block.time);
+
+        assert_eq!(
+            Err(ContractError::InsufficientLoanIncrease),
+            lpp.try_increase_loan(&mut deps.as_mut(), &env, lease_addr.clone(), interest)
+        );
+
+        let increase = Coin::new(3_000_000);
+        let increase_rate = lpp
+            .query_quote(
+                increase,
+                &env.contract.address,
+                deps.as_ref().querier,
+                &env.block.time,
+            )
+            .expect("can't query quote")
+            .expect("should have liquidity");
+        let sent = lpp
+            .try_increase_loan(&mut deps.as_mut(), &env, lease_addr.clone(), increase)
+            .expect("can't increase loan");
+        assert_eq!(increase - interest, sent);
+
+        let loan_increased = Loan::<TheCurrency>::query(deps.as_ref().storage, lease_addr)
+            .expect("can't query loan")
+            .expect("should be some response");
+        assert_eq!(amount + increase, loan_increased.principal_due);
+        assert_eq!(env.block.time, loan_increased.interest_paid);
+        assert_eq!(
+            Percent::from_ratio(
+                loan.annual_interest_rate.of(amount) + increase_rate.of(increase),
+                amount + increase
+            ),
+            loan_increased.annual_interest_rate
+        );
+        assert_eq!(
+            amount + increase,
+            LiquidityPool::<TheCurrency>::load(deps.as_ref().storage)
+                .expect("can't load LiquidityPool")
+                .total
+                .total_principal_due()
+        );
+    }
+
     #[test]
     fn try_open_loan_with_no_liquidity() {
         let mut deps = testing::mock_dependencies();
@@ -52,6 +52,12 @@ where
     OpenLoan {
         amount: CoinDTO<Lpns>,
     },
+    /// Borrow an extra amount paying off the interest due
+    ///
+    /// The amount less the interest is sent back to the lease.
+    IncreaseLoan {
+        amount: CoinDTO<Lpns>,
+    },
     RepayLoan(),
 
     Deposit(),
@@ -26,6 +26,12 @@ where
     /// and then, if there is any remaining amount, to repay the principal.
     /// Amount 0 is acceptable although does not change the loan.
     fn repay(&mut self, by: &Timestamp, repayment: Coin<Lpn>) -> RepayShares<Lpn>;
+    /// Borrow an extra `amount` by the specified time
+    ///
+    /// The due interest is paid off the `amount` which must exceed it.
+    /// Return the paid interest. The rest of the `amount` is sent to the lease.
+    /// The Lpp determines the new annual interest rate of the loan.
+    fn increase(&mut self, by: &Timestamp, amount: Coin<Lpn>) -> Coin<Lpn>;
     fn annual_interest_rate(&self) -> Percent;
 }
 
@@ -45,6 +51,7 @@ pub(super) struct LppLoanImpl<Lpn, Lpns> {
     lpp_ref: LppRef<Lpn, Lpns>,
     lpn: PhantomData<Lpn>,
     loan: Loan<Lpn>,
+    increase: Coin<Lpn>,
     repayment: Coin<Lpn>,
 }
 
@@ -57,6 +64,7 @@ where
             lpp_ref,
             lpn: PhantomData,
             loan,
+            increase: Default::default(),
             repayment: Default::default(),
         }
     }
@@ -80,6 +88,12 @@ where
         self.loan.repay(by, repayment)
     }
 
+    fn increase(&mut self, by: &Timestamp, amount: Coin<Lpn>) -> Coin<Lpn> {
+        self.increase += amount;
+        self.loan
+            .increase(by, amount, self.loan.annual_interest_rate)
+    }
+
     fn annual_interest_rate(&self) -> Percent {
         self.loan.annual_interest_rate
     }
@@ -95,6 +109,14 @@ where
 
     fn try_from(stub: LppLoanImpl<Lpn, Lpns>) -> StdResult<Self, Self::Error> {
         let mut batch = Batch::default();
+        if !stub.increase.is_zero() {
+            batch.schedule_execute_wasm_no_reply_no_funds(
+                stub.lpp_ref.addr().clone(),
+                &ExecuteMsg::<Lpns>::IncreaseLoan {
+                    amount: stub.increase.into(),
+                },
+            )?;
+        }
         if !stub.repayment.is_zero() {
             batch.schedule_execute_wasm_no_reply(
                 stub.lpp_ref.addr().clone(),
@@ -173,4 +195,38 @@ mod test {
             assert_eq!(exp, batch.batch);
         }
     }
+
+    #[test]
+    fn try_from_increase() {
+        let lpp_ref = LppRef::<Lpn, _>::unchecked("lpp_address");
+        let start = Timestamp::from_seconds(0);
+        let mut loan = LppLoanImpl::new(
+            lpp_ref.clone(),
+            Loan {
+                principal_due: Coin::<Lpn>::new(100),
+                annual_interest_rate: Percent::from_percent(12),
+                interest_paid: start,
+            },
+        );
+        let increase = 50.into();
+        assert_eq!(
+            Coin::<Lpn>::new(12),
+            loan.increase(&(start + Duration::YEAR), increase)
+        );
+        assert_eq!(Coin::<Lpn>::new(150), loan.principal_due());
+        let batch: LppBatch<LppRef<Lpn, Lpns>> = loan.try_into().unwrap();
+
+        assert_eq!(lpp_ref, batch.lpp_ref);
+        {
+            let mut exp = Batch::default();
+            exp.schedule_execute_wasm_no_reply_no_funds(
+                lpp_ref.addr().clone(),
+                &ExecuteMsg::<Lpns>::IncreaseLoan {
+                    amount: increase.into(),
+                },
+            )
+            .unwrap();
+            assert_eq!(exp, batch.batch);
+        }
+    }
 }
@@ -39,6 +39,12 @@ pub type Result<T> = StdResult<T, Error>;
 pub enum Error {
     #[error("[Oracle; Stub] Failed to query swap path! Cause: {0}")]
     StubSwapPathQuery(StdError),
+
+    #[error("[Oracle; Stub] Failed to query a base price! Cause: {0}")]
+    StubPriceQuery(StdError),
+
+    #[error("[Oracle; Stub] {0}")]
+    Finance(#[from] finance::error::Error),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, JsonSchema)]
@@ -1,5 +1,18 @@
-use currency::{Currency, CurrencyDTO, Group, MemberOf};
-use oracle_platform::OracleRef;
+use std::marker::PhantomData;
+
+use currency::{Currency, CurrencyDTO, CurrencyDef, Group, MemberOf};
+use finance::{
+    coin::{Amount, Coin},
+    price::{
+        self,
+        base::{
+            with_price::{self, WithPrice},
+            BasePrice,
+        },
+        Price,
+    },
+};
+use oracle_platform::{msg::BaseCurrencyQueryMsg, OracleRef};
 use sdk::cosmwasm_std::QuerierWrapper;
 
 use crate::api::swap::{Error, QueryMsg, Result, SwapTarget};
@@ -17,12 +30,25 @@ where
     where
         SwapIn: Group + MemberOf<SwapGroup>,
         SwapOut: Group + MemberOf<SwapGroup>;
+
+    /// Provides the amount of `to` an `amount_in` of `from` is worth at the current prices
+    fn amount_out<SwapIn, SwapOut>(
+        &self,
+        amount_in: Amount,
+        from: CurrencyDTO<SwapIn>,
+        to: CurrencyDTO<SwapOut>,
+        querier: QuerierWrapper<'_>,
+    ) -> Result<Amount>
+    where
+        SwapIn: Group + MemberOf<SwapGroup>,
+        SwapOut: Group + MemberOf<SwapGroup>;
 }
 
 impl<SwapGroup, OracleBase, OracleBaseG> SwapPath<SwapGroup> for OracleRef<OracleBase, OracleBaseG>
 where
     SwapGroup: Group,
-    OracleBase: Currency + MemberOf<OracleBaseG>,
+    OracleBase: CurrencyDef,
+    OracleBase::Group: MemberOf<SwapGroup> + MemberOf<SwapGroup::TopG> + MemberOf<OracleBaseG>,
     OracleBaseG: Group,
 {
     fn swap_path<SwapIn, SwapOut>(
@@ -45,4 +71,120 @@ where
             )
             .map_err(Error::StubSwapPathQuery)
     }
+
+    fn amount_out<SwapIn, SwapOut>(
+        &self,
+        amount_in: Amount,
+        from: CurrencyDTO<SwapIn>,
+        to: CurrencyDTO<SwapOut>,
+        querier: QuerierWrapper<'_>,
+    ) -> Result<Amount>
+    where
+        SwapIn: Group + MemberOf<SwapGroup>,
+        SwapOut: Group + MemberOf<SwapGroup>,
+    {
+        base_price(self, from.into_super_group::<SwapGroup>(), querier).and_then(|price_in| {
+            base_price(self, to.into_super_group::<SwapGroup>(), querier).and_then(|price_out| {
+                with_price::execute(
+                    &price_in,
+                    AmountInCmd {
+                        amount_in,
+                        price_out: &price_out,
+                    },
+                )
+            })
+        })
+    }
+}
+
+struct AmountInCmd<'price, SwapGroup, OracleBase, OracleBaseG>
+where
+    SwapGroup: Group,
+    OracleBase: CurrencyDef,
+    OracleBase::Group: MemberOf<OracleBaseG> + MemberOf<SwapGroup::TopG>,
+    OracleBaseG: Group,
+{
+    amount_in: Amount,
+    price_out: &'price BasePrice<SwapGroup, OracleBase, OracleBaseG>,
+}
+
+impl<SwapGroup, OracleBase, OracleBaseG> WithPrice<OracleBase>
+    for AmountInCmd<'_, SwapGroup, OracleBase, OracleBaseG>
+where
+    SwapGroup: Group,
+    OracleBase: CurrencyDef,
+    OracleBase::Group: MemberOf<OracleBaseG> + MemberOf<SwapGroup::TopG>,
+    OracleBaseG: Group,
+{
+    type PriceG = SwapGroup;
+
+    type Output = Amount;
+
+    type Error = Error;
+
+    fn exec<SwapIn>(self, price_in: Price<SwapIn, OracleBase>) -> Result<Self::Output>
+    where
+        SwapIn: CurrencyDef,
+        SwapIn::Group: MemberOf<Self::PriceG>,
+    {
+        with_price::execute(
+            self.price_out,
+            AmountOutCmd {
+                amount_in: Coin::<SwapIn>::new(self.amount_in),
+                price_in,
+                _swap_group: PhantomData::<SwapGroup>,
+            },
+        )
+    }
+}
+
+struct AmountOutCmd<SwapIn, OracleBase, SwapGroup> {
+    amount_in: Coin<SwapIn>,
+    price_in: Price<SwapIn, OracleBase>,
+    _swap_group: PhantomData<SwapGroup>,
+}
+
+impl<SwapIn, OracleBase, SwapGroup> WithPrice<OracleBase>
+    for AmountOutCmd<SwapIn, OracleBase, SwapGroup>
+where
+    SwapIn: 'static,
+    OracleBase: Currency,
+    SwapGroup: Group,
+{
+    type PriceG = SwapGroup;
+
+    type Output = Amount;
+
+    type Error = Error;
+
+    fn exec<SwapOut>(self, price_out: Price<SwapOut, OracleBase>) -> Result<Self::Output>
+    where
+        SwapOut: CurrencyDef,
+        SwapOut::Group: MemberOf<Self::PriceG>,
+    {
+        Ok(price::total(self.amount_in, self.price_in * price_out.inv()).into())
+    }
+}
+
+fn base_price<G, OracleBase, OracleBaseG>(
+    oracle: &OracleRef<OracleBase, OracleBaseG>,
+    currency: CurrencyDTO<G>,
+    querier: QuerierWrapper<'_>,
+) -> Result<BasePrice<G, OracleBase, OracleBaseG>>
+where
+    G: Group,
+    OracleBase: CurrencyDef,
+    OracleBase::Group: MemberOf<G> + MemberOf<G::TopG> + MemberOf<OracleBaseG>,
+    OracleBaseG: Group,
+{
+    if currency == *OracleBase::definition().dto() {
+        Ok(Price::<OracleBase, OracleBase>::identity().into())
+    } else {
+        querier
+            .query_wasm_smart(
+                oracle.addr().clone(),
+                &BaseCurrencyQueryMsg::BasePrice { currency },
+            )
+            .map_err(Error::StubPriceQuery)
+    }
 }
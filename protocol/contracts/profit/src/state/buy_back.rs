@@ -9,6 +9,7 @@ use dex::{
 use finance::{
     coin::{Coin, CoinDTO},
     duration::Duration,
+    percent::Percent,
 };
 use oracle::stub::SwapPath;
 use platform::{
@@ -91,6 +92,10 @@ impl SwapTask for BuyBack {
         currency::dto::<Nls, Self::OutG>()
     }
 
+    fn max_slippage(&self) -> Option<Percent> {
+        None
+    }
+
     fn on_coins<Visitor>(&self, visitor: &mut Visitor) -> Result<IterState, Visitor::Error>
     where
         Visitor: CoinVisitor<GIn = Self::InG, Result = IterNext>,
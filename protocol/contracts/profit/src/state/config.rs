@@ -1,4 +1,4 @@
-use currency::Group;
+use currency::{Group, MemberOf};
 use serde::{Deserialize, Serialize};
 
 use currencies::{Lpn as QuoteC, Lpns as QuoteG};
@@ -51,6 +51,7 @@ impl Config {
     pub fn oracle<GSwap>(&self) -> &impl SwapPath<GSwap>
     where
         GSwap: Group,
+        QuoteG: MemberOf<GSwap> + MemberOf<GSwap::TopG>,
     {
         &self.oracle
     }
@@ -21,7 +21,7 @@ pub use self::{
     out_remote::{start as start_local_remote, StartLocalRemoteState, State as StateRemoteOut},
     resp_delivery::{ICAOpenResponseDelivery, ResponseDelivery},
     response::{ContinueResult, Handler, Response, Result},
-    swap_coins::{on_coin, on_coins},
+    swap_coins::{on_coin, on_coins, on_coins_seq},
     swap_exact_in::SwapExactIn,
     swap_task::{CoinVisitor, CoinsNb, IterNext, IterState, SwapTask},
     transfer_in_finish::TransferInFinish,
@@ -33,6 +33,25 @@ where
     })
 }
 
+/// Visit the coins in order until the visitor stops the iteration
+pub fn on_coins_seq<'coin, G, Coins, Visitor>(
+    coins: Coins,
+    visitor: &mut Visitor,
+) -> Result<IterState, Visitor::Error>
+where
+    G: Group + MemberOf<Visitor::GIn> + 'coin,
+    Coins: IntoIterator<Item = &'coin CoinDTO<G>>,
+    Visitor: CoinVisitor<Result = IterNext>,
+{
+    let mut coins = coins.into_iter().peekable();
+    while let Some(coin) = coins.next() {
+        if visitor.visit(coin)? == IterNext::Stop && coins.peek().is_some() {
+            return Ok(IterState::Incomplete);
+        }
+    }
+    Ok(IterState::Complete)
+}
+
 #[cfg(test)]
 mod test {
     use std::marker::PhantomData;
@@ -147,6 +166,26 @@ mod test {
         assert!(v.second_visited(coin1().amount()));
     }
 
+    #[test]
+    fn visit_seq_stop_one() {
+        let mut v = TestVisitor::<SuperGroup, IterNext>::new(IterNext::Stop, IterNext::Continue);
+
+        let iter_res = never::safe_unwrap(super::on_coins_seq(&[coin1(), coin2()], &mut v));
+        assert_eq!(iter_res, IterState::Incomplete);
+        assert!(v.first_visited(coin1().amount()));
+        assert!(v.second_not_visited());
+    }
+
+    #[test]
+    fn visit_seq_continue() {
+        let mut v = TestVisitor::<SuperGroup, IterNext>::new(IterNext::Continue, IterNext::Stop);
+
+        let iter_res = never::safe_unwrap(super::on_coins_seq(&[coin2(), coin1()], &mut v));
+        assert_eq!(iter_res, IterState::Complete);
+        assert!(v.first_visited(coin2().amount()));
+        assert!(v.second_visited(coin1().amount()));
+    }
+
     #[test]
     fn visit_two_continue() {
         let mut v =
@@ -10,6 +10,7 @@ use currency::{CurrencyDTO, Group, MemberOf};
 use finance::{
     coin::{self, Amount, CoinDTO},
     duration::Duration,
+    percent::Percent,
     zero::Zero,
 };
 use platform::{batch::Batch, trx};
@@ -88,6 +89,7 @@ where
             SwapTrx<'a, SwapInOut, SwapPathImpl>,
             PhantomData<SwapIn>,
             CurrencyDTO<SwapOut>,
+            Option<Percent>,
             PhantomData<SwapClient>,
         )
         where
@@ -113,7 +115,7 @@ where
                 G: Group + MemberOf<Self::GIn>,
             {
                 self.0
-                    .swap_exact_in::<_, SwapIn, SwapOut, SwapClient>(*coin, self.2)?;
+                    .swap_exact_in::<_, SwapIn, SwapOut, SwapClient>(*coin, self.2, self.3)?;
                 Ok(IterNext::Continue)
             }
         }
@@ -122,6 +124,7 @@ where
             swap_trx,
             PhantomData::<SwapTask::InG>,
             self.spec.out_currency(),
+            self.spec.max_slippage(),
             PhantomData::<SwapClient>,
         );
 
@@ -1,5 +1,5 @@
 use currency::{CurrencyDTO, Group, MemberOf};
-use finance::coin::CoinDTO;
+use finance::{coin::CoinDTO, percent::Percent};
 use oracle::stub::SwapPath;
 use sdk::cosmwasm_std::{Env, QuerierWrapper};
 use timealarms::stub::TimeAlarmsRef;
@@ -25,6 +25,11 @@ pub trait SwapTask {
     fn time_alarm(&self) -> &TimeAlarmsRef;
     fn out_currency(&self) -> CurrencyDTO<Self::OutG>;
 
+    /// The maximum slippage of the swaps against the oracle prices
+    ///
+    /// `None` accepts any output amount.
+    fn max_slippage(&self) -> Option<Percent>;
+
     /// Call back the worker with each coin this swap is about.
     /// The iteration is done over the coins always in the same order.
     /// It continues either until there are no more coins or the worker has responded
@@ -2,8 +2,10 @@ use std::marker::PhantomData;
 
 use currency::{platform::Nls, CurrencyDTO, Group, MemberOf};
 use finance::{
-    coin::{Coin, CoinDTO},
+    coin::{Amount, Coin, CoinDTO},
     duration::Duration,
+    fraction::Fraction,
+    percent::Percent,
 };
 use oracle::stub::SwapPath;
 use platform::{
@@ -26,6 +28,9 @@ const ICA_TRANSFER_TIMEOUT_TIP: Coin<Nls> = ICA_TRANSFER_ACK_TIP;
 const ICA_SWAP_ACK_TIP: Coin<Nls> = Coin::new(1);
 const ICA_SWAP_TIMEOUT_TIP: Coin<Nls> = ICA_SWAP_ACK_TIP;
 
+// the DEX-es require a positive minimum out amount
+const MIN_AMOUNT_OUT: Amount = 1;
+
 pub(super) struct TransferOutTrx<'a> {
     sender: LocalSender<'a>,
 }
@@ -96,10 +101,15 @@ where
         }
     }
 
+    /// Swap `amount` for `currency_out`
+    ///
+    /// If a `max_slippage` is specified, the swap fails if it would return less than
+    /// the oracle price of `amount` decreased by `max_slippage`.
     pub fn swap_exact_in<GIn, SwapGIn, SwapGOut, SwapClient>(
         &mut self,
         amount: CoinDTO<GIn>,
         currency_out: CurrencyDTO<SwapGOut>,
+        max_slippage: Option<Percent>,
     ) -> Result<()>
     where
         GIn: Group + MemberOf<SwapGIn>,
@@ -107,23 +117,49 @@ where
         SwapGOut: Group + MemberOf<SwapGroup>,
         SwapClient: ExactAmountIn,
     {
-        self.swap_path
-            .swap_path(
-                amount.currency().into_super_group::<SwapGIn>(),
-                currency_out,
-                self.querier,
-            )
-            .map_err(Into::into)
-            .and_then(|swap_path| {
+        let currency_in = amount.currency().into_super_group::<SwapGIn>();
+        self.min_amount_out(amount.amount(), currency_in, currency_out, max_slippage)
+            .and_then(|min_amount_out| {
+                self.swap_path
+                    .swap_path(currency_in, currency_out, self.querier)
+                    .map_err(Into::into)
+                    .map(|swap_path| (min_amount_out, swap_path))
+            })
+            .and_then(|(min_amount_out, swap_path)| {
                 SwapClient::build_request(
                     &mut self.trx,
                     self.ica_account.clone(),
                     &amount,
+                    min_amount_out,
                     &swap_path,
                 )
                 .map_err(Into::into)
             })
     }
+
+    fn min_amount_out<SwapGIn, SwapGOut>(
+        &self,
+        amount_in: Amount,
+        currency_in: CurrencyDTO<SwapGIn>,
+        currency_out: CurrencyDTO<SwapGOut>,
+        max_slippage: Option<Percent>,
+    ) -> Result<Amount>
+    where
+        SwapGIn: Group + MemberOf<SwapGroup>,
+        SwapGOut: Group + MemberOf<SwapGroup>,
+    {
+        max_slippage.map_or(Ok(MIN_AMOUNT_OUT), |max_slippage| {
+            Percent::HUNDRED
+                .checked_sub(max_slippage)
+                .map_err(Into::into)
+                .and_then(|min_share| {
+                    self.swap_path
+                        .amount_out(amount_in, currency_in, currency_out, self.querier)
+                        .map(|amount_out| min_share.of(amount_out).max(MIN_AMOUNT_OUT))
+                        .map_err(Into::into)
+                })
+        })
+    }
 }
 
 impl<SwapGroup, SwapPathImpl> From<SwapTrx<'_, SwapGroup, SwapPathImpl>> for LocalBatch {
@@ -9,12 +9,16 @@ use sdk::{cosmos_sdk_proto::Any as CosmosAny, cosmwasm_std::StdError};
 pub trait ExactAmountIn {
     /// `swap_path` should be a non-empty list
     ///
+    /// The swap should fail if it would return less than `min_token_out`
+    /// of the last currency in the path.
+    ///
     /// `GIn` - the group of the input token
     /// `GSwap` - the group common for all tokens in the swap path
     fn build_request<GIn, GSwap>(
         trx: &mut Transaction,
         sender: HostAccount,
         token_in: &CoinDTO<GIn>,
+        min_token_out: Amount,
         swap_path: &SwapPath<GSwap>,
     ) -> Result<()>
     where
@@ -50,6 +50,7 @@ where
         trx: &mut Transaction,
         sender: HostAccount,
         token_in: &CoinDTO<GIn>,
+        min_token_out: Amount,
         swap_path: &SwapPath<GSwap>,
     ) -> Result<()>
     where
@@ -61,8 +62,8 @@ where
 
         cosmwasm_std::to_json_vec(&ExecuteMsg::ExecuteSwapOperations {
             operations: to_operations::<GSwap>(&token_in.denom, swap_path),
-            minimum_receive: None, // disable checks on the received amount
-            to: None,              // means the sender
+            minimum_receive: Some(min_token_out.into()),
+            to: None,                     // means the sender
             max_spread: Some(MAX_IMPACT), // if None that would be equivalent to `astroport::pair::DEFAULT_SLIPPAGE`, i.e. 0.5%
         })
         .map_err(Into::into)
@@ -26,6 +26,7 @@ where
     GSwap: Group,
 {
     pub token_in: CoinDTO<GIn>,
+    pub min_token_out: Amount,
     pub swap_path: SwapPath<GSwap>,
 }
 
@@ -93,6 +93,7 @@ impl Instantiator {
                 customer: config.customer,
                 currency: lease_currency,
                 max_ltd,
+                max_slippage: None,
                 position_spec: PositionSpecDTO::new(
                     Liability::new(
                         config.liability_init_percent,
@@ -115,6 +116,8 @@ impl Instantiator {
                 reserve: addresses.reserve,
                 time_alarms: addresses.time_alarms,
                 market_price_oracle: addresses.oracle,
+                open_at: None,
+                basket: None,
             },
             dex: config.dex,
             finalizer: addresses.finalizer,
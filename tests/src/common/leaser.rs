@@ -133,6 +133,7 @@ where
                 downpayment: test::funds::<_, DownpaymentC>(downpayment.into()),
                 lease_asset: currency::dto::<LeaseC, _>(),
                 max_ltd,
+                customer: None,
             },
         )
         .unwrap()
@@ -66,7 +66,32 @@ impl Instantiator {
         Lpn: CurrencyDef,
         Lpn::Group: MemberOf<Lpns>,
     {
-        let lpp_id = app.store_code(endpoints);
+        let lpp_code = app.store_code(endpoints);
+
+        Self::instantiate_code::<Lpn>(
+            app,
+            lpp_code,
+            lease_code,
+            init_balance,
+            borrow_rate,
+            min_utilization,
+        )
+    }
+
+    /// Instantiate an Lpp of an already stored code
+    #[track_caller]
+    pub fn instantiate_code<Lpn>(
+        app: &mut App,
+        lpp_code: Code,
+        lease_code: Code,
+        init_balance: &[CwCoin],
+        borrow_rate: InterestRate,
+        min_utilization: BoundToHundredPercent,
+    ) -> Addr
+    where
+        Lpn: CurrencyDef,
+        Lpn::Group: MemberOf<Lpns>,
+    {
         let lease_code_admin = LeaserInstantiator::expected_addr();
         let msg = InstantiateMsg {
             lpn: currency::dto::<Lpn, Lpns>(),
@@ -77,7 +102,7 @@ impl Instantiator {
         };
 
         app.instantiate(
-            lpp_id,
+            lpp_code,
             testing::user(ADMIN),
             &msg,
             init_balance,
@@ -0,0 +1,114 @@
+use lease::{api::ExecuteMsg, error::ContractError};
+use sdk::{
+    cosmwasm_std::{Addr, Coin as CwCoin},
+    cw_multi_test::AppResponse,
+    testing,
+};
+
+use crate::common::{
+    self,
+    test_case::{app::App, response::ResponseWithInterChainMsgs},
+    USER,
+};
+
+use super::{LeaseTestCase, PaymentCoin, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn deposit_withdraw() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let customer = testing::user(USER);
+    let budget = PaymentCoin::new(1_000_000_000);
+    let balance_before = lease_balance(&test_case, lease.clone());
+
+    () = try_deposit(&mut test_case.app, customer.clone(), lease.clone(), budget)
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+    assert_eq!(
+        vec![common::cwcoin(budget)],
+        lease_balance(&test_case, lease.clone())
+    );
+
+    let err =
+        try_withdraw(&mut test_case.app, testing::user("another"), lease.clone()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(ContractError::Unauthorized(_))
+    ));
+
+    () = try_withdraw(&mut test_case.app, customer.clone(), lease.clone())
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+    assert_eq!(balance_before, lease_balance(&test_case, lease.clone()));
+
+    let err = try_withdraw(&mut test_case.app, customer, lease).unwrap_err();
+    assert_eq!(
+        Some(&ContractError::AutoRepayEmpty()),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+#[test]
+fn nothing_overdue() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let budget = PaymentCoin::new(1_000_000_000);
+
+    () = try_deposit(
+        &mut test_case.app,
+        testing::user(USER),
+        lease.clone(),
+        budget,
+    )
+    .unwrap()
+    .ignore_response()
+    .unwrap_response();
+
+    let response: AppResponse = test_case
+        .app
+        .execute(
+            test_case.address_book.time_alarms().clone(),
+            lease.clone(),
+            &ExecuteMsg::TimeAlarm {},
+            &[],
+        )
+        .unwrap()
+        .unwrap_response();
+
+    assert!(!response
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-ls-auto-repay"));
+    assert_eq!(
+        vec![common::cwcoin(budget)],
+        lease_balance(&test_case, lease)
+    );
+}
+
+fn try_deposit(
+    app: &mut App,
+    sender: Addr,
+    lease: Addr,
+    amount: PaymentCoin,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    app.execute(
+        sender,
+        lease,
+        &ExecuteMsg::DepositAutoRepay(),
+        &[common::cwcoin(amount)],
+    )
+}
+
+fn try_withdraw(
+    app: &mut App,
+    sender: Addr,
+    lease: Addr,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    app.execute(sender, lease, &ExecuteMsg::WithdrawAutoRepay(), &[])
+}
+
+fn lease_balance(test_case: &LeaseTestCase, lease: Addr) -> Vec<CwCoin> {
+    test_case.app.query().query_all_balances(lease).unwrap()
+}
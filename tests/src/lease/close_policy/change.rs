@@ -1,21 +1,15 @@
 use ::lease::{
-    api::{
-        position::ChangeCmd,
-        query::{ClosePolicy, StateResponse},
-    },
+    api::{position::ChangeCmd, query::ClosePolicy},
     error::{ContractError, PositionError},
     CloseStrategy,
 };
 use anyhow::Error;
 use finance::{coin::Coin, percent::Percent};
-use sdk::{cosmwasm_std::Addr, testing};
+use sdk::testing;
 
 use crate::{
     common::{oracle, ADMIN},
-    lease::{
-        self, LeaseCurrency, LeaseTestCase, LeaserInstantiator, LpnCurrency, PaymentCurrency,
-        DOWNPAYMENT,
-    },
+    lease::{self, LeaseCurrency, LeaserInstantiator, LpnCurrency, PaymentCurrency, DOWNPAYMENT},
 };
 
 #[test]
@@ -154,7 +148,7 @@ fn tp_set() {
     let lease = lease::open_lease(&mut test_case, DOWNPAYMENT, None);
     assert_eq!(
         ClosePolicy::default(),
-        query_policy(&test_case, lease.clone())
+        super::query_policy(&test_case, lease.clone())
     );
 
     let tp = Percent::from_percent(28);
@@ -166,7 +160,7 @@ fn tp_set() {
     );
     assert_eq!(
         ClosePolicy::new(Some(tp), None),
-        query_policy(&test_case, lease.clone())
+        super::query_policy(&test_case, lease.clone())
     );
 
     // LeaseC/LpnC = 10/25
@@ -185,13 +179,6 @@ fn tp_set() {
     assert_trigger_tp_error(err, tp)
 }
 
-fn query_policy(test_case: &LeaseTestCase, lease: Addr) -> ClosePolicy {
-    let StateResponse::Opened { close_policy, .. } = lease::state_query(test_case, lease) else {
-        unreachable!()
-    };
-    close_policy
-}
-
 fn assert_trigger_tp_error(err: Error, exp_tp: Percent) {
     let Some(ContractError::PositionError(PositionError::TriggerClose {
         lease_ltv: _,
@@ -1,6 +1,7 @@
 use ::lease::{
     api::{
         position::{ChangeCmd, ClosePolicyChange},
+        query::{ClosePolicy, StateResponse},
         ExecuteMsg,
     },
     error::ContractError,
@@ -10,10 +11,11 @@ use sdk::{cosmwasm_std::Addr, cw_multi_test::AppResponse, testing};
 
 use crate::{
     common::{test_case::response::ResponseWithInterChainMsgs, ADMIN, USER},
-    lease::LeaseTestCase,
+    lease::{self, LeaseTestCase},
 };
 
 mod change;
+mod operator;
 mod trigger;
 
 fn change_ok(
@@ -87,3 +89,10 @@ fn send_change<'r>(
         &[],
     )
 }
+
+fn query_policy(test_case: &LeaseTestCase, lease: Addr) -> ClosePolicy {
+    let StateResponse::Opened { close_policy, .. } = lease::state_query(test_case, lease) else {
+        unreachable!()
+    };
+    *close_policy
+}
@@ -0,0 +1,126 @@
+use ::lease::{
+    api::{
+        position::{ChangeCmd, ClosePolicyChange, FullClose, PositionClose},
+        ExecuteMsg, OperatorPermission,
+    },
+    error::ContractError,
+};
+use access_control::error::Error as AccessError;
+use anyhow::Error;
+use sdk::{cosmwasm_std::Addr, cw_multi_test::AppResponse, testing};
+
+use crate::{
+    common::{test_case::response::ResponseWithInterChainMsgs, ADMIN, USER},
+    lease::{self, LeaseTestCase, PaymentCurrency, DOWNPAYMENT},
+};
+
+const OPERATOR: &str = "risk_bot";
+
+#[test]
+fn grant_by_another_user() {
+    let mut test_case = lease::create_test_case::<PaymentCurrency>();
+    let lease = lease::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let err = send_grant(&mut test_case, ADMIN, lease, None).unwrap_err();
+    assert_unauthorized(&err);
+}
+
+#[test]
+fn change_by_operator() {
+    let mut test_case = lease::create_test_case::<PaymentCurrency>();
+    let lease = lease::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    assert_unauthorized(&send_reset(&mut test_case, lease.clone()).unwrap_err());
+
+    () = send_grant(
+        &mut test_case,
+        USER,
+        lease.clone(),
+        Some(vec![OperatorPermission::ChangeClosePolicy]),
+    )
+    .unwrap()
+    .ignore_response()
+    .unwrap_response();
+
+    () = send_reset(&mut test_case, lease.clone())
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    let err = test_case
+        .app
+        .execute(
+            testing::user(OPERATOR),
+            lease.clone(),
+            &ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose { max_slippage: None })),
+            &[],
+        )
+        .unwrap_err();
+    assert_unauthorized(&err);
+
+    () = send_revoke(&mut test_case, lease.clone())
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    assert_unauthorized(&send_reset(&mut test_case, lease.clone()).unwrap_err());
+
+    let err = send_revoke(&mut test_case, lease).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::OperatorNotFound(testing::user(OPERATOR)))
+    );
+}
+
+fn send_reset(
+    test_case: &mut LeaseTestCase,
+    lease: Addr,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    super::send_change(
+        test_case,
+        OPERATOR,
+        lease,
+        ClosePolicyChange {
+            stop_loss: None,
+            take_profit: Some(ChangeCmd::Reset),
+        },
+    )
+}
+
+fn send_grant<'r>(
+    test_case: &'r mut LeaseTestCase,
+    sender: &str,
+    lease: Addr,
+    permissions: Option<Vec<OperatorPermission>>,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'r, AppResponse>> {
+    test_case.app.execute(
+        testing::user(sender),
+        lease,
+        &ExecuteMsg::GrantOperator {
+            operator: testing::user(OPERATOR),
+            permissions,
+        },
+        &[],
+    )
+}
+
+fn send_revoke(
+    test_case: &mut LeaseTestCase,
+    lease: Addr,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    test_case.app.execute(
+        testing::user(USER),
+        lease,
+        &ExecuteMsg::RevokeOperator {
+            operator: testing::user(OPERATOR),
+        },
+        &[],
+    )
+}
+
+fn assert_unauthorized(err: &Error) {
+    assert_eq!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::Unauthorized(AccessError::Unauthorized {}))
+    );
+}
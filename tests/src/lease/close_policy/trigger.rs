@@ -1,9 +1,15 @@
-use ::lease::api::position::ChangeCmd;
+use ::lease::api::{
+    position::ChangeCmd,
+    query::{ClosePolicy, StateResponse},
+    ExecuteMsg,
+};
 use ::swap::testing::SwapRequest;
 use currencies::PaymentGroup;
 use finance::{
     coin::{Amount, Coin},
+    duration::Duration,
     percent::Percent,
+    price,
 };
 use sdk::{
     cosmwasm_std::{Addr, Event},
@@ -13,8 +19,8 @@ use sdk::{
 use crate::{
     common::swap,
     lease::{
-        self, LeaseCurrency, LeaserInstantiator, LpnCurrency, PaymentCurrency, TestCase,
-        DOWNPAYMENT,
+        self, LeaseCurrency, LeaserInstantiator, LpnCurrency, PaymentCoin, PaymentCurrency,
+        TestCase, DOWNPAYMENT,
     },
 };
 
@@ -44,6 +50,137 @@ fn trigger_sl() {
     assert_events(&resp, &lease, "stop-loss-ltv", sl);
 }
 
+#[test]
+fn trigger_partial_tp() {
+    let mut test_case = lease::create_test_case::<PaymentCurrency>();
+
+    let tp = LeaserInstantiator::INITIAL_LTV;
+    // LeaseC/LpnC = 1
+    let lease = lease::open_lease(&mut test_case, DOWNPAYMENT, None);
+    super::change_ok(
+        &mut test_case,
+        lease.clone(),
+        Some(ChangeCmd::SetPartial {
+            ltv: tp,
+            close: Percent::from_percent(50),
+        }),
+        None,
+    );
+
+    // LeaseC/LpnC = 0.999999
+    let resp = trigger_close(test_case, 999999, 1000000);
+    assert_events(&resp, &lease, "take-profit-ltv", tp);
+}
+
+#[test]
+fn skip_partial_tp_below_min_transaction() {
+    let mut test_case = lease::create_test_case::<PaymentCurrency>();
+
+    let tp = LeaserInstantiator::INITIAL_LTV;
+    // LeaseC/LpnC = 1
+    let lease = lease::open_lease(&mut test_case, PaymentCoin::new(100), None);
+    super::change_ok(
+        &mut test_case,
+        lease.clone(),
+        Some(ChangeCmd::SetPartial {
+            ltv: tp,
+            close: Percent::from_percent(10),
+        }),
+        None,
+    );
+
+    // LeaseC/LpnC = 0.999999
+    let resp = lease::deliver_new_price(
+        &mut test_case,
+        Coin::<LeaseCurrency>::from(999999),
+        Coin::<LpnCurrency>::from(1000000),
+    )
+    .unwrap_response();
+    assert_events(&resp, &lease, "take-profit-ltv", tp);
+    assert!(resp.events.iter().any(|event| {
+        event.ty == "wasm-ls-auto-close-position"
+            && event.attributes.iter().any(|attr| attr.key == "skipped")
+    }));
+
+    assert_eq!(
+        ClosePolicy::default(),
+        super::query_policy(&test_case, lease)
+    );
+}
+
+#[test]
+fn drop_price_tp_on_full_repay() {
+    let mut test_case = lease::create_test_case::<PaymentCurrency>();
+
+    // LeaseC/LpnC = 1
+    let lease = lease::open_lease(&mut test_case, DOWNPAYMENT, None);
+    super::change_ok(
+        &mut test_case,
+        lease.clone(),
+        // LeaseC/LpnC = 1.1
+        Some(ChangeCmd::SetPrice(
+            price::total_of(Coin::<LeaseCurrency>::new(10))
+                .is(Coin::<LpnCurrency>::new(11))
+                .into(),
+        )),
+        None,
+    );
+
+    let borrowed_lpn = lease::quote_borrow(&test_case, DOWNPAYMENT);
+    let borrowed: PaymentCoin = price::total(borrowed_lpn, lease::price_lpn_of().inv());
+    lease::repay::repay(&mut test_case, lease.clone(), borrowed);
+
+    assert!(matches!(
+        lease::state_query(&test_case, lease),
+        StateResponse::Paid { .. }
+    ));
+}
+
+#[test]
+fn drop_price_sl_past_liquidation() {
+    let mut test_case = lease::create_test_case::<PaymentCurrency>();
+
+    // LeaseC/LpnC = 1
+    let lease = lease::open_lease(&mut test_case, DOWNPAYMENT, None);
+    super::change_ok(
+        &mut test_case,
+        lease.clone(),
+        None,
+        // LeaseC/LpnC = 0.813, the stop loss LTV is just below the liquidation one
+        Some(ChangeCmd::SetPrice(
+            price::total_of(Coin::<LeaseCurrency>::new(1000))
+                .is(Coin::<LpnCurrency>::new(813))
+                .into(),
+        )),
+    );
+    // the accrued interest takes the stop loss past the liquidation
+    test_case.app.time_shift(Duration::from_days(30));
+    lease::feed_price(&mut test_case);
+
+    let resp = test_case
+        .app
+        .execute(
+            test_case.address_book.time_alarms().clone(),
+            lease.clone(),
+            &ExecuteMsg::TimeAlarm {},
+            &[],
+        )
+        .unwrap()
+        .unwrap_response();
+    assert!(resp.events.iter().any(|event| {
+        event.ty == "wasm-ls-close-trigger-drop"
+            && event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "stop-loss-ltv")
+    }));
+
+    assert_eq!(
+        ClosePolicy::default(),
+        super::query_policy(&test_case, lease)
+    );
+}
+
 fn open_lease(test_case: &mut LeaseTestCase, tp: Option<Percent>, sl: Option<Percent>) -> Addr {
     // LeaseC/LpnC = 1
     let lease = lease::open_lease(test_case, DOWNPAYMENT, None);
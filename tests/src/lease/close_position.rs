@@ -2,12 +2,14 @@ use currencies::{testing::PaymentC5, LeaseGroup, PaymentGroup};
 use currency::CurrencyDef;
 use finance::{
     coin::{Amount, Coin},
+    fraction::Fraction,
+    percent::Percent,
     price,
     zero::Zero,
 };
 use lease::{
     api::{
-        position::{FullClose, PartialClose, PositionClose},
+        position::{FullClose, PartialClose, PartialCloseLtv, PartialClosePercent, PositionClose},
         query::StateResponse,
         ExecuteMsg,
     },
@@ -40,13 +42,14 @@ fn close_by_another_user() {
     assert_unauthorized(
         &mut test_case,
         lease.clone(),
-        ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose {})),
+        ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose { max_slippage: None })),
     );
     assert_unauthorized(
         &mut test_case,
         lease,
         ExecuteMsg::ClosePosition(PositionClose::PartialClose(PartialClose {
             amount: LeaseCoin::from(1234414).into(),
+            max_slippage: None,
         })),
     );
 }
@@ -63,7 +66,7 @@ fn full_close() {
         &mut test_case,
         &customer,
         lease_amount,
-        PositionClose::FullClose(FullClose {}),
+        PositionClose::FullClose(FullClose { max_slippage: None }),
         exp_loan_close,
         exp_change,
         LeaseCoin::ZERO,
@@ -88,6 +91,37 @@ fn full_close() {
     );
 }
 
+#[test]
+fn full_close_max_slippage() {
+    let lease_amount: LeaseCoin = lease_amount();
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let mut response_close = send_close(
+        &mut test_case,
+        lease,
+        &ExecuteMsg::ClosePosition(PositionClose::FullClose(FullClose {
+            max_slippage: Some(Percent::from_percent(5)),
+        })),
+    );
+    let requests: Vec<SwapRequest<PaymentGroup, PaymentGroup>> = common::swap::expect_swap(
+        &mut response_close,
+        TestCase::DEX_CONNECTION_ID,
+        TestCase::LEASE_ICA_ID,
+    );
+    () = response_close.unwrap_response();
+
+    let exp_min_token_out: LpnCoin =
+        Percent::from_percent(95).of(price::total(lease_amount, super::price_lpn_of()));
+    assert_eq!(
+        vec![Amount::from(exp_min_token_out)],
+        requests
+            .into_iter()
+            .map(|request| request.min_token_out)
+            .collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn partial_close_loan_not_closed() {
     let lease_amount: LeaseCoin = lease_amount();
@@ -107,6 +141,7 @@ fn partial_close_loan_not_closed() {
         close_amount,
         PositionClose::PartialClose(PartialClose {
             amount: close_amount.into(),
+            max_slippage: None,
         }),
         exp_loan_close,
         exp_change,
@@ -158,6 +193,7 @@ fn partial_close_loan_closed() {
         close_amount,
         PositionClose::PartialClose(PartialClose {
             amount: close_amount.into(),
+            max_slippage: None,
         }),
         exp_loan_close,
         exp_change,
@@ -167,6 +203,7 @@ fn partial_close_loan_closed() {
     assert_eq!(
         StateResponse::Paid {
             amount: (lease_amount - close_amount).into(),
+            basket: vec![],
             in_progress: None
         },
         state
@@ -187,6 +224,112 @@ fn partial_close_loan_closed() {
     );
 }
 
+#[test]
+fn partial_close_percent() {
+    let lease_amount: LeaseCoin = lease_amount();
+    let close_percent = Percent::from_percent(10);
+    let close_amount: LeaseCoin = close_percent.of(lease_amount);
+    let repay_principal = price::total(close_amount, super::price_lpn_of());
+    let customer = testing::user(USER);
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+
+    let exp_loan_close = false;
+    let exp_change = LpnCoin::ZERO;
+    let lease = do_close(
+        &mut test_case,
+        &customer,
+        close_amount,
+        PositionClose::PartialClosePercent(PartialClosePercent {
+            percent: close_percent,
+            max_slippage: None,
+        }),
+        exp_loan_close,
+        exp_change,
+        lease_amount - close_amount,
+    );
+    let state = super::state_query(&test_case, lease.clone());
+    assert_eq!(
+        super::expected_open_state(
+            &test_case,
+            DOWNPAYMENT,
+            repay_principal,
+            close_amount,
+            Instantiator::REPAYMENT_PERIOD,
+        ),
+        state
+    );
+    assert_eq!(
+        lease_balance(&test_case, lease),
+        common::cwcoin_as_balance(exp_change),
+    );
+}
+
+#[test]
+fn partial_close_percent_min_transaction() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let msg = &ExecuteMsg::ClosePosition(PositionClose::PartialClosePercent(PartialClosePercent {
+        percent: Percent::ZERO,
+        max_slippage: None,
+    }));
+
+    let err = test_case
+        .app
+        .execute(testing::user(USER), lease, msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::PositionError(
+            PositionError::PositionCloseAmountTooSmall(Instantiator::min_transaction())
+        ))
+    );
+}
+
+#[test]
+fn partial_close_percent_min_asset() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let msg = &ExecuteMsg::ClosePosition(PositionClose::PartialClosePercent(PartialClosePercent {
+        percent: Percent::HUNDRED,
+        max_slippage: None,
+    }));
+
+    let err = test_case
+        .app
+        .execute(testing::user(USER), lease, msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::PositionError(
+            PositionError::PositionCloseAmountTooBig(Instantiator::min_asset())
+        ))
+    );
+}
+
+#[test]
+fn partial_close_ltv_not_below_current() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let msg = &ExecuteMsg::ClosePosition(PositionClose::PartialCloseLtv(PartialCloseLtv {
+        ltv: Percent::from_percent(95),
+        max_slippage: None,
+    }));
+
+    let err = test_case
+        .app
+        .execute(testing::user(USER), lease, msg, &[])
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::PositionError(
+            PositionError::CloseLtvNotBelowCurrent { .. }
+        ))
+    ));
+}
+
 #[test]
 fn partial_close_invalid_currency() {
     let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
@@ -200,6 +343,7 @@ fn partial_close_invalid_currency() {
             lease,
             &(&ExecuteMsg::ClosePosition(PositionClose::PartialClose(PartialClose {
                 amount: Coin::<PaymentC5>::from(12345678).into(),
+                max_slippage: None,
             }))),
             &[],
         )
@@ -227,6 +371,7 @@ fn partial_close_min_asset() {
     let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
     let msg = &ExecuteMsg::ClosePosition(PositionClose::PartialClose(PartialClose {
         amount: (lease_amount - min_asset + 1.into()).into(),
+        max_slippage: None,
     }));
 
     let err = test_case
@@ -251,6 +396,7 @@ fn partial_close_min_transaction() {
     let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
     let msg = &ExecuteMsg::ClosePosition(PositionClose::PartialClose(PartialClose {
         amount: (min_transaction - 1.into()).into(),
+        max_slippage: None,
     }));
 
     let err = test_case
@@ -265,6 +411,43 @@ fn partial_close_min_transaction() {
     );
 }
 
+#[test]
+fn repay_from_position_by_another_user() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    assert_unauthorized(
+        &mut test_case,
+        lease,
+        ExecuteMsg::RepayFromPosition {
+            amount: LeaseCoin::from(1234414).into(),
+        },
+    );
+}
+
+#[test]
+fn repay_from_position_min_transaction() {
+    let min_transaction_lpn = Instantiator::min_transaction().try_into().unwrap();
+    let min_transaction: LeaseCoin = price::total(min_transaction_lpn, super::price_lpn_of().inv());
+
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let msg = &ExecuteMsg::RepayFromPosition {
+        amount: (min_transaction - 1.into()).into(),
+    };
+
+    let err = test_case
+        .app
+        .execute(testing::user(USER), lease, msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::PositionError(
+            PositionError::PositionCloseAmountTooSmall(min_transaction_lpn.into())
+        ))
+    );
+}
+
 fn do_close(
     test_case: &mut LeaseTestCase,
     customer_addr: &Addr,
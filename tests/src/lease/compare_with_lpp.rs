@@ -1,4 +1,7 @@
-use ::lease::api::query::{ClosePolicy, StateResponse};
+use ::lease::api::{
+    open::LeaseKind,
+    query::{ClosePolicy, StateResponse},
+};
 use finance::{
     coin::{Amount, Coin},
     duration::Duration,
@@ -31,7 +34,9 @@ fn manual_calculation() {
 
     let query_result = super::state_query(&test_case, lease_address);
     let expected_result = StateResponse::Opened {
+        kind: LeaseKind::Long,
         amount: LeaseCoin::from(Amount::from(DOWNPAYMENT + 1_857_142_857_142.into())).into(),
+        basket: vec![],
         loan_interest_rate: quote_result.annual_interest_rate,
         margin_interest_rate: quote_result.annual_interest_rate_margin,
         principal_due: Coin::<LpnCurrency>::new(1_857_142_857_142).into(),
@@ -41,7 +46,9 @@ fn manual_calculation() {
         due_margin: LpnCoin::new(13_737_769_080).into(),
         due_interest: LpnCoin::new(32_054_794_520).into(),
         due_projection: Duration::default(),
-        close_policy: ClosePolicy::default(),
+        close_policy: Box::new(ClosePolicy::default()),
+        // the lease asset price has not been fed since the time shift
+        liability: None,
         validity: crate::block_time(&test_case),
         in_progress: None,
     };
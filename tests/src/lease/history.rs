@@ -0,0 +1,54 @@
+use finance::{fraction::Fraction, ratio::Rational};
+use lease::api::query::{HistoryResponse, QueryMsg};
+use sdk::cosmwasm_std::Addr;
+
+use super::{LeaseTestCase, PaymentCoin, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn open_and_repay() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let borrowed = super::quote_borrow(&test_case, DOWNPAYMENT);
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let payment: PaymentCoin = Fraction::<PaymentCoin>::of(
+        &Rational::new(1, 2),
+        super::create_payment_coin(borrowed.into()),
+    );
+    super::repay::repay(&mut test_case, lease.clone(), payment);
+
+    let records = query_history(&test_case, lease.clone(), None).records;
+    assert_eq!(
+        vec!["ls-open", "ls-repay"],
+        records
+            .iter()
+            .map(|record| record.event.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(records[1]
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "principal"));
+
+    assert_eq!(
+        records[1..],
+        query_history(&test_case, lease, Some(records[0].seq)).records
+    );
+}
+
+fn query_history(
+    test_case: &LeaseTestCase,
+    lease: Addr,
+    start_after: Option<u64>,
+) -> HistoryResponse {
+    test_case
+        .app
+        .query()
+        .query_wasm_smart(
+            lease,
+            &QueryMsg::History {
+                start_after,
+                limit: None,
+            },
+        )
+        .unwrap()
+}
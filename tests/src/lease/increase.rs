@@ -0,0 +1,118 @@
+use std::slice;
+
+use finance::price;
+use lease::{
+    api::{ExecuteMsg, LpnCoinDTO},
+    error::{ContractError, PositionError},
+};
+use sdk::{cosmwasm_std::Addr, testing};
+
+use crate::common::{
+    self, ibc, leaser::Instantiator as LeaserInstantiator, lpp::LppQueryMsg, test_case::TestCase,
+    CwCoin, USER,
+};
+
+use super::{LeaseTestCase, LpnCoin, LpnCurrency, PaymentCoin, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn insufficient_payment() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let err = test_case
+        .app
+        .execute(
+            testing::user(USER),
+            lease,
+            &ExecuteMsg::IncreasePosition { borrow: None },
+            &[common::cwcoin(super::create_payment_coin(49))],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ContractError>(),
+        Some(&ContractError::PositionError(
+            PositionError::InsufficientTransactionAmount(LeaserInstantiator::min_transaction())
+        ))
+    );
+}
+
+#[test]
+fn payment_transferred() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let payment = super::create_payment_coin(50);
+    assert_eq!(
+        common::cwcoin(payment),
+        send_increase(&mut test_case, lease, payment, None)
+    );
+}
+
+#[test]
+fn borrow_capped() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let principal = loan_principal(&test_case, &lease);
+
+    let payment = super::create_payment_coin(1_000_000);
+    let borrow = LpnCoin::new(1_000_000_000);
+    assert_eq!(
+        common::cwcoin(payment),
+        send_increase(&mut test_case, lease.clone(), payment, Some(borrow.into()))
+    );
+
+    // the position LTV is kept at the initial one
+    let equity = price::total(DOWNPAYMENT + payment, super::price_lpn_of());
+    let exp_principal = LeaserInstantiator::liability().init_borrow_amount(equity, None);
+    assert!(exp_principal - principal < borrow);
+    assert_eq!(exp_principal, loan_principal(&test_case, &lease));
+    // the payment and the borrowed amount are on the lease until transferred out
+    assert!(test_case
+        .app
+        .query()
+        .query_all_balances(lease)
+        .unwrap()
+        .contains(&common::cwcoin(exp_principal - principal)));
+}
+
+fn send_increase(
+    test_case: &mut LeaseTestCase,
+    lease: Addr,
+    payment: PaymentCoin,
+    borrow: Option<LpnCoinDTO>,
+) -> CwCoin {
+    let mut response = test_case
+        .app
+        .execute(
+            testing::user(USER),
+            lease.clone(),
+            &ExecuteMsg::IncreasePosition { borrow },
+            slice::from_ref(&common::cwcoin(payment)),
+        )
+        .unwrap()
+        .ignore_response();
+
+    let ica_addr: Addr = TestCase::ica_addr(&lease, TestCase::LEASE_ICA_ID);
+    let transfer_amount = ibc::expect_transfer(
+        &mut response,
+        TestCase::LEASER_IBC_CHANNEL,
+        lease.as_str(),
+        ica_addr.as_str(),
+    );
+    () = response.unwrap_response();
+    transfer_amount
+}
+
+fn loan_principal(test_case: &LeaseTestCase, lease: &Addr) -> LpnCoin {
+    let loan: lpp::msg::LoanResponse<LpnCurrency> = test_case
+        .app
+        .query()
+        .query_wasm_smart(
+            test_case.address_book.lpp().clone(),
+            &LppQueryMsg::Loan {
+                lease_addr: lease.clone(),
+            },
+        )
+        .unwrap();
+    loan.principal_due
+}
@@ -6,14 +6,20 @@ use currency::{Currency, CurrencyDef, MemberOf};
 use finance::{
     coin::{Amount, Coin},
     duration::Duration,
+    fraction::Fraction,
     interest,
+    liability::Level,
     percent::Percent,
     price::{self, Price},
 };
-use lease::api::query::{ClosePolicy, StateResponse};
+use lease::api::{
+    open::LeaseKind,
+    query::{ClosePolicy, LiabilityStatus, LiabilityZone, StateResponse, SteadyPrices},
+    AssetPriceDTO,
+};
 use leaser::msg::QuoteResponse;
 use sdk::{
-    cosmwasm_std::{coin, Addr},
+    cosmwasm_std::{coin, Addr, Timestamp},
     cw_multi_test::AppResponse,
     testing,
 };
@@ -30,14 +36,24 @@ use crate::common::{
     ADDON_OPTIMAL_INTEREST_RATE, ADMIN, BASE_INTEREST_RATE, USER, UTILIZATION_OPTIMAL,
 };
 
+mod auto_repay;
 mod close;
 mod close_policy;
 mod close_position;
 mod compare_with_lpp;
 mod heal;
+mod history;
+mod increase;
 mod liquidation;
+mod margin_buffer;
 mod open;
+mod open_limit;
+mod ownership;
+mod projection;
+mod refinance;
 mod repay;
+mod short;
+mod simulate;
 
 type LpnCurrency = Lpn;
 type LpnCoin = Coin<LpnCurrency>;
@@ -215,6 +231,9 @@ where
             &leaser::msg::ExecuteMsg::OpenLease {
                 currency: currency::dto::<LeaseCurrency, _>(),
                 max_ltd,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
             },
             downpayment.as_ref().map_or(&[], std::slice::from_ref),
         )
@@ -389,7 +408,7 @@ where
     DownpaymentC::Group: MemberOf<PaymentGroup>,
     PaymentC: Currency + MemberOf<PaymentGroup>,
     AssetC: CurrencyDef,
-    AssetC::Group: MemberOf<LeaseGroup>,
+    AssetC::Group: MemberOf<LeaseGroup> + MemberOf<PaymentGroup>,
 {
     let now = crate::block_time(test_case);
     let last_paid = now;
@@ -405,7 +424,9 @@ where
         Duration::between(&due_period_start, &now),
     );
     StateResponse::Opened {
+        kind: LeaseKind::Long,
         amount: (total - closed).into(),
+        basket: vec![],
         loan_interest_rate: quote_result.annual_interest_rate,
         margin_interest_rate: quote_result.annual_interest_rate_margin,
         principal_due: expected_principal.into(),
@@ -439,12 +460,51 @@ where
         )
         .into(),
         due_projection: Duration::default(),
-        close_policy: ClosePolicy::default(),
+        close_policy: Box::new(ClosePolicy::default()),
+        liability: Some(Box::new(expected_steady_liability(
+            total - closed,
+            expected_principal,
+            now,
+        ))),
         validity: now,
         in_progress: None,
     }
 }
 
+/// The liability of a position with no close policy and no overdue interest
+pub(super) fn expected_steady_liability<AssetC>(
+    amount: Coin<AssetC>,
+    total_due: LpnCoin,
+    now: Timestamp,
+) -> LiabilityStatus
+where
+    AssetC: CurrencyDef,
+    AssetC::Group: MemberOf<PaymentGroup>,
+{
+    let ltv = Percent::from_ratio(
+        price::total(total_due, price_lpn_of::<AssetC>().inv()),
+        amount,
+    );
+    let zone = LeaserInstantiator::liability().zone_of(ltv);
+    let ltv_to_price = |level: Level| -> AssetPriceDTO {
+        price::total_of(level.ltv().of(amount)).is(total_due).into()
+    };
+
+    LiabilityStatus::Steady {
+        ltv,
+        zone: LiabilityZone {
+            level: zone.low().map_or(0, Level::ordinal),
+            low: zone.low().map(Into::into),
+            high: zone.high().into(),
+        },
+        steady_prices: SteadyPrices {
+            above: ltv_to_price(zone.high()),
+            below_or_equal: zone.low().map(ltv_to_price),
+        },
+        next_check: now + LeaserInstantiator::RECALC_TIME,
+    }
+}
+
 pub(super) fn expected_newly_opened_state<
     ProtocolsRegistry,
     Treasury,
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use currencies::{LeaseGroup, PaymentGroup};
+use finance::{duration::Duration, price, test};
+use lease::{
+    api::{open::OpenLimit, query::StateResponse, ExecuteMsg},
+    error::ContractError,
+};
+use leaser::{
+    error::ContractError as LeaserError,
+    msg::{ConfigResponse, OpenInterestCaps, QueryMsg, QuoteResponse, SudoMsg},
+};
+use sdk::{
+    cosmwasm_std::{Addr, Event, Timestamp},
+    testing,
+};
+
+use crate::common::{
+    self, leaser as leaser_mod,
+    test_case::{response::RemoteChain, TestCase},
+    USER,
+};
+
+use super::{LeaseCoin, LeaseCurrency, LeaseTestCase, LpnCoin, PaymentCoin, PaymentCurrency};
+
+const DOWNPAYMENT: PaymentCoin = PaymentCoin::new(1_000_000_000);
+
+#[test]
+fn cancel_pending() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let customer = testing::user(USER);
+    let balance_before: PaymentCoin =
+        platform::bank::balance::<_, PaymentGroup>(&customer, test_case.app.query()).unwrap();
+
+    let open_at = open_limit(test_case.app.block_info().time + Duration::from_days(1));
+    let lease = open_pending(&mut test_case, open_at);
+    assert_eq!(
+        balance_before - DOWNPAYMENT,
+        platform::bank::balance::<_, PaymentGroup>(&customer, test_case.app.query()).unwrap()
+    );
+    assert_eq!(
+        StateResponse::Pending {
+            currency: currency::dto::<LeaseCurrency, _>(),
+            downpayment: DOWNPAYMENT.into(),
+            open_at,
+        },
+        super::state_query(&test_case, lease.clone())
+    );
+
+    let err = test_case
+        .app
+        .execute(
+            testing::user("another"),
+            lease.clone(),
+            &ExecuteMsg::Close(),
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(ContractError::Unauthorized(_))
+    ));
+
+    () = test_case
+        .app
+        .execute(customer.clone(), lease.clone(), &ExecuteMsg::Close(), &[])
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    assert_eq!(
+        StateResponse::Closed(),
+        super::state_query(&test_case, lease)
+    );
+    assert_eq!(
+        balance_before,
+        platform::bank::balance::<_, PaymentGroup>(&customer, test_case.app.query()).unwrap()
+    );
+    leaser_mod::assert_no_leases(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        customer,
+    );
+}
+
+#[test]
+fn open_interest_on_loan() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let borrow = super::quote_borrow(&test_case, DOWNPAYMENT);
+    set_currency_cap(&mut test_case, borrow + borrow - LpnCoin::new(1));
+
+    let open_at = open_limit(test_case.app.block_info().time + Duration::from_days(1));
+    let lease = open_pending(&mut test_case, open_at);
+    // the pending lease has not borrowed yet
+    assert_eq!(borrow, super::quote_borrow(&test_case, DOWNPAYMENT));
+
+    let mut response = super::deliver_new_price(&mut test_case, LeaseCoin::new(3), LpnCoin::new(1));
+    response.expect_register_ica(TestCase::DEX_CONNECTION_ID, TestCase::LEASE_ICA_ID);
+    () = response.ignore_response().unwrap_response();
+    assert!(!matches!(
+        super::state_query(&test_case, lease),
+        StateResponse::Pending { .. }
+    ));
+
+    let quote_err = test_case
+        .app
+        .query()
+        .query_wasm_smart::<QuoteResponse>(
+            test_case.address_book.leaser().clone(),
+            &QueryMsg::Quote {
+                downpayment: test::funds::<_, PaymentCurrency>(DOWNPAYMENT.into()),
+                lease_asset: currency::dto::<LeaseCurrency, _>(),
+                max_ltd: None,
+                customer: None,
+            },
+        )
+        .unwrap_err();
+    let cap_exceeded = LeaserError::OpenInterestCapExceeded(
+        currency::dto::<LeaseCurrency, LeaseGroup>().to_string(),
+    );
+    assert!(quote_err.to_string().contains(&cap_exceeded.to_string()));
+}
+
+#[test]
+fn cancel_on_cap_exceeded() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let customer = testing::user(USER);
+    let balance_before: PaymentCoin =
+        platform::bank::balance::<_, PaymentGroup>(&customer, test_case.app.query()).unwrap();
+    let borrow = super::quote_borrow(&test_case, DOWNPAYMENT);
+
+    let open_at = open_limit(test_case.app.block_info().time + Duration::from_days(1));
+    let lease = open_pending(&mut test_case, open_at);
+    set_currency_cap(&mut test_case, borrow - LpnCoin::new(1));
+
+    let resp = super::deliver_new_price(&mut test_case, LeaseCoin::new(3), LpnCoin::new(1))
+        .unwrap_response();
+    platform::tests::assert_event(
+        &resp.events,
+        &Event::new("wasm-ls-open-cancel")
+            .add_attribute("id", lease.clone())
+            .add_attribute("open-interest-cap-exceeded", "true"),
+    );
+
+    assert_eq!(
+        StateResponse::Closed(),
+        super::state_query(&test_case, lease)
+    );
+    assert_eq!(
+        balance_before,
+        platform::bank::balance::<_, PaymentGroup>(&customer, test_case.app.query()).unwrap()
+    );
+    leaser_mod::assert_no_leases(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        customer,
+    );
+}
+
+#[test]
+fn expired_on_open() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let open_at = open_limit(test_case.app.block_info().time);
+
+    let err = test_case
+        .app
+        .execute(
+            testing::user(USER),
+            test_case.address_book.leaser().clone(),
+            &leaser::msg::ExecuteMsg::OpenLease {
+                currency: currency::dto::<LeaseCurrency, _>(),
+                max_ltd: None,
+                max_slippage: None,
+                open_at: Some(open_at),
+                basket: None,
+            },
+            &[common::cwcoin(DOWNPAYMENT)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        Some(&ContractError::OpenLimitExpired()),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+fn set_currency_cap(test_case: &mut LeaseTestCase, cap: LpnCoin) {
+    let leaser = test_case.address_book.leaser().clone();
+    let ConfigResponse { config } = test_case
+        .app
+        .query()
+        .query_wasm_smart(leaser.clone(), &QueryMsg::Config {})
+        .unwrap();
+    () = test_case
+        .app
+        .sudo(
+            leaser,
+            &SudoMsg::Config {
+                lease_interest_rate_margin: config.lease_interest_rate_margin,
+                lease_position_spec: config.lease_position_spec,
+                lease_due_period: config.lease_due_period,
+                lease_asset_specs: config.lease_asset_specs,
+                open_interest_caps: OpenInterestCaps {
+                    per_currency: BTreeMap::from([(
+                        currency::dto::<LeaseCurrency, _>(),
+                        cap.into(),
+                    )]),
+                    per_customer: None,
+                },
+            },
+        )
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+}
+
+fn open_limit(expiry: Timestamp) -> OpenLimit {
+    OpenLimit {
+        price: price::total_of(LeaseCoin::new(2))
+            .is(LpnCoin::new(1))
+            .into(),
+        expiry,
+    }
+}
+
+fn open_pending(test_case: &mut LeaseTestCase, open_at: OpenLimit) -> Addr {
+    () = test_case
+        .app
+        .execute(
+            testing::user(USER),
+            test_case.address_book.leaser().clone(),
+            &leaser::msg::ExecuteMsg::OpenLease {
+                currency: currency::dto::<LeaseCurrency, _>(),
+                max_ltd: None,
+                max_slippage: None,
+                open_at: Some(open_at),
+                basket: None,
+            },
+            &[common::cwcoin(DOWNPAYMENT)],
+        )
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    leaser_mod::expect_a_lease(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        testing::user(USER),
+    )
+}
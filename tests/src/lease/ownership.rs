@@ -0,0 +1,145 @@
+use lease::{api::ExecuteMsg, error::ContractError};
+use sdk::{cosmwasm_std::Addr, cw_multi_test::AppResponse, testing};
+
+use crate::common::{
+    leaser as leaser_mod,
+    test_case::{app::App, response::ResponseWithInterChainMsgs},
+    USER,
+};
+
+use super::{LeaseTestCase, PaymentCurrency, DOWNPAYMENT};
+
+const NEW_OWNER: &str = "new_owner";
+
+#[test]
+fn transfer() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let customer = testing::user(USER);
+    let new_owner = testing::user(NEW_OWNER);
+
+    () = try_transfer(
+        &mut test_case.app,
+        customer.clone(),
+        lease.clone(),
+        new_owner.clone(),
+        false,
+    )
+    .unwrap()
+    .ignore_response()
+    .unwrap_response();
+
+    assert_owner(&test_case, &lease, customer.clone(), new_owner.clone());
+
+    let err = try_transfer(
+        &mut test_case.app,
+        customer.clone(),
+        lease.clone(),
+        customer,
+        false,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(ContractError::Unauthorized(_))
+    ));
+}
+
+#[test]
+fn transfer_to_owner() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let customer = testing::user(USER);
+
+    let err = try_transfer(
+        &mut test_case.app,
+        customer.clone(),
+        lease.clone(),
+        customer.clone(),
+        false,
+    )
+    .unwrap_err();
+    assert_eq!(
+        Some(&ContractError::AlreadyOwner(customer)),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+#[test]
+fn transfer_accept() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let customer = testing::user(USER);
+    let new_owner = testing::user(NEW_OWNER);
+
+    let err = try_accept(&mut test_case.app, new_owner.clone(), lease.clone()).unwrap_err();
+    assert_eq!(
+        Some(&ContractError::NoPendingOwnershipTransfer()),
+        err.downcast_ref::<ContractError>()
+    );
+
+    () = try_transfer(
+        &mut test_case.app,
+        customer.clone(),
+        lease.clone(),
+        new_owner.clone(),
+        true,
+    )
+    .unwrap()
+    .ignore_response()
+    .unwrap_response();
+    leaser_mod::assert_lease(
+        &test_case.app,
+        test_case.address_book.leaser().clone(),
+        customer.clone(),
+        &lease,
+    );
+
+    let err = try_accept(&mut test_case.app, customer.clone(), lease.clone()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(ContractError::Unauthorized(_))
+    ));
+
+    () = try_accept(&mut test_case.app, new_owner.clone(), lease.clone())
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    assert_owner(&test_case, &lease, customer, new_owner);
+}
+
+fn assert_owner(test_case: &LeaseTestCase, lease: &Addr, customer: Addr, new_owner: Addr) {
+    let leaser = test_case.address_book.leaser().clone();
+    leaser_mod::assert_no_leases(&test_case.app, leaser.clone(), customer);
+    assert_eq!(
+        lease,
+        &leaser_mod::expect_a_lease(&test_case.app, leaser, new_owner)
+    );
+}
+
+fn try_transfer(
+    app: &mut App,
+    sender: Addr,
+    lease: Addr,
+    new_owner: Addr,
+    require_accept: bool,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    app.execute(
+        sender,
+        lease,
+        &ExecuteMsg::TransferOwnership {
+            new_owner,
+            require_accept,
+        },
+        &[],
+    )
+}
+
+fn try_accept(
+    app: &mut App,
+    sender: Addr,
+    lease: Addr,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    app.execute(sender, lease, &ExecuteMsg::AcceptOwnership(), &[])
+}
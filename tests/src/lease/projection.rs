@@ -0,0 +1,46 @@
+use finance::price::Price;
+use lease::api::query::{PriceLevels, ProjectionResponse, QueryMsg, StateResponse};
+use sdk::cosmwasm_std::Addr;
+
+use super::{LeaseCurrency, LeaseTestCase, LpnCurrency, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn price_levels() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let projection = query_projection(&test_case, lease.clone());
+    let StateResponse::Opened { amount, .. } = super::state_query(&test_case, lease) else {
+        unreachable!()
+    };
+    assert_eq!(amount, projection.amount);
+    assert!(!projection.total_due.is_zero());
+
+    let PriceLevels {
+        first_liq_warn,
+        second_liq_warn,
+        third_liq_warn,
+        liquidation,
+        take_profit,
+        stop_loss,
+    } = projection
+        .price_levels
+        .expect("the lease should have a debt");
+    assert!(price(&first_liq_warn) > price(&second_liq_warn));
+    assert!(price(&second_liq_warn) > price(&third_liq_warn));
+    assert!(price(&third_liq_warn) > price(&liquidation));
+    assert_eq!(None, take_profit);
+    assert_eq!(None, stop_loss);
+}
+
+fn query_projection(test_case: &LeaseTestCase, lease: Addr) -> ProjectionResponse {
+    test_case
+        .app
+        .query()
+        .query_wasm_smart(lease, &QueryMsg::Projection { due_projection: 0 })
+        .unwrap()
+}
+
+fn price(dto: &lease::api::AssetPriceDTO) -> Price<LeaseCurrency, LpnCurrency> {
+    dto.try_into().unwrap()
+}
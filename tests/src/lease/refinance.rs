@@ -0,0 +1,236 @@
+use currency::CurrencyDef;
+use finance::price;
+use lease::{
+    api::{query::StateResponse, ExecuteMsg},
+    error::ContractError,
+};
+use lpp::{borrow::InterestRate, msg::QueryLoanResponse};
+use platform::contract::Code;
+use sdk::{
+    cosmwasm_std::{coin, Addr},
+    cw_multi_test::AppResponse,
+    testing,
+};
+
+use crate::common::{
+    leaser::Instantiator as LeaserInstantiator,
+    lpp::{Instantiator as LppInstantiator, LppQueryMsg},
+    test_case::{app::App, response::ResponseWithInterChainMsgs, TestCase},
+    ADDON_OPTIMAL_INTEREST_RATE, BASE_INTEREST_RATE, USER, UTILIZATION_OPTIMAL,
+};
+
+use super::{LeaseTestCase, LpnCoin, LpnCurrency, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn refinance() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    test_case
+        .app
+        .time_shift(LeaserInstantiator::REPAYMENT_PERIOD);
+    super::feed_price(&mut test_case);
+    pay_interest_due(&mut test_case, lease.clone());
+
+    let lpp = test_case.address_book.lpp().clone();
+    let new_lpp = instantiate_lpp(&mut test_case, true);
+    let now = crate::block_time(&test_case);
+    let loan = query_loan(&test_case, lpp.clone(), lease.clone()).unwrap();
+    assert!(loan.interest_due(&now).is_zero());
+
+    () = try_refinance(
+        &mut test_case.app,
+        testing::user(USER),
+        lease.clone(),
+        new_lpp.clone(),
+    )
+    .unwrap()
+    .ignore_response()
+    .unwrap_response();
+
+    assert_eq!(None, query_loan(&test_case, lpp, lease.clone()));
+    let new_loan = query_loan(&test_case, new_lpp, lease.clone()).unwrap();
+    assert_eq!(loan.principal_due, new_loan.principal_due);
+    assert_eq!(now, new_loan.interest_paid);
+
+    let StateResponse::Opened {
+        principal_due,
+        overdue_margin,
+        overdue_interest,
+        due_margin,
+        due_interest,
+        ..
+    } = super::state_query(&test_case, lease)
+    else {
+        unreachable!()
+    };
+    assert_eq!(
+        loan.principal_due,
+        LpnCoin::try_from(principal_due).unwrap()
+    );
+    assert!(overdue_margin.is_zero());
+    assert!(overdue_interest.is_zero());
+    assert!(due_margin.is_zero());
+    assert!(due_interest.is_zero());
+}
+
+#[test]
+fn refinance_interest_due() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    test_case
+        .app
+        .time_shift(LeaserInstantiator::REPAYMENT_PERIOD);
+    let new_lpp = instantiate_lpp(&mut test_case, true);
+
+    let err = try_refinance(&mut test_case.app, testing::user(USER), lease, new_lpp).unwrap_err();
+    assert_eq!(
+        Some(&ContractError::RefinanceInterestDue()),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+#[test]
+fn refinance_interest_overdue() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    test_case
+        .app
+        .time_shift(LeaserInstantiator::REPAYMENT_PERIOD + LeaserInstantiator::REPAYMENT_PERIOD);
+    let new_lpp = instantiate_lpp(&mut test_case, true);
+
+    let err = try_refinance(&mut test_case.app, testing::user(USER), lease, new_lpp).unwrap_err();
+    assert_eq!(
+        Some(&ContractError::RefinanceInterestDue()),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+#[test]
+fn refinance_unauthorized() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let new_lpp = instantiate_lpp(&mut test_case, true);
+
+    let err =
+        try_refinance(&mut test_case.app, testing::user("another"), lease, new_lpp).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<ContractError>(),
+        Some(ContractError::Unauthorized(_))
+    ));
+}
+
+#[test]
+fn refinance_same_lpp() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let lpp = test_case.address_book.lpp().clone();
+
+    let err =
+        try_refinance(&mut test_case.app, testing::user(USER), lease, lpp.clone()).unwrap_err();
+    assert_eq!(
+        Some(&ContractError::RefinanceLppMismatch(lpp)),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+#[test]
+fn refinance_another_code() {
+    let mut test_case = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let new_lpp = instantiate_lpp(&mut test_case, false);
+
+    let err = try_refinance(
+        &mut test_case.app,
+        testing::user(USER),
+        lease,
+        new_lpp.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        Some(&ContractError::RefinanceLppMismatch(new_lpp)),
+        err.downcast_ref::<ContractError>()
+    );
+}
+
+fn instantiate_lpp(test_case: &mut LeaseTestCase, same_code: bool) -> Addr {
+    let lease_code = test_case.address_book.lease_code();
+    let init_balance = [coin(
+        5_000_000_000_000_000_000_000_000_000,
+        LpnCurrency::bank(),
+    )];
+    let borrow_rate = InterestRate::new(
+        BASE_INTEREST_RATE,
+        UTILIZATION_OPTIMAL,
+        ADDON_OPTIMAL_INTEREST_RATE,
+    )
+    .unwrap();
+
+    if same_code {
+        let lpp_code = Code::unchecked(
+            test_case
+                .app
+                .query()
+                .query_wasm_contract_info(test_case.address_book.lpp().clone())
+                .unwrap()
+                .code_id,
+        );
+        LppInstantiator::instantiate_code::<LpnCurrency>(
+            &mut test_case.app,
+            lpp_code,
+            lease_code,
+            &init_balance,
+            borrow_rate,
+            TestCase::DEFAULT_LPP_MIN_UTILIZATION,
+        )
+    } else {
+        LppInstantiator::instantiate_default::<LpnCurrency>(
+            &mut test_case.app,
+            lease_code,
+            &init_balance,
+            borrow_rate,
+            TestCase::DEFAULT_LPP_MIN_UTILIZATION,
+        )
+    }
+}
+
+/// Pay all interest due and a small part of the principal
+fn pay_interest_due(test_case: &mut LeaseTestCase, lease: Addr) {
+    let StateResponse::Opened {
+        overdue_margin,
+        overdue_interest,
+        due_margin,
+        due_interest,
+        ..
+    } = super::state_query(test_case, lease.clone())
+    else {
+        unreachable!()
+    };
+    let interest_due = [overdue_margin, overdue_interest, due_margin, due_interest]
+        .into_iter()
+        .map(|due| LpnCoin::try_from(due).unwrap())
+        .sum::<LpnCoin>();
+    assert!(!interest_due.is_zero());
+
+    let payment = price::total(
+        interest_due + LpnCoin::new(100),
+        super::price_lpn_of().inv(),
+    );
+    super::repay::repay(test_case, lease, payment);
+}
+
+fn query_loan(test_case: &LeaseTestCase, lpp: Addr, lease: Addr) -> QueryLoanResponse<LpnCurrency> {
+    test_case
+        .app
+        .query()
+        .query_wasm_smart(lpp, &LppQueryMsg::Loan { lease_addr: lease })
+        .unwrap()
+}
+
+fn try_refinance(
+    app: &mut App,
+    sender: Addr,
+    lease: Addr,
+    lpp: Addr,
+) -> anyhow::Result<ResponseWithInterChainMsgs<'_, AppResponse>> {
+    app.execute(sender, lease, &ExecuteMsg::Refinance { lpp }, &[])
+}
@@ -13,6 +13,7 @@ use finance::{
     zero::Zero,
 };
 use lease::api::{
+    open::LeaseKind,
     query::{ClosePolicy, StateResponse},
     ExecuteMsg,
 };
@@ -154,6 +155,7 @@ fn full_repay() {
     );
     let expected_result = StateResponse::Paid {
         amount: LeaseCoin::into(expected_amount),
+        basket: vec![],
         in_progress: None,
     };
     let query_result = super::state_query(&test_case, lease_address);
@@ -173,19 +175,28 @@ fn full_repay_with_max_ltd() {
         downpayment,
         Price::<PaymentCurrency, LeaseCurrency>::identity(),
     ));
+    let principal_due: LpnCoin = price::total(percent.of(downpayment), super::price_lpn_of());
+    let validity = Timestamp::from_nanos(1537237459879305533);
     let expected_result = StateResponse::Opened {
+        kind: LeaseKind::Long,
         amount: lease_amount.into(),
+        basket: vec![],
         loan_interest_rate: Percent::from_permille(70),
         margin_interest_rate: Percent::from_permille(30),
-        principal_due: price::total(percent.of(downpayment), super::price_lpn_of()).into(),
+        principal_due: principal_due.into(),
         overdue_margin: LpnCoin::ZERO.into(),
         overdue_interest: LpnCoin::ZERO.into(),
         overdue_collect_in: LeaserInstantiator::REPAYMENT_PERIOD,
         due_margin: LpnCoin::ZERO.into(),
         due_interest: LpnCoin::ZERO.into(),
         due_projection: Duration::default(),
-        close_policy: ClosePolicy::default(),
-        validity: Timestamp::from_nanos(1537237459879305533),
+        close_policy: Box::new(ClosePolicy::default()),
+        liability: Some(Box::new(super::expected_steady_liability(
+            lease_amount,
+            principal_due,
+            validity,
+        ))),
+        validity,
         in_progress: None,
     };
     let query_result = super::state_query(&test_case, lease_address.clone());
@@ -203,6 +214,7 @@ fn full_repay_with_max_ltd() {
     );
     let expected_result = StateResponse::Paid {
         amount: LeaseCoin::into(expected_amount),
+        basket: vec![],
         in_progress: None,
     };
     let query_result = super::state_query(&test_case, lease_address);
@@ -255,6 +267,7 @@ fn full_repay_with_excess() {
                 price::total(downpayment + borrowed, price_lpn_of()),
                 price_lpn_of().inv(),
             )),
+            basket: vec![],
             in_progress: None,
         }
     );
@@ -0,0 +1,33 @@
+use lease::api::{open::LeaseKind, query::StateResponse};
+use leaser::msg::{OpenInterestCaps, SudoMsg};
+
+use crate::common::leaser::Instantiator as LeaserInstantiator;
+
+use super::{LeaseTestCase, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn open_short() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    () = test_case
+        .app
+        .sudo(
+            test_case.address_book.leaser().clone(),
+            &SudoMsg::Config {
+                lease_interest_rate_margin: LeaserInstantiator::INTEREST_RATE_MARGIN,
+                lease_position_spec: LeaserInstantiator::position_spec()
+                    .with_kind(LeaseKind::Short),
+                lease_due_period: LeaserInstantiator::REPAYMENT_PERIOD,
+                lease_asset_specs: vec![],
+                open_interest_caps: OpenInterestCaps::default(),
+            },
+        )
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let StateResponse::Opened { kind, .. } = super::state_query(&test_case, lease) else {
+        unreachable!()
+    };
+    assert_eq!(LeaseKind::Short, kind);
+}
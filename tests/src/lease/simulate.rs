@@ -0,0 +1,50 @@
+use finance::{fraction::Fraction, ratio::Rational};
+use lease::api::query::{QueryMsg, SimulateAction, SimulationResponse};
+use sdk::cosmwasm_std::Addr;
+
+use super::{LeaseTestCase, PaymentCoin, PaymentCurrency, DOWNPAYMENT};
+
+#[test]
+fn repay_accepted() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let borrowed = super::quote_borrow(&test_case, DOWNPAYMENT);
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+    let state_before = super::state_query(&test_case, lease.clone());
+
+    let payment: PaymentCoin = Fraction::<PaymentCoin>::of(
+        &Rational::new(1, 2),
+        super::create_payment_coin(borrowed.into()),
+    );
+    let SimulationResponse::Accepted(outcome) = simulate(
+        &test_case,
+        lease.clone(),
+        SimulateAction::Repay(payment.into()),
+    ) else {
+        unreachable!("the repayment should be accepted")
+    };
+    assert!(!outcome.principal_paid.is_zero());
+    assert!(outcome.change.is_zero());
+    assert!(outcome.ltv.is_some());
+
+    assert_eq!(state_before, super::state_query(&test_case, lease));
+}
+
+#[test]
+fn repay_rejected() {
+    let mut test_case: LeaseTestCase = super::create_test_case::<PaymentCurrency>();
+    let lease = super::open_lease(&mut test_case, DOWNPAYMENT, None);
+
+    let payment = PaymentCoin::new(1);
+    assert!(matches!(
+        simulate(&test_case, lease, SimulateAction::Repay(payment.into())),
+        SimulationResponse::Rejected { .. }
+    ));
+}
+
+fn simulate(test_case: &LeaseTestCase, lease: Addr, action: SimulateAction) -> SimulationResponse {
+    test_case
+        .app
+        .query()
+        .query_wasm_smart(lease, &QueryMsg::Simulate { action })
+        .unwrap()
+}
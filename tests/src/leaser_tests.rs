@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use currencies::{
     testing::{LeaseC1, LeaseC2, LeaseC3, LeaseC6, LeaseC7, PaymentC1},
     LeaseGroup as LeaseCurrencies, Lpn, Lpns, PaymentGroup as PriceCurrencies,
@@ -5,8 +7,15 @@ use currencies::{
 use currency::{CurrencyDef, MemberOf};
 use finance::{
     coin::{Amount, Coin},
+    fraction::Fraction,
+    interest,
     percent::Percent,
     price::{total, total_of, Price},
+    test,
+};
+use leaser::{
+    error::ContractError,
+    msg::{ConfigResponse, OpenInterestCaps, QueryMsg, QuoteResponse, SudoMsg},
 };
 use sdk::{
     cosmwasm_ext::Response,
@@ -89,6 +98,9 @@ fn open_multiple_loans() {
                 &leaser::msg::ExecuteMsg::OpenLease {
                     currency: currency::dto::<LeaseCurrency, _>(),
                     max_ltd: None,
+                    max_slippage: None,
+                    open_at: None,
+                    basket: None,
                 },
                 &[cwcoin::<Lpn, _>(75)],
             )
@@ -116,6 +128,9 @@ fn open_multiple_loans() {
             &leaser::msg::ExecuteMsg::OpenLease {
                 currency: currency::dto::<LeaseCurrency, _>(),
                 max_ltd: None,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
             },
             &[cwcoin::<Lpn, _>(78)],
         )
@@ -192,6 +207,38 @@ fn test_quote() {
 
     assert_eq!(resp.annual_interest_rate_margin, Percent::from_permille(30),);
 
+    assert!(!resp.borrow_swap_path.is_empty());
+    assert_eq!(resp.downpayment_swap_path, resp.borrow_swap_path);
+    assert_eq!(
+        Price::<LeaseCurrency, Lpn>::try_from(resp.asset_price),
+        Ok(price_lease_lpn)
+    );
+    assert_eq!(
+        resp.initial_ltv,
+        Percent::from_ratio(borrow, downpayment + borrow)
+    );
+    assert_eq!(
+        Price::<LeaseCurrency, Lpn>::try_from(resp.liquidation_price),
+        Ok(total_of(
+            leaser_mod::Instantiator::liability()
+                .max()
+                .of(total(downpayment + borrow, price_lease_lpn.inv()))
+        )
+        .is(borrow))
+    );
+    assert_eq!(
+        resp.first_due_interest.try_into(),
+        Ok(interest::interest(
+            Percent::from_permille(72),
+            borrow,
+            leaser_mod::Instantiator::REPAYMENT_PERIOD
+        ) + interest::interest(
+            Percent::from_permille(30),
+            borrow,
+            leaser_mod::Instantiator::REPAYMENT_PERIOD
+        ))
+    );
+
     let leaser = test_case.address_book.leaser().clone();
     let resp = leaser_mod::query_quote::<Downpayment, LeaseCurrency>(
         &test_case.app,
@@ -207,6 +254,117 @@ fn test_quote() {
     );
 }
 
+#[test]
+fn open_interest_cap_exceeded() {
+    type Lpn = TheCurrency;
+    type LeaseCurrency = LeaseC7;
+
+    let user_addr = testing::user(USER);
+    let mut test_case = TestCaseBuilder::<Lpn>::new()
+        .init_lpp(
+            None,
+            BASE_INTEREST_RATE,
+            UTILIZATION_OPTIMAL,
+            ADDON_OPTIMAL_INTEREST_RATE,
+            TestCase::DEFAULT_LPP_MIN_UTILIZATION,
+        )
+        .init_time_alarms()
+        .init_protocols_registry(Registry::NoProtocol)
+        .init_oracle(None)
+        .init_treasury()
+        .init_profit(24)
+        .init_reserve()
+        .init_leaser()
+        .into_generic();
+
+    let downpayment = Coin::<Lpn>::new(100);
+    test_case.send_funds_from_admin(user_addr.clone(), &[cwcoin(downpayment)]);
+
+    let feeder = setup_feeder(&mut test_case);
+    oracle_mod::feed_price(
+        &mut test_case,
+        feeder,
+        Coin::<LeaseCurrency>::new(2),
+        Coin::<Lpn>::new(1),
+    );
+
+    let leaser = test_case.address_book.leaser().clone();
+    let borrow: Coin<Lpn> = leaser_mod::query_quote::<Lpn, LeaseCurrency>(
+        &test_case.app,
+        leaser.clone(),
+        downpayment,
+        None,
+    )
+    .borrow
+    .try_into()
+    .unwrap();
+
+    let ConfigResponse { config } = test_case
+        .app
+        .query()
+        .query_wasm_smart(leaser.clone(), &QueryMsg::Config {})
+        .unwrap();
+    () = test_case
+        .app
+        .sudo(
+            leaser.clone(),
+            &SudoMsg::Config {
+                lease_interest_rate_margin: config.lease_interest_rate_margin,
+                lease_position_spec: config.lease_position_spec,
+                lease_due_period: config.lease_due_period,
+                lease_asset_specs: config.lease_asset_specs,
+                open_interest_caps: OpenInterestCaps {
+                    per_currency: BTreeMap::from([(
+                        currency::dto::<LeaseCurrency, _>(),
+                        (borrow - Coin::new(1)).into(),
+                    )]),
+                    per_customer: None,
+                },
+            },
+        )
+        .unwrap()
+        .ignore_response()
+        .unwrap_response();
+
+    let quote_err = test_case
+        .app
+        .query()
+        .query_wasm_smart::<QuoteResponse>(
+            leaser.clone(),
+            &QueryMsg::Quote {
+                downpayment: test::funds::<_, Lpn>(downpayment.into()),
+                lease_asset: currency::dto::<LeaseCurrency, _>(),
+                max_ltd: None,
+                customer: None,
+            },
+        )
+        .unwrap_err();
+    let cap_exceeded = ContractError::OpenInterestCapExceeded(
+        currency::dto::<LeaseCurrency, LeaseCurrencies>().to_string(),
+    );
+    assert!(quote_err.to_string().contains(&cap_exceeded.to_string()));
+
+    let open_err = test_case
+        .app
+        .execute(
+            user_addr,
+            leaser,
+            &leaser::msg::ExecuteMsg::OpenLease {
+                currency: currency::dto::<LeaseCurrency, _>(),
+                max_ltd: None,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
+            },
+            &[cwcoin(downpayment)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        Some(&cap_exceeded),
+        open_err.downcast_ref::<ContractError>()
+    );
+}
+
 fn common_quote_with_conversion(
     downpayment: Coin<PaymentC1>,
     borrow_after_mul2: Coin<TheCurrency>,
@@ -454,6 +612,9 @@ fn open_loans_lpp_fails() {
             &leaser::msg::ExecuteMsg::OpenLease {
                 currency: currency::dto::<LeaseCurrency, _>(),
                 max_ltd: None,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
             },
             &[downpayment],
         )
@@ -551,6 +712,9 @@ where
             &leaser::msg::ExecuteMsg::OpenLease {
                 currency: currency::dto::<LeaseC, _>(),
                 max_ltd: None,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
             },
             &[cwcoin(downpayment)],
         )
@@ -616,6 +780,9 @@ fn open_loans_insufficient_amount(downpayment: Amount) {
             &leaser::msg::ExecuteMsg::OpenLease {
                 currency: currency::dto::<LeaseCurrency, _>(),
                 max_ltd: None,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
             },
             &[downpayment_amount],
         )
@@ -292,6 +292,9 @@ fn open_lease<ProtocolsRegistry, Treasury, Profit, Reserve, Lpp, Oracle, TimeAla
             &leaser::msg::ExecuteMsg::OpenLease {
                 currency: currency::dto::<LeaseCurrency, _>(),
                 max_ltd: None,
+                max_slippage: None,
+                open_at: None,
+                basket: None,
             },
             &[cw_coin(downpayment)],
         )